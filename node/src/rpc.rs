@@ -33,6 +33,7 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
         C: Send + Sync + 'static,
         C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
         C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+        C::Api: activity_log_rpc::ActivityLogRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: posts_rpc::PostsRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: profile_follows_rpc::ProfileFollowsRuntimeApi<Block, AccountId>,
         C::Api: profiles_rpc::ProfilesRuntimeApi<Block, AccountId, BlockNumber>,
@@ -46,6 +47,7 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
+    use activity_log_rpc::{ActivityLog, ActivityLogApi};
     use posts_rpc::{Posts, PostsApi};
     use profile_follows_rpc::{ProfileFollows, ProfileFollowsApi};
     use profiles_rpc::{Profiles, ProfilesApi};
@@ -73,7 +75,9 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
 
     io.extend_with(ReactionsApi::to_delegate(Reactions::new(client.clone())));
 
-    io.extend_with(RolesApi::to_delegate(Roles::new(client)));
+    io.extend_with(RolesApi::to_delegate(Roles::new(client.clone())));
+
+    io.extend_with(ActivityLogApi::to_delegate(ActivityLog::new(client)));
 
     io
 }
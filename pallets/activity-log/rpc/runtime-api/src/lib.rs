@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use pallet_activity_log::rpc::FlatActivity;
+
+sp_api::decl_runtime_apis! {
+    pub trait ActivityLogApi<AccountId, BlockNumber> where
+        AccountId: Codec,
+        BlockNumber: Codec
+    {
+        fn get_recent_activities(limit: u32) -> Vec<FlatActivity<AccountId, BlockNumber>>;
+    }
+}
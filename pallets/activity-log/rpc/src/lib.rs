@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+
+use pallet_activity_log::rpc::FlatActivity;
+use pallet_utils::rpc::map_rpc_error;
+pub use activity_log_runtime_api::ActivityLogApi as ActivityLogRuntimeApi;
+
+#[rpc]
+pub trait ActivityLogApi<BlockHash, AccountId, BlockNumber> {
+    /// Get the most recent social activities, newest first.
+    #[rpc(name = "activityLog_getRecentActivities")]
+    fn get_recent_activities(
+        &self,
+        at: Option<BlockHash>,
+        limit: u32,
+    ) -> Result<Vec<FlatActivity<AccountId, BlockNumber>>>;
+}
+
+pub struct ActivityLog<C, M> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> ActivityLog<C, M> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, BlockNumber> ActivityLogApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
+    for ActivityLog<C, Block>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    BlockNumber: Codec,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: ActivityLogRuntimeApi<Block, AccountId, BlockNumber>,
+{
+    fn get_recent_activities(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        limit: u32,
+    ) -> Result<Vec<FlatActivity<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_recent_activities(&at, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+}
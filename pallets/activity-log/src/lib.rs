@@ -0,0 +1,130 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use frame_support::{decl_module, decl_storage, traits::Get};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use frame_system::{self as system};
+
+use pallet_posts::{Post, PostHooks, PostUpdate};
+use pallet_reactions::{ReactionHooks, ReactionId, ReactionKind};
+use pallet_spaces::{Space, SpaceHooks, SpaceUpdate};
+use pallet_utils::{PostId, SpaceId};
+
+pub mod rpc;
+
+/// A reference to an entity that an activity is about.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ActivityEntity {
+    Space(SpaceId),
+    Post(PostId),
+}
+
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ActivityAction {
+    SpaceCreated,
+    SpaceUpdated,
+    /// A post, comment or sharing post was created.
+    PostCreated,
+    PostUpdated,
+    PostReacted,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Activity<T: Config> {
+    pub account: T::AccountId,
+    pub entity: ActivityEntity,
+    pub action: ActivityAction,
+    pub block: T::BlockNumber,
+}
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_spaces::Config
+    + pallet_posts::Config
+    + pallet_reactions::Config
+{
+    /// Max number of recent activities kept in the log.
+    /// When the log is full, the oldest activity is overwritten by a new one.
+    type MaxActivities: Get<u32>;
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Config> as ActivityLogModule {
+        /// The total number of activities ever logged.
+        /// The next activity is written to the slot `ActivitiesCount % MaxActivities`.
+        pub ActivitiesCount get(fn activities_count): u64;
+
+        /// A ring buffer of recent activities.
+        pub ActivityBySlot get(fn activity_by_slot):
+            map hasher(twox_64_concat) u32 => Option<Activity<T>>;
+    }
+}
+
+// The pallet's dispatchable functions.
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+    const MaxActivities: u32 = T::MaxActivities::get();
+  }
+}
+
+impl<T: Config> Module<T> {
+
+    fn log_activity(account: &T::AccountId, entity: ActivityEntity, action: ActivityAction) {
+        let max_activities = T::MaxActivities::get();
+        if max_activities == 0 {
+            return;
+        }
+
+        let count = Self::activities_count();
+        let slot = (count % max_activities as u64) as u32;
+
+        ActivityBySlot::<T>::insert(slot, Activity {
+            account: account.clone(),
+            entity,
+            action,
+            block: <system::Pallet<T>>::block_number(),
+        });
+        ActivitiesCount::put(count.saturating_add(1));
+    }
+}
+
+impl<T: Config> SpaceHooks<T> for Module<T> {
+    fn on_space_created(owner: &T::AccountId, space: &Space<T>) {
+        Self::log_activity(owner, ActivityEntity::Space(space.id), ActivityAction::SpaceCreated);
+    }
+
+    fn on_space_updated(editor: &T::AccountId, space: &Space<T>, _old_data: &SpaceUpdate) {
+        Self::log_activity(editor, ActivityEntity::Space(space.id), ActivityAction::SpaceUpdated);
+    }
+}
+
+// Hidden posts, including posts scheduled for a later publication, are not logged,
+// so that the log does not reveal them before they become visible.
+impl<T: Config> PostHooks<T> for Module<T> {
+    fn on_post_created(account: &T::AccountId, post: &Post<T>) {
+        if !post.hidden {
+            Self::log_activity(account, ActivityEntity::Post(post.id), ActivityAction::PostCreated);
+        }
+    }
+
+    fn on_post_updated(account: &T::AccountId, post: &Post<T>, _old_data: &PostUpdate) {
+        if !post.hidden {
+            Self::log_activity(account, ActivityEntity::Post(post.id), ActivityAction::PostUpdated);
+        }
+    }
+}
+
+impl<T: Config> ReactionHooks<T> for Module<T> {
+    fn on_reaction_created(reactor: &T::AccountId, post_id: PostId, _reaction_id: ReactionId, _kind: ReactionKind) {
+        Self::log_activity(reactor, ActivityEntity::Post(post_id), ActivityAction::PostReacted);
+    }
+}
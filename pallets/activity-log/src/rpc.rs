@@ -0,0 +1,43 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+use frame_support::traits::Get;
+
+use crate::{Activity, ActivityAction, ActivityEntity, Config, Module};
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatActivity<AccountId, BlockNumber> {
+    pub account: AccountId,
+    pub entity: ActivityEntity,
+    pub action: ActivityAction,
+    pub block: BlockNumber,
+}
+
+impl<T: Config> From<Activity<T>> for FlatActivity<T::AccountId, T::BlockNumber> {
+    fn from(from: Activity<T>) -> Self {
+        let Activity { account, entity, action, block } = from;
+
+        Self { account, entity, action, block }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Get up to `limit` most recent activities, newest first.
+    pub fn get_recent_activities(limit: u32) -> Vec<FlatActivity<T::AccountId, T::BlockNumber>> {
+        let max_activities = T::MaxActivities::get() as u64;
+        let count = Self::activities_count();
+        let available = count.min(max_activities).min(limit as u64);
+
+        (1..=available)
+            .filter_map(|i| {
+                let slot = ((count - i) % max_activities) as u32;
+                Self::activity_by_slot(slot)
+            })
+            .map(|activity| activity.into())
+            .collect()
+    }
+}
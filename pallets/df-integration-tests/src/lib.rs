@@ -4,17 +4,18 @@ mod tests {
     use sp_io::TestExternalities;
 
     use sp_runtime::{
-        traits::{BlakeTwo256, IdentityLookup, Zero},
-        testing::Header,
+        traits::{BlakeTwo256, Hash as HashT, IdentityLookup, SignedExtension, Zero},
+        testing::{Header, TestSignature, UintAuthorityId},
+        transaction_validity::{InvalidTransaction, TransactionValidity},
         Storage,
     };
 
     use frame_support::{
         assert_ok, assert_noop,
         parameter_types,
-        dispatch::{DispatchResult, DispatchError},
-        storage::StorageMap,
-        traits::Everything,
+        dispatch::{DispatchResult, DispatchResultWithPostInfo, DispatchError},
+        storage::{StorageMap, StorageValue},
+        traits::{Everything, OnInitialize},
     };
     use frame_system as system;
 
@@ -23,19 +24,33 @@ mod tests {
         SpacePermission as SP,
         SpacePermissions,
     };
-    use pallet_posts::{Post, PostUpdate, PostExtension, Comment, Error as PostsError};
-    use pallet_profiles::{ProfileUpdate, Error as ProfilesError};
+    use pallet_posts::{
+        Post, PostUpdate, PostExtension, PostOptions, PostProvenance, Comment, Error as PostsError, CheckSpamScore,
+        spam_score::{SPAM_SCORE_FOR_NEW_ACCOUNT, SPAM_SCORE_PER_MODERATION_FLAG, SPAM_SCORE_PER_RECENT_POST},
+    };
+    use pallet_profiles::{ProfileUpdate, ProfileSettingsBundle, Badge, Error as ProfilesError};
     use pallet_profile_follows::Error as ProfileFollowsError;
     use pallet_reactions::{ReactionId, ReactionKind, Error as ReactionsError};
-    use pallet_spaces::{SpaceById, SpaceUpdate, Error as SpacesError, SpacesSettings};
+    use pallet_roles::Error as RolesError;
+    use pallet_spaces::{
+        Space, SpaceById, SpaceStats, SpaceStatsStorageSplit, SpaceUpdate,
+        Error as SpacesError, SpacesSettings,
+        migrations::{OldSpace, split_space_stats},
+        rpc::SpaceIdOrHandle,
+    };
+    use pallet_space_categories::{CategoryId, Error as SpaceCategoriesError};
     use pallet_space_follows::Error as SpaceFollowsError;
     use pallet_space_ownership::Error as SpaceOwnershipError;
+    use pallet_space_subscriptions::{SubscriptionTierId, Error as SpaceSubscriptionsError};
     use pallet_moderation::{EntityId, EntityStatus, ReportId};
+    use pallet_onboarding::OnboardingSpace;
+    use pallet_activity_log::{ActivityAction, ActivityEntity};
     use pallet_utils::{
         mock_functions::*,
         DEFAULT_MIN_HANDLE_LEN, DEFAULT_MAX_HANDLE_LEN,
         Error as UtilsError,
-        SpaceId, PostId, User, Content,
+        SpaceId, PostId, User, Content, ContentDefaults, ContentKind, WhoAndWhen,
+        MAX_LICENSE_LEN,
     };
 
     type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
@@ -50,7 +65,9 @@ mod tests {
             System: system::{Pallet, Call, Config, Storage, Event<T>},
             Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
             Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+            ActivityLog: pallet_activity_log::{Pallet, Storage},
             Moderation: pallet_moderation::{Pallet, Call, Storage, Event<T>},
+            Onboarding: pallet_onboarding::{Pallet, Call, Event<T>},
             Permissions: pallet_permissions::{Pallet, Call},
             Posts: pallet_posts::{Pallet, Call, Storage, Event<T>},
             PostHistory: pallet_post_history::{Pallet, Storage},
@@ -59,9 +76,11 @@ mod tests {
             ProfileHistory: pallet_profile_history::{Pallet, Storage},
             Reactions: pallet_reactions::{Pallet, Call, Storage, Event<T>},
             Roles: pallet_roles::{Pallet, Call, Storage, Event<T>},
+            SpaceCategories: pallet_space_categories::{Pallet, Call, Storage, Event<T>},
             SpaceFollows: pallet_space_follows::{Pallet, Call, Storage, Event<T>},
             SpaceHistory: pallet_space_history::{Pallet, Storage},
             SpaceOwnership: pallet_space_ownership::{Pallet, Call, Storage, Event<T>},
+            SpaceSubscriptions: pallet_space_subscriptions::{Pallet, Call, Storage, Event<T>},
             Spaces: pallet_spaces::{Pallet, Call, Storage, Event<T>, Config<T>},
             Utils: pallet_utils::{Pallet, Storage, Event<T>, Config<T>},
         }
@@ -134,6 +153,7 @@ mod tests {
         type Currency = Balances;
         type MinHandleLen = MinHandleLen;
         type MaxHandleLen = MaxHandleLen;
+        type DeferredJobHandler = Roles;
     }
 
     use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -144,57 +164,168 @@ mod tests {
 
     parameter_types! {
         pub const MaxCommentDepth: u32 = 10;
+        pub const MaxMutedThreadsPerAccount: u16 = 2;
+        pub const MaxScheduledPostsPerBlock: u16 = 1;
+        pub const RecentPostsPeriod: u64 = 10;
+        pub const MaxSpamScore: u32 = 40;
+        pub const PostsByAccountEraLength: u64 = 10;
+        pub const MaxPostsPerHideByAuthor: u32 = 2;
+        pub const MaxPostIdsPerReservation: u32 = 3;
+        pub const PostIdReservationDeposit: u64 = 1;
+        pub const PostIdReservationPeriod: u64 = 10;
+        pub const MaxContentProofsPerPost: u32 = 2;
+        pub const MaxSharesToNotifyOnUpdate: u32 = 1;
+        pub const MaxScheduledPostsPerAccount: u16 = 1;
+        pub const MaxPostErasPerAccount: u32 = 3;
     }
 
     impl pallet_posts::Config for TestRuntime {
         type Event = Event;
         type MaxCommentDepth = MaxCommentDepth;
         type AfterPostUpdated = PostHistory;
+        type PostHooks = (ActivityLog, ProfileBadges);
         type IsPostBlocked = Moderation;
+        type MaxMutedThreadsPerAccount = MaxMutedThreadsPerAccount;
+        type MaxScheduledPostsPerBlock = MaxScheduledPostsPerBlock;
+        type RecentPostsPeriod = RecentPostsPeriod;
+        type MaxSpamScore = MaxSpamScore;
+        type AccountCreatedAt = Profiles;
+        type AccountModerationFlags = Moderation;
+        type ContentDefaults = Profiles;
+        type PostsByAccountEraLength = PostsByAccountEraLength;
+        type MaxPostsPerHideByAuthor = MaxPostsPerHideByAuthor;
+        type MaxPostIdsPerReservation = MaxPostIdsPerReservation;
+        type PostIdReservationDeposit = PostIdReservationDeposit;
+        type PostIdReservationPeriod = PostIdReservationPeriod;
+        type MaxContentProofsPerPost = MaxContentProofsPerPost;
+        type MaxSharesToNotifyOnUpdate = MaxSharesToNotifyOnUpdate;
+        type MaxScheduledPostsPerAccount = MaxScheduledPostsPerAccount;
+        type MaxPostErasPerAccount = MaxPostErasPerAccount;
     }
 
     impl pallet_post_history::Config for TestRuntime {}
 
+    parameter_types! {
+        pub const MaxAccountsPerBatch: u16 = 3;
+    }
+
     impl pallet_profile_follows::Config for TestRuntime {
         type Event = Event;
         type BeforeAccountFollowed = ();
+        type AfterAccountFollowed = ProfileBadges;
         type BeforeAccountUnfollowed = ();
+        type IsAccountActive = Profiles;
+        type MaxAccountsPerBatch = MaxAccountsPerBatch;
+    }
+
+    parameter_types! {
+        pub const FollowersBadgeThreshold: u32 = 2;
+        pub const AccountAgeBadgeThreshold: u64 = 20;
     }
 
     impl pallet_profiles::Config for TestRuntime {
         type Event = Event;
         type AfterProfileUpdated = ProfileHistory;
+        type FollowersBadgeThreshold = FollowersBadgeThreshold;
+        type AccountAgeBadgeThreshold = AccountAgeBadgeThreshold;
+        type Signature = TestSignature;
+        type Signer = UintAuthorityId;
+        type MutedThreads = Posts;
+    }
+
+    pub struct ProfileBadges;
+
+    impl pallet_posts::PostHooks<TestRuntime> for ProfileBadges {
+        fn on_post_created(account: &AccountId, _post: &Post<TestRuntime>) {
+            Profiles::on_post_created(account);
+        }
+    }
+
+    impl pallet_profile_follows::AfterAccountFollowed<TestRuntime> for ProfileBadges {
+        fn after_account_followed(follower: &AccountId, following: &AccountId) {
+            Profiles::on_account_followed(follower, following);
+        }
     }
 
     impl pallet_profile_history::Config for TestRuntime {}
 
     impl pallet_reactions::Config for TestRuntime {
         type Event = Event;
+        type ReactionHooks = ActivityLog;
     }
 
     parameter_types! {
         pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+        pub const MaxUsersPerRoleChange: u16 = 40;
+        pub const MaxRolesPerSpace: u16 = 10;
     }
 
     impl pallet_roles::Config for TestRuntime {
         type Event = Event;
         type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+        type MaxUsersPerRoleChange = MaxUsersPerRoleChange;
+        type MaxRolesPerSpace = MaxRolesPerSpace;
         type Spaces = Spaces;
         type SpaceFollows = SpaceFollows;
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
     }
 
+    parameter_types! {
+        pub const MaxSpacesPerBatch: u16 = 3;
+    }
+
     impl pallet_space_follows::Config for TestRuntime {
         type Event = Event;
         type BeforeSpaceFollowed = ();
         type BeforeSpaceUnfollowed = ();
+        type MaxSpacesPerBatch = MaxSpacesPerBatch;
     }
 
     impl pallet_space_ownership::Config for TestRuntime {
         type Event = Event;
     }
 
+    parameter_types! {
+        pub const MaxCategories: u32 = 3;
+        pub const MaxCategoryDepth: u32 = 2;
+        pub const MaxSpacesPerCategory: u32 = 1;
+    }
+
+    impl pallet_onboarding::Config for TestRuntime {
+        type Event = Event;
+    }
+
+    impl pallet_space_categories::Config for TestRuntime {
+        type Event = Event;
+        type MaxCategories = MaxCategories;
+        type MaxCategoryDepth = MaxCategoryDepth;
+        type MaxSpacesPerCategory = MaxSpacesPerCategory;
+    }
+
+    parameter_types! {
+        pub const MaxTiersPerSpace: u32 = 2;
+        pub const MinSubscriptionPeriod: BlockNumber = 5;
+        pub const MaxRenewalsPerBlock: u32 = 1;
+        pub const MaxRenewalDelay: u32 = 1;
+    }
+
+    impl pallet_space_subscriptions::Config for TestRuntime {
+        type Event = Event;
+        type MaxTiersPerSpace = MaxTiersPerSpace;
+        type MinSubscriptionPeriod = MinSubscriptionPeriod;
+        type MaxRenewalsPerBlock = MaxRenewalsPerBlock;
+        type MaxRenewalDelay = MaxRenewalDelay;
+    }
+
+    parameter_types! {
+        pub const MaxActivities: u32 = 3;
+    }
+
+    impl pallet_activity_log::Config for TestRuntime {
+        type MaxActivities = MaxActivities;
+    }
+
     const HANDLE_DEPOSIT: u64 = 15;
 
     parameter_types! {
@@ -208,8 +339,11 @@ mod tests {
         type SpaceFollows = SpaceFollows;
         type BeforeSpaceCreated = SpaceFollows;
         type AfterSpaceUpdated = SpaceHistory;
+        type SpaceHooks = ActivityLog;
+        type WelcomePost = Posts;
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
+        type IsAccountActive = Profiles;
         type HandleDeposit = HandleDeposit;
     }
 
@@ -217,11 +351,16 @@ mod tests {
 
     parameter_types! {
         pub const DefaultAutoblockThreshold: u16 = 20;
+        pub const MaxBlocksExpiringAtBlock: u32 = 10;
+        pub const MaxPendingAppealsPerSpace: u32 = 10;
     }
 
     impl pallet_moderation::Config for TestRuntime {
         type Event = Event;
         type DefaultAutoblockThreshold = DefaultAutoblockThreshold;
+        type OnEntityStatusChanged = ();
+        type MaxBlocksExpiringAtBlock = MaxBlocksExpiringAtBlock;
+        type MaxPendingAppealsPerSpace = MaxPendingAppealsPerSpace;
     }
 
     type AccountId = u64;
@@ -385,9 +524,17 @@ mod tests {
     const SPACE1: SpaceId = 1001;
     const SPACE2: SpaceId = 1002;
 
+    const CATEGORY1: CategoryId = 1;
+    const CATEGORY2: CategoryId = 2;
+
+    const TIER1: SubscriptionTierId = 1;
+    const TIER_PRICE: u64 = 30;
+    const TIER_PERIOD: BlockNumber = 10;
+
     const POST1: PostId = 1;
     const POST2: PostId = 2;
     const POST3: PostId = 3;
+    const POST4: PostId = 4;
 
     const REACTION1: ReactionId = 1;
     const REACTION2: ReactionId = 2;
@@ -483,6 +630,7 @@ mod tests {
             space_id,
             content,
             hidden,
+            content_kind: None,
         }
     }
 
@@ -569,6 +717,62 @@ mod tests {
         )
     }
 
+    fn _create_default_space_with_welcome_post() -> DispatchResult {
+        _create_space_with_welcome_post(None, None)
+    }
+
+    fn _set_max_comment_depth(origin: Option<Origin>, max_comment_depth: Option<u32>) -> DispatchResult {
+        Spaces::set_max_comment_depth(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            max_comment_depth,
+        )
+    }
+
+    fn _create_space_with_welcome_post(
+        origin: Option<Origin>,
+        welcome_post_content: Option<Content>,
+    ) -> DispatchResult {
+        Spaces::create_space_with_welcome_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            None,
+            Some(space_handle()),
+            space_content_ipfs(),
+            None,
+            welcome_post_content.unwrap_or_else(post_content_ipfs),
+        )
+    }
+
+    fn _onboard(
+        origin: Option<Origin>,
+        first_post_content: Option<Content>,
+    ) -> DispatchResult {
+        Onboarding::onboard(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            profile_content_ipfs(),
+            OnboardingSpace {
+                parent_id: None,
+                handle: Some(space_handle()),
+                content: space_content_ipfs(),
+                permissions: None,
+            },
+            first_post_content.unwrap_or_else(post_content_ipfs),
+        )
+    }
+
+    const SYSTEM_SPACE: SpaceId = 1;
+
+    fn _force_create_system_space(origin: Option<Origin>, space_id: Option<SpaceId>) -> DispatchResultWithPostInfo {
+        Spaces::force_create_system_space(
+            origin.unwrap_or_else(Origin::root),
+            space_id.unwrap_or(SYSTEM_SPACE),
+            ACCOUNT1,
+            space_handle(),
+            space_content_ipfs(),
+            None,
+        )
+    }
+
     fn _update_space(
         origin: Option<Origin>,
         space_id: Option<SpaceId>,
@@ -620,6 +824,20 @@ mod tests {
         )
     }
 
+    fn _follow_spaces(origin: Option<Origin>, space_ids: Option<Vec<SpaceId>>) -> DispatchResult {
+        SpaceFollows::follow_spaces(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            space_ids.unwrap_or_else(|| vec![SPACE1]),
+        )
+    }
+
+    fn _unfollow_spaces(origin: Option<Origin>, space_ids: Option<Vec<SpaceId>>) -> DispatchResult {
+        SpaceFollows::unfollow_spaces(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            space_ids.unwrap_or_else(|| vec![SPACE1]),
+        )
+    }
+
     fn _create_default_post() -> DispatchResult {
         _create_post(None, None, None, None)
     }
@@ -638,6 +856,38 @@ mod tests {
         )
     }
 
+    fn _hide_posts_by_author(origin: Option<Origin>, author: AccountId, hide: bool) -> DispatchResult {
+        Posts::hide_posts_by_author(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            author,
+            hide,
+            Posts::post_ids_by_space_id(SPACE1).len() as u32,
+        )
+    }
+
+    fn _reserve_post_ids(origin: Option<Origin>, count: u32) -> DispatchResult {
+        Posts::reserve_post_ids(origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)), SPACE1, count)
+    }
+
+    fn _release_post_ids(origin: Option<Origin>) -> DispatchResult {
+        Posts::release_post_ids(origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)), SPACE1)
+    }
+
+    fn _import_post(origin: Option<Origin>, extension: Option<PostExtension>) -> DispatchResult {
+        Posts::import_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            extension.unwrap_or_else(extension_regular_post),
+            post_content_ipfs(),
+            post_provenance(),
+        )
+    }
+
+    fn post_provenance() -> PostProvenance {
+        PostProvenance { original_created_at: 1_500_000_000_000 }
+    }
+
     fn _update_post(
         origin: Option<Origin>,
         post_id: Option<PostId>,
@@ -706,6 +956,73 @@ mod tests {
         )
     }
 
+    const PUBLISH_AT: BlockNumber = 10;
+
+    fn _create_default_scheduled_post() -> DispatchResult {
+        _create_scheduled_post(None, None, None)
+    }
+
+    fn _create_scheduled_post(
+        origin: Option<Origin>,
+        extension: Option<PostExtension>,
+        publish_at: Option<BlockNumber>,
+    ) -> DispatchResult {
+        Posts::create_scheduled_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            Some(SPACE1),
+            extension.unwrap_or_else(extension_regular_post),
+            post_content_ipfs(),
+            publish_at.unwrap_or(PUBLISH_AT),
+        )
+    }
+
+    fn _update_post_content_kind(
+        origin: Option<Origin>,
+        post_id: Option<PostId>,
+        content_kind_opt: Option<Option<ContentKind>>,
+    ) -> DispatchResult {
+        Posts::update_post_content_kind(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+            content_kind_opt.unwrap_or(Some(ContentKind::Markdown)),
+        )
+    }
+
+    const COMMENTS_LOCK_AFTER: BlockNumber = 5;
+
+    fn _update_comments_lock(
+        origin: Option<Origin>,
+        lock_after_opt: Option<Option<BlockNumber>>,
+    ) -> DispatchResult {
+        Posts::update_comments_lock(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            lock_after_opt.unwrap_or(Some(COMMENTS_LOCK_AFTER)),
+        )
+    }
+
+    fn _mute_default_thread() -> DispatchResult {
+        _mute_thread(None, None)
+    }
+
+    fn _mute_thread(origin: Option<Origin>, root_post_id: Option<PostId>) -> DispatchResult {
+        Posts::mute_thread(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            root_post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn _unmute_default_thread() -> DispatchResult {
+        _unmute_thread(None, None)
+    }
+
+    fn _unmute_thread(origin: Option<Origin>, root_post_id: Option<PostId>) -> DispatchResult {
+        Posts::unmute_thread(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            root_post_id.unwrap_or(POST1),
+        )
+    }
+
     fn _create_default_post_reaction() -> DispatchResult {
         _create_post_reaction(None, None, None)
     }
@@ -769,6 +1086,14 @@ mod tests {
         )
     }
 
+    fn _toggle_post_reaction(origin: Option<Origin>, kind: Option<ReactionKind>) -> DispatchResult {
+        Reactions::toggle_post_reaction(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            POST1,
+            kind.unwrap_or_else(reaction_upvote),
+        )
+    }
+
     fn _delete_comment_reaction(
         origin: Option<Origin>,
         post_id: Option<PostId>,
@@ -803,6 +1128,74 @@ mod tests {
         )
     }
 
+    fn _update_profile_content_kind(
+        origin: Option<Origin>,
+        content_kind_opt: Option<Option<ContentKind>>,
+    ) -> DispatchResult {
+        Profiles::update_profile_content_kind(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            content_kind_opt.unwrap_or(Some(ContentKind::Markdown)),
+        )
+    }
+
+    fn _update_profile_avatar(origin: Option<Origin>, avatar_opt: Option<Option<Content>>) -> DispatchResult {
+        Profiles::update_profile_avatar(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            avatar_opt.unwrap_or_else(|| Some(profile_content_ipfs())),
+        )
+    }
+
+    fn content_defaults() -> ContentDefaults {
+        ContentDefaults {
+            license: Some(b"CC-BY-4.0".to_vec()),
+            language: Some(b"en".to_vec()),
+            hidden_by_default: true,
+        }
+    }
+
+    fn _update_content_defaults(
+        origin: Option<Origin>,
+        defaults_opt: Option<Option<ContentDefaults>>,
+    ) -> DispatchResult {
+        Profiles::update_content_defaults(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            defaults_opt.unwrap_or_else(|| Some(content_defaults())),
+        )
+    }
+
+    fn profile_settings_bundle() -> ProfileSettingsBundle<AccountId, BlockNumber> {
+        ProfileSettingsBundle {
+            exported_by: ACCOUNT1,
+            import_to: ACCOUNT2,
+            exported_at: System::block_number(),
+            settings: Profiles::profile_settings(&ACCOUNT1),
+        }
+    }
+
+    fn _import_profile_settings(
+        origin: Option<Origin>,
+        bundle: Option<ProfileSettingsBundle<AccountId, BlockNumber>>,
+        signer: Option<AccountId>,
+    ) -> DispatchResult {
+        let bundle = bundle.unwrap_or_else(profile_settings_bundle);
+        let digest = BlakeTwo256::hash_of(&bundle);
+        let signature = TestSignature(signer.unwrap_or(ACCOUNT1), digest.as_ref().to_vec());
+
+        Profiles::import_profile_settings(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            bundle,
+            signature,
+        )
+    }
+
+    fn _deactivate_account(origin: Option<Origin>) -> DispatchResult {
+        Profiles::deactivate_account(origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)))
+    }
+
+    fn _reactivate_account(origin: Option<Origin>) -> DispatchResult {
+        Profiles::reactivate_account(origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)))
+    }
+
     fn _default_follow_account() -> DispatchResult {
         _follow_account(None, None)
     }
@@ -825,6 +1218,20 @@ mod tests {
         )
     }
 
+    fn _follow_accounts(origin: Option<Origin>, accounts: Option<Vec<AccountId>>) -> DispatchResult {
+        ProfileFollows::follow_accounts(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            accounts.unwrap_or_else(|| vec![ACCOUNT1]),
+        )
+    }
+
+    fn _unfollow_accounts(origin: Option<Origin>, accounts: Option<Vec<AccountId>>) -> DispatchResult {
+        ProfileFollows::unfollow_accounts(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            accounts.unwrap_or_else(|| vec![ACCOUNT1]),
+        )
+    }
+
     fn _transfer_default_space_ownership() -> DispatchResult {
         _transfer_space_ownership(None, None, None)
     }
@@ -841,6 +1248,10 @@ mod tests {
         )
     }
 
+    fn _transfer_default_space_ownership_and_freeze() -> DispatchResult {
+        SpaceOwnership::transfer_space_ownership_and_freeze(Origin::signed(ACCOUNT1), SPACE1, ACCOUNT2)
+    }
+
     fn _accept_default_pending_ownership() -> DispatchResult {
         _accept_pending_ownership(None, None)
     }
@@ -867,6 +1278,67 @@ mod tests {
         )
     }
 
+    fn _create_default_category() -> DispatchResult {
+        _create_category(None, None)
+    }
+
+    fn _create_category(origin: Option<Origin>, parent_id: Option<Option<CategoryId>>) -> DispatchResult {
+        SpaceCategories::create_category(
+            origin.unwrap_or_else(Origin::root),
+            parent_id.unwrap_or_default(),
+            space_content_ipfs(),
+        )
+    }
+
+    fn _request_default_space_placement() -> DispatchResult {
+        _request_space_placement(None, None, None)
+    }
+
+    fn _request_space_placement(
+        origin: Option<Origin>,
+        space_id: Option<SpaceId>,
+        category_id: Option<CategoryId>,
+    ) -> DispatchResult {
+        SpaceCategories::request_space_placement(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+            category_id.unwrap_or(CATEGORY1),
+        )
+    }
+
+    fn _approve_space_placement(space_id: Option<SpaceId>) -> DispatchResult {
+        SpaceCategories::approve_space_placement(Origin::root(), space_id.unwrap_or(SPACE1))
+    }
+
+    /// Create Tier 1 in Space 1 that grants Role 1 to its subscribers
+    fn _create_default_tier() -> DispatchResult {
+        _create_tier(None, None, Some(Some(ROLE1)))
+    }
+
+    fn _create_tier(
+        origin: Option<Origin>,
+        period: Option<BlockNumber>,
+        role_id: Option<Option<RoleId>>,
+    ) -> DispatchResult {
+        SpaceSubscriptions::create_tier(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            TIER_PRICE,
+            period.unwrap_or(TIER_PERIOD),
+            role_id.unwrap_or_default(),
+            space_content_ipfs(),
+        )
+    }
+
+    /// Account 2 subscribes to Tier 1
+    fn _default_subscribe() -> DispatchResult {
+        SpaceSubscriptions::subscribe(Origin::signed(ACCOUNT2), TIER1)
+    }
+
+    fn account2_has_role1() -> bool {
+        Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).contains(&ROLE1)
+    }
+
     /* ---------------------------------------------------------------------------------------------- */
 
     // TODO: fix copy-paste from pallet_roles
@@ -988,6 +1460,7 @@ mod tests {
             entity.unwrap_or(EntityId::Post(POST1)),
             scope.unwrap_or(SPACE1),
             status_opt.unwrap_or(Some(EntityStatus::Allowed)),
+            None,
         )
     }
 
@@ -1197,64 +1670,170 @@ mod tests {
     // Spaces tests
 
     #[test]
-    fn create_space_should_work() {
+    fn create_space_with_welcome_post_should_work() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_space()); // SpaceId 1
-
-            // Check storages
-            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE1]);
-            assert_eq!(find_space_id_by_handle(space_handle()), Some(SPACE1));
-            assert_eq!(Spaces::next_space_id(), SPACE2);
+            assert_ok!(_create_default_space_with_welcome_post()); // SpaceId 1, PostId 1
 
-            // Check whether data stored correctly
             let space = Spaces::space_by_id(SPACE1).unwrap();
-
-            assert_eq!(space.created.account, ACCOUNT1);
-            assert!(space.updated.is_none());
-            assert_eq!(space.hidden, false);
-
             assert_eq!(space.owner, ACCOUNT1);
-            assert_eq!(space.handle, Some(space_handle()));
-            assert_eq!(space.content, space_content_ipfs());
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).posts_count, 1);
 
-            assert_eq!(space.posts_count, 0);
-            assert_eq!(space.followers_count, 1);
-            assert!(SpaceHistory::edit_history(space.id).is_empty());
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.owner, ACCOUNT1);
+            assert_eq!(post.space_id, Some(SPACE1));
+            assert_eq!(post.content, post_content_ipfs());
 
-            // Check that the handle deposit has been reserved:
-            let reserved_balance = Balances::reserved_balance(ACCOUNT1);
-            assert_eq!(reserved_balance, HANDLE_DEPOSIT);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Posts::pinned_post_id_by_space_id(SPACE1), Some(POST1));
         });
     }
 
     #[test]
-    fn create_space_should_work_with_permissions_override() {
-        let perms = permissions_where_everyone_can_create_post();
-        ExtBuilder::build_with_space_and_custom_permissions(perms.clone()).execute_with(|| {
-            let space = Spaces::space_by_id(SPACE1).unwrap();
-            assert_eq!(space.permissions, Some(perms));
+    fn create_space_with_welcome_post_should_fail_when_welcome_post_content_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            // Neither a space nor its handle deposit should remain after a failure.
+            assert_noop!(
+                _create_space_with_welcome_post(None, Some(invalid_content_ipfs())),
+                UtilsError::<TestRuntime>::InvalidIpfsCid
+            );
         });
     }
 
     #[test]
-    fn create_post_should_work_overridden_space_permission_for_everyone() {
-        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_everyone_can_create_post()).execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            ));
+    fn move_post_should_unpin_welcome_post() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_space_with_welcome_post()); // SpaceId 1, PostId 1
+            assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 2
+
+            assert_ok!(_move_post_1_to_space_2());
+            assert!(Posts::pinned_post_id_by_space_id(SPACE1).is_none());
         });
     }
 
     #[test]
-    fn create_post_should_work_overridden_space_permission_for_followers() {
-        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_follower_can_create_post()).execute_with(|| {
+    fn onboard_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_onboard(None, None)); // SpaceId 1, PostId 1
 
-            assert_ok!(_default_follow_space());
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.content, profile_content_ipfs());
 
-            assert_ok!(_create_post(
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.owner, ACCOUNT1);
+            assert_eq!(Spaces::space_id_by_handle(space_handle()), Some(SPACE1));
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.owner, ACCOUNT1);
+            assert_eq!(post.space_id, Some(SPACE1));
+            assert_eq!(post.content, post_content_ipfs());
+        });
+    }
+
+    #[test]
+    fn onboard_should_fail_when_first_post_content_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            // Neither a profile, nor a space with its handle deposit should remain after a failure.
+            assert_noop!(
+                _onboard(None, Some(invalid_content_ipfs())),
+                UtilsError::<TestRuntime>::InvalidIpfsCid
+            );
+        });
+    }
+
+    #[test]
+    fn onboard_should_fail_when_profile_already_created() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_noop!(_onboard(None, None), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+        });
+    }
+
+    #[test]
+    fn get_space_view_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_space_with_welcome_post()); // SpaceId 1, PostId 1
+            assert_ok!(_default_follow_space());
+
+            let owner_view = Posts::get_space_view(SpaceIdOrHandle::Handle(space_handle()), Some(ACCOUNT1)).unwrap();
+            assert_eq!(owner_view.space.id, SPACE1);
+            assert_eq!(owner_view.space.followers_count, 2);
+            assert_eq!(owner_view.pinned_post.map(|post| post.id), Some(POST1));
+            assert_eq!(owner_view.is_viewer_owner, Some(true));
+            assert!(owner_view.viewer_permissions.contains(&SP::UpdateSpace));
+
+            let follower_view = Posts::get_space_view(SpaceIdOrHandle::Id(SPACE1), Some(ACCOUNT2)).unwrap();
+            assert!(follower_view.is_viewer_owner.is_none());
+            assert_eq!(follower_view.is_viewer_follower, Some(true));
+            assert!(follower_view.viewer_permissions.contains(&SP::CreateComments));
+            assert!(!follower_view.viewer_permissions.contains(&SP::UpdateSpace));
+
+            let anonymous_view = Posts::get_space_view(SpaceIdOrHandle::Id(SPACE1), None).unwrap();
+            assert!(anonymous_view.viewer_permissions.is_empty());
+
+            assert!(Posts::get_space_view(SpaceIdOrHandle::Id(SPACE2), None).is_none());
+        });
+    }
+
+    #[test]
+    fn create_space_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_space()); // SpaceId 1
+
+            // Check storages
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE1]);
+            assert_eq!(find_space_id_by_handle(space_handle()), Some(SPACE1));
+            assert_eq!(Spaces::next_space_id(), SPACE2);
+
+            // Check whether data stored correctly
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+
+            assert_eq!(space.created.account, ACCOUNT1);
+            assert!(space.updated.is_none());
+            assert_eq!(space.hidden, false);
+
+            assert_eq!(space.owner, ACCOUNT1);
+            assert_eq!(space.handle, Some(space_handle()));
+            assert_eq!(space.content, space_content_ipfs());
+
+            let space_stats = Spaces::space_stats_by_id(space.id);
+            assert_eq!(space_stats.posts_count, 0);
+            assert_eq!(space_stats.followers_count, 1);
+            assert!(SpaceHistory::edit_history(space.id).is_empty());
+
+            // Check that the handle deposit has been reserved:
+            let reserved_balance = Balances::reserved_balance(ACCOUNT1);
+            assert_eq!(reserved_balance, HANDLE_DEPOSIT);
+        });
+    }
+
+    #[test]
+    fn create_space_should_work_with_permissions_override() {
+        let perms = permissions_where_everyone_can_create_post();
+        ExtBuilder::build_with_space_and_custom_permissions(perms.clone()).execute_with(|| {
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.permissions, Some(perms));
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_overridden_space_permission_for_everyone() {
+        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_everyone_can_create_post()).execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            ));
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_overridden_space_permission_for_followers() {
+        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_follower_can_create_post()).execute_with(|| {
+
+            assert_ok!(_default_follow_space());
+
+            assert_ok!(_create_post(
                 Some(Origin::signed(ACCOUNT2)),
                 None,
                 None,
@@ -1715,6 +2294,8 @@ mod tests {
             let spaces_settings = Spaces::settings();
             // Ensure that `handles_enabled` field is false
             assert!(!spaces_settings.handles_enabled);
+
+            System::assert_last_event(Event::Spaces(pallet_spaces::RawEvent::SpacesSettingsUpdated(spaces_settings)));
         });
     }
 
@@ -1738,6 +2319,173 @@ mod tests {
         });
     }
 
+    #[test]
+    fn force_unreserve_handle_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(Spaces::force_unreserve_handle(Origin::root(), space_handle()));
+
+            assert!(Spaces::space_by_id(SPACE1).unwrap().handle.is_none());
+            assert!(find_space_id_by_handle(space_handle()).is_none());
+            System::assert_last_event(Event::Spaces(pallet_spaces::RawEvent::HandleForceUnreserved(
+                space_handle().to_ascii_lowercase(),
+                Some(SPACE1)
+            )));
+        });
+    }
+
+    #[test]
+    fn force_create_system_space_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_update_space_settings_with_handles_disabled());
+            assert_ok!(_force_create_system_space(None, None));
+
+            let space = Spaces::space_by_id(SYSTEM_SPACE).unwrap();
+            assert_eq!(space.owner, ACCOUNT1);
+            assert_eq!(space.handle, Some(space_handle()));
+            assert_eq!(space.content, space_content_ipfs());
+
+            assert!(Spaces::is_system_space(SYSTEM_SPACE));
+            assert_eq!(find_space_id_by_handle(space_handle()), Some(SYSTEM_SPACE));
+            assert_eq!(Spaces::next_space_id(), SPACE1);
+
+            // No handle deposit is reserved for a system space.
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+        });
+    }
+
+    #[test]
+    fn force_create_system_space_should_fail_when_account_is_not_root() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _force_create_system_space(Some(Origin::signed(ACCOUNT1)), None),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn force_create_system_space_should_fail_when_space_id_is_not_reserved() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _force_create_system_space(None, Some(SPACE1)),
+                SpacesError::<TestRuntime>::NotASystemSpaceId
+            );
+        });
+    }
+
+    #[test]
+    fn force_create_system_space_should_fail_when_already_created() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_force_create_system_space(None, None));
+            assert_noop!(
+                _force_create_system_space(None, None),
+                SpacesError::<TestRuntime>::SystemSpaceAlreadyCreated
+            );
+        });
+    }
+
+    #[test]
+    fn force_create_system_space_should_replace_reserved_space_placeholder() {
+        ExtBuilder::build().execute_with(|| {
+            System::set_block_number(0);
+            SpaceById::<TestRuntime>::insert(
+                SYSTEM_SPACE,
+                Space::<TestRuntime>::new(SYSTEM_SPACE, None, ACCOUNT2, Content::None, None, None)
+            );
+            System::set_block_number(1);
+
+            assert_ok!(_force_create_system_space(None, None));
+            assert_eq!(Spaces::space_by_id(SYSTEM_SPACE).unwrap().owner, ACCOUNT1);
+        });
+    }
+
+    #[test]
+    fn force_create_system_space_should_fail_when_reserved_space_is_in_use() {
+        ExtBuilder::build().execute_with(|| {
+            SpaceById::<TestRuntime>::insert(
+                SYSTEM_SPACE,
+                Space::<TestRuntime>::new(SYSTEM_SPACE, None, ACCOUNT2, Content::None, None, None)
+            );
+
+            assert_noop!(
+                _force_create_system_space(None, None),
+                SpacesError::<TestRuntime>::ReservedSpaceIsInUse
+            );
+        });
+    }
+
+    #[test]
+    fn force_create_system_space_should_fail_when_reserved_space_placeholder_has_roles() {
+        ExtBuilder::build().execute_with(|| {
+            System::set_block_number(0);
+            SpaceById::<TestRuntime>::insert(
+                SYSTEM_SPACE,
+                Space::<TestRuntime>::new(SYSTEM_SPACE, None, ACCOUNT2, Content::None, None, None)
+            );
+            System::set_block_number(1);
+
+            assert_ok!(_create_role(Some(Origin::signed(ACCOUNT2)), Some(SYSTEM_SPACE), None, None, None));
+
+            assert_noop!(
+                _force_create_system_space(None, None),
+                SpacesError::<TestRuntime>::ReservedSpaceIsInUse
+            );
+        });
+    }
+
+    #[test]
+    fn update_space_should_fail_when_changing_handle_of_system_space() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_force_create_system_space(None, None));
+            assert_noop!(
+                _update_space(
+                    None,
+                    Some(SYSTEM_SPACE),
+                    Some(space_update(Some(Some(space_handle_2())), None, None))
+                ),
+                SpacesError::<TestRuntime>::CannotChangeSystemSpaceHandle
+            );
+        });
+    }
+
+    #[test]
+    fn split_space_stats_should_move_counters_out_of_space() {
+        ExtBuilder::build().execute_with(|| {
+            let old_space = OldSpace::<TestRuntime> {
+                id: SPACE1,
+                created: WhoAndWhen::<TestRuntime>::new(ACCOUNT1),
+                updated: None,
+                owner: ACCOUNT1,
+                parent_id: None,
+                handle: Some(space_handle()),
+                content: space_content_ipfs(),
+                hidden: false,
+                posts_count: 3,
+                hidden_posts_count: 1,
+                followers_count: 2,
+                score: 5,
+                permissions: None,
+            };
+
+            frame_support::storage::unhashed::put(&SpaceById::<TestRuntime>::hashed_key_for(SPACE1), &old_space);
+            SpaceStatsStorageSplit::put(false);
+
+            split_space_stats::<TestRuntime>();
+
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.owner, ACCOUNT1);
+            assert_eq!(space.handle, Some(space_handle()));
+            assert_eq!(space.content, space_content_ipfs());
+            assert!(space.max_comment_depth.is_none());
+
+            assert_eq!(
+                Spaces::space_stats_by_id(SPACE1),
+                SpaceStats { posts_count: 3, hidden_posts_count: 1, followers_count: 2, score: 5 }
+            );
+            assert!(SpaceStatsStorageSplit::get());
+        });
+    }
+
     // TODO: refactor or remove. Deprecated tests
     // Find public space ids tests
     // --------------------------------------------------------------------------------------------
@@ -2040,14 +2788,14 @@ mod tests {
         assert_eq!(new_space_id, expected_new_space_id);
 
         // Check that stats on the old space have been decreased
-        let old_space = Spaces::space_by_id(old_space_id).unwrap();
-        assert_eq!(old_space.posts_count, 0);
-        assert_eq!(old_space.hidden_posts_count, 0);
+        let old_space_stats = Spaces::space_stats_by_id(old_space_id);
+        assert_eq!(old_space_stats.posts_count, 0);
+        assert_eq!(old_space_stats.hidden_posts_count, 0);
 
         // Check that stats on the new space have been increased
-        let new_space = Spaces::space_by_id(new_space_id).unwrap();
-        assert_eq!(new_space.posts_count, 1);
-        assert_eq!(new_space.hidden_posts_count, if post.hidden { 1 } else { 0 });
+        let new_space_stats = Spaces::space_stats_by_id(new_space_id);
+        assert_eq!(new_space_stats.posts_count, 1);
+        assert_eq!(new_space_stats.hidden_posts_count, if post.hidden { 1 } else { 0 });
     }
 
     #[test]
@@ -2614,6 +3362,66 @@ mod tests {
         });
     }
 
+    #[test]
+    fn create_comment_should_fail_when_space_max_comment_depth_reached() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_max_comment_depth(None, Some(1)));
+
+            assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
+            assert_noop!(
+                _create_comment(None, None, Some(Some(2)), None),
+                PostsError::<TestRuntime>::MaxCommentDepthReached
+            );
+        });
+    }
+
+    #[test]
+    fn space_max_comment_depth_should_not_exceed_global_limit() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_max_comment_depth(None, Some(MaxCommentDepth::get() + 1)));
+            assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
+
+            for parent_id in 2..11_u64 {
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None)); // PostId N (last = 10)
+            }
+
+            assert_noop!(
+                _create_comment(None, None, Some(Some(11)), None),
+                PostsError::<TestRuntime>::MaxCommentDepthReached
+            );
+        });
+    }
+
+    #[test]
+    fn set_max_comment_depth_should_not_record_space_history() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_set_max_comment_depth(None, Some(1)));
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().max_comment_depth, Some(1));
+            assert!(SpaceHistory::edit_history(SPACE1).is_empty());
+        });
+    }
+
+    #[test]
+    fn set_max_comment_depth_should_fail_when_depth_is_zero() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _set_max_comment_depth(None, Some(0)),
+                SpacesError::<TestRuntime>::ZeroMaxCommentDepth
+            );
+        });
+    }
+
+    #[test]
+    fn set_max_comment_depth_should_fail_when_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _set_max_comment_depth(Some(Origin::signed(ACCOUNT2)), Some(1)),
+                SpacesError::<TestRuntime>::NoPermissionToUpdateSpace
+            );
+        });
+    }
+
     #[test]
     fn update_comment_should_work() {
         ExtBuilder::build_with_comment().execute_with(|| {
@@ -2699,384 +3507,1698 @@ mod tests {
         });
     }
 
-    // Reaction tests
+    // Post content kind tests
     #[test]
-    fn create_post_reaction_should_work_upvote() {
+    fn update_post_content_kind_should_work() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None
-            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+            assert_ok!(_update_post_content_kind(None, None, None));
+            assert_eq!(Posts::content_kind_by_post_id(POST1), Some(ContentKind::Markdown));
 
-            // Check storages
-            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
-            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+            assert_ok!(_update_post_content_kind(None, None, Some(None)));
+            assert!(Posts::content_kind_by_post_id(POST1).is_none());
+        });
+    }
 
-            // Check post reaction counters
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.upvotes_count, 1);
-            assert_eq!(post.downvotes_count, 0);
+    #[test]
+    fn create_post_with_options_should_set_content_kind() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(Posts::create_post_with_options(
+                Origin::signed(ACCOUNT1),
+                Some(SPACE1),
+                extension_regular_post(),
+                post_content_ipfs(),
+                PostOptions { content_kind: Some(ContentKind::Markdown), ..Default::default() }
+            )); // PostId 1
 
-            // Check whether data stored correctly
-            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
-            assert_eq!(reaction.created.account, ACCOUNT2);
-            assert_eq!(reaction.kind, reaction_upvote());
+            assert_eq!(Posts::content_kind_by_post_id(POST1), Some(ContentKind::Markdown));
         });
     }
 
     #[test]
-    fn create_post_reaction_should_work_downvote() {
+    fn update_post_should_replace_content_kind_when_content_changes() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+            assert_ok!(_update_post_content_kind(None, None, None));
 
-            // Check storages
-            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
-            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+            let mut update = post_update(None, Some(updated_post_content()), None);
+            update.content_kind = Some(ContentKind::ImageSet);
+            assert_ok!(_update_post(None, None, Some(update)));
+            assert_eq!(Posts::content_kind_by_post_id(POST1), Some(ContentKind::ImageSet));
 
-            // Check post reaction counters
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.upvotes_count, 0);
-            assert_eq!(post.downvotes_count, 1);
+            // A stale hint is dropped if the new content has no hint.
+            assert_ok!(_update_post(None, None, Some(post_update(None, Some(post_content_ipfs()), None))));
+            assert!(Posts::content_kind_by_post_id(POST1).is_none());
+        });
+    }
 
-            // Check whether data stored correctly
-            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
-            assert_eq!(reaction.created.account, ACCOUNT2);
-            assert_eq!(reaction.kind, reaction_downvote());
+    #[test]
+    fn update_post_should_keep_content_kind_when_content_is_not_changed() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post_content_kind(None, None, None));
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+
+            assert_eq!(Posts::content_kind_by_post_id(POST1), Some(ContentKind::Markdown));
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_account_has_already_reacted() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            // Try to catch an error creating reaction by the same account
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::AccountAlreadyReacted);
+    fn update_post_content_kind_should_work_for_comment() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_update_post_content_kind(None, Some(POST2), Some(Some(ContentKind::Plain))));
+            assert_eq!(Posts::content_kind_by_post_id(POST2), Some(ContentKind::Plain));
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_post_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            // Try to catch an error creating reaction by the same account
-            assert_noop!(_create_default_post_reaction(), PostsError::<TestRuntime>::PostNotFound);
+    fn update_post_content_kind_should_fail_when_nothing_to_update() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post_content_kind(None, None, None));
+            assert_noop!(
+                _update_post_content_kind(None, None, None),
+                PostsError::<TestRuntime>::NoUpdatesForPost
+            );
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_trying_to_react_in_hidden_space() {
+    fn update_post_content_kind_should_fail_when_content_is_empty() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(None, None, None, Some(Content::None)));
+            assert_noop!(
+                _update_post_content_kind(None, None, None),
+                UtilsError::<TestRuntime>::ContentIsEmpty
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_content_kind_should_fail_when_account_has_no_permission() {
         ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _update_post_content_kind(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost
+            );
+        });
+    }
 
-            // Hide the space
-            assert_ok!(_update_space(
-                None,
-                None,
-                Some(space_update(None, None, Some(true)))
-            ));
+    // Scheduled post tests
+    #[test]
+    fn create_scheduled_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post());
 
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenSpaceHidden);
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert!(post.hidden);
+
+            let space_stats = Spaces::space_stats_by_id(SPACE1);
+            assert_eq!(space_stats.posts_count, 1);
+            assert_eq!(space_stats.hidden_posts_count, 1);
+
+            assert_eq!(Posts::scheduled_post_ids_at(PUBLISH_AT), vec![POST1]);
+            assert_eq!(Posts::publish_at_by_post_id(POST1), Some(PUBLISH_AT));
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_trying_to_react_on_hidden_post() {
+    fn scheduled_post_should_be_published_at_its_block() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post());
+
+            Posts::on_initialize(PUBLISH_AT - 1);
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+
+            Posts::on_initialize(PUBLISH_AT);
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).hidden_posts_count, 0);
+
+            assert!(Posts::scheduled_post_ids_at(PUBLISH_AT).is_empty());
+            assert!(Posts::publish_at_by_post_id(POST1).is_none());
+        });
+    }
+
+    #[test]
+    fn scheduled_post_should_not_be_published_when_unhidden_and_hidden_again() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post());
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(false)))));
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+
+            Posts::on_initialize(PUBLISH_AT);
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).hidden_posts_count, 1);
+        });
+    }
+
+    #[test]
+    fn create_scheduled_post_should_fail_when_publish_at_is_in_the_past() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_scheduled_post(None, None, Some(1)),
+                PostsError::<TestRuntime>::PublishAtIsInThePast
+            );
+        });
+    }
+
+    #[test]
+    fn create_scheduled_post_should_fail_when_post_is_comment() {
         ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _create_scheduled_post(None, Some(extension_comment(None, POST1)), None),
+                PostsError::<TestRuntime>::CannotScheduleComment
+            );
+        });
+    }
 
-            // Hide the post
-            assert_ok!(_update_post(
-                None,
-                None,
-                Some(post_update(None, None, Some(true)))
-            ));
+    #[test]
+    fn create_scheduled_post_should_fail_when_too_many_posts_scheduled_at_block() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post());
+            assert_noop!(
+                _create_default_scheduled_post(),
+                PostsError::<TestRuntime>::TooManyPostsScheduledAtBlock
+            );
+        });
+    }
 
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenPostHidden);
+    #[test]
+    fn create_scheduled_post_should_fail_when_too_many_posts_scheduled_by_account() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post());
+            assert_noop!(
+                _create_scheduled_post(None, None, Some(PUBLISH_AT + 1)),
+                PostsError::<TestRuntime>::TooManyScheduledPostsByAccount
+            );
         });
     }
 
-// Shares tests
+    #[test]
+    fn create_scheduled_post_should_work_after_previous_one_is_published() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post());
+            assert_eq!(Posts::scheduled_posts_count_by_account(ACCOUNT1), 1);
+
+            Posts::on_initialize(PUBLISH_AT);
+            assert_eq!(Posts::scheduled_posts_count_by_account(ACCOUNT1), 0);
+
+            assert_ok!(_create_scheduled_post(None, None, Some(PUBLISH_AT + 1)));
+        });
+    }
 
     #[test]
-    fn share_post_should_work() {
+    fn create_scheduled_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_scheduled_post(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
+        });
+    }
+
+    // Mute thread tests
+    #[test]
+    fn mute_thread_should_work() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
+            assert_ok!(_mute_default_thread());
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
+            assert_eq!(Posts::muted_thread_ids_by_account(ACCOUNT2), vec![POST1]);
+            assert!(Posts::thread_muted_by_account((ACCOUNT2, POST1)));
+        });
+    }
 
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
-            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
-            assert_eq!(Posts::next_post_id(), POST3);
+    #[test]
+    fn mute_thread_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_mute_default_thread(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
 
-            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+    #[test]
+    fn mute_thread_should_fail_when_post_is_comment() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_noop!(
+                _mute_thread(None, Some(POST2)),
+                PostsError::<TestRuntime>::CannotMuteComment
+            );
+        });
+    }
 
-            // Check whether data stored correctly
-            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+    #[test]
+    fn mute_thread_should_fail_when_thread_already_muted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_mute_default_thread());
+            assert_noop!(_mute_default_thread(), PostsError::<TestRuntime>::AlreadyMutedThread);
+        });
+    }
 
-            let shared_post = Posts::post_by_id(POST2).unwrap();
+    #[test]
+    fn mute_thread_should_fail_when_too_many_threads_muted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
 
-            assert_eq!(shared_post.space_id, Some(SPACE2));
-            assert_eq!(shared_post.created.account, ACCOUNT2);
-            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            assert_ok!(_mute_thread(None, Some(POST1)));
+            assert_ok!(_mute_thread(None, Some(POST2)));
+            assert_noop!(
+                _mute_thread(None, Some(POST3)),
+                PostsError::<TestRuntime>::TooManyMutedThreads
+            );
         });
     }
 
     #[test]
-    fn share_post_should_work_when_one_of_roles_is_permitted() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content,
-                None
-            ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
+    fn unmute_thread_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_mute_default_thread());
+            assert_ok!(_unmute_default_thread());
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+            assert!(Posts::muted_thread_ids_by_account(ACCOUNT2).is_empty());
+            assert!(!Posts::thread_muted_by_account((ACCOUNT2, POST1)));
         });
     }
 
     #[test]
-    fn share_post_should_work_for_share_own_post_in_same_own_space() {
+    fn unmute_thread_should_fail_when_thread_is_not_muted() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1
+            assert_noop!(_unmute_default_thread(), PostsError::<TestRuntime>::ThreadIsNotMuted);
+        });
+    }
 
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2]);
-            assert_eq!(Posts::next_post_id(), POST3);
+    // Hide posts by author tests
 
-            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+    #[test]
+    fn hide_posts_by_author_should_hide_posts_in_chunks() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 1
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
 
-            // Check whether data stored correctly
-            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+            assert_ok!(_hide_posts_by_author(None, ACCOUNT1, true));
+            assert!(Posts::post_by_id(POST2).unwrap().hidden);
+            assert!(!Posts::post_by_id(POST3).unwrap().hidden);
+            assert_eq!(Posts::hide_posts_by_author_cursor((SPACE1, ACCOUNT1)), Some((true, 2)));
+            System::assert_last_event(Event::Posts(pallet_posts::RawEvent::PostsByAuthorHidden(
+                ACCOUNT1, SPACE1, ACCOUNT1, true, 2, false
+            )));
 
-            let shared_post = Posts::post_by_id(POST2).unwrap();
-            assert_eq!(shared_post.space_id, Some(SPACE1));
-            assert_eq!(shared_post.created.account, ACCOUNT1);
-            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            assert_ok!(_hide_posts_by_author(None, ACCOUNT1, true));
+            assert!(Posts::post_by_id(POST3).unwrap().hidden);
+            assert!(Posts::hide_posts_by_author_cursor((SPACE1, ACCOUNT1)).is_none());
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).hidden_posts_count, 3);
+            System::assert_last_event(Event::Posts(pallet_posts::RawEvent::PostsByAuthorHidden(
+                ACCOUNT1, SPACE1, ACCOUNT1, true, 1, true
+            )));
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_original_post_not_found() {
+    fn hide_posts_by_author_should_start_over_when_visibility_changes() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
+            assert_ok!(_create_default_post()); // PostId 1
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
 
-            // Skipped creating PostId 1
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::OriginalPostNotFound);
+            assert_ok!(_hide_posts_by_author(None, ACCOUNT1, true));
+            assert_ok!(_hide_posts_by_author(None, ACCOUNT1, false));
+
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+            assert!(!Posts::post_by_id(POST2).unwrap().hidden);
+            assert_eq!(Posts::hide_posts_by_author_cursor((SPACE1, ACCOUNT1)), Some((false, 2)));
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).hidden_posts_count, 0);
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_trying_to_share_shared_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
+    fn hide_posts_by_author_should_cancel_scheduled_publication() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post());
+            assert_ok!(_hide_posts_by_author(None, ACCOUNT1, true));
+            assert!(Posts::publish_at_by_post_id(POST1).is_none());
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None)
-            );
+            Posts::on_initialize(PUBLISH_AT);
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+        });
+    }
 
-            // Try to share post with extension SharedPost
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST2)),
-                None
-            ), PostsError::<TestRuntime>::CannotShareSharingPost);
+    #[test]
+    fn hide_posts_by_author_should_not_change_posts_of_other_authors() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_hide_posts_by_author(None, ACCOUNT2, true));
+
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+            System::assert_last_event(Event::Posts(pallet_posts::RawEvent::PostsByAuthorHidden(
+                ACCOUNT1, SPACE1, ACCOUNT2, true, 0, true
+            )));
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
+    fn hide_posts_by_author_should_record_post_history() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(None), // No space_handle provided (ok)
-                None, // Default space content,
-                None
-            )); // SpaceId 2 by ACCOUNT1
+            assert_ok!(_hide_posts_by_author(None, ACCOUNT1, true));
 
-            // Try to share post with extension SharedPost
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            let edit_history = PostHistory::edit_history(POST1);
+            assert_eq!(edit_history.len(), 1);
+            assert_eq!(edit_history[0].old_data.hidden, Some(false));
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content
+    fn hide_posts_by_author_should_fail_when_space_post_ids_count_too_low() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                Posts::hide_posts_by_author(Origin::signed(ACCOUNT1), SPACE1, ACCOUNT1, true, 0),
+                PostsError::<TestRuntime>::SpacePostIdsCountTooLow
+            );
+        });
+    }
+
+    #[test]
+    fn hide_posts_by_author_should_fail_when_no_permission() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _hide_posts_by_author(Some(Origin::signed(ACCOUNT2)), ACCOUNT1, true),
+                PostsError::<TestRuntime>::NoPermissionToHideAnyPost
+            );
+        });
+    }
+
+    // Post import tests
+
+    #[test]
+    fn import_posts_into_reserved_ids_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_reserve_post_ids(None, 2)); // Reserves PostId 1 and 2
+            assert_eq!(Posts::next_post_id(), POST1 + 2);
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT + 2 * PostIdReservationDeposit::get());
+
+            // Regular posts are created after the reserved range:
+            assert_ok!(_create_default_post()); // PostId 3
+            assert!(Posts::post_by_id(POST1 + 2).is_some());
+
+            assert_ok!(_import_post(None, None)); // PostId 1
+            assert_ok!(_import_post(None, Some(extension_comment(None, POST1)))); // PostId 2
+
+            assert_eq!(Posts::provenance_by_post_id(POST1), Some(post_provenance()));
+            assert!(Posts::post_by_id(POST1 + 1).unwrap().is_comment());
+            assert!(Posts::reserved_post_ids_by_space_id(SPACE1).is_none());
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
+
+            assert_noop!(_import_post(None, None), PostsError::<TestRuntime>::NoReservedPostIds);
+        });
+    }
+
+    #[test]
+    fn import_post_should_fail_when_reservation_expired() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_reserve_post_ids(None, 1));
+
+            System::set_block_number(2 + PostIdReservationPeriod::get());
+            assert_noop!(_import_post(None, None), PostsError::<TestRuntime>::PostIdsReservationExpired);
+        });
+    }
+
+    #[test]
+    fn release_post_ids_should_return_deposit_of_remaining_ids() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_reserve_post_ids(None, 3));
+            assert_ok!(_import_post(None, None)); // PostId 1
+
+            assert_ok!(_release_post_ids(None));
+            assert!(Posts::reserved_post_ids_by_space_id(SPACE1).is_none());
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
+
+            // Released ids are not reused:
+            assert_eq!(Posts::next_post_id(), POST1 + 3);
+        });
+    }
+
+    #[test]
+    fn release_post_ids_should_fail_when_not_expired_and_not_reserving_account() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_reserve_post_ids(None, 1));
+            assert_noop!(
+                _release_post_ids(Some(Origin::signed(ACCOUNT2))),
+                PostsError::<TestRuntime>::PostIdsReservationNotExpired
+            );
+
+            System::set_block_number(2 + PostIdReservationPeriod::get());
+            assert_ok!(_release_post_ids(Some(Origin::signed(ACCOUNT2))));
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
+        });
+    }
+
+    #[test]
+    fn reserve_post_ids_should_fail_when_count_is_invalid() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_reserve_post_ids(None, 0), PostsError::<TestRuntime>::ZeroPostIdsToReserve);
+            assert_noop!(_reserve_post_ids(None, 4), PostsError::<TestRuntime>::TooManyPostIdsToReserve);
+        });
+    }
+
+    #[test]
+    fn reserve_post_ids_should_fail_when_ids_already_reserved() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_reserve_post_ids(None, 1));
+            assert_noop!(_reserve_post_ids(None, 1), PostsError::<TestRuntime>::PostIdsAlreadyReserved);
+        });
+    }
+
+    #[test]
+    fn import_post_should_fail_when_not_a_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_reserve_post_ids(None, 1));
+            assert_noop!(
+                _import_post(Some(Origin::signed(ACCOUNT2)), None),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+        });
+    }
+
+    // Comments lock tests
+    #[test]
+    fn update_comments_lock_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_update_comments_lock(None, None));
+            assert_eq!(Posts::comments_lock_after_by_space_id(SPACE1), Some(COMMENTS_LOCK_AFTER));
+
+            assert_ok!(_update_comments_lock(None, Some(None)));
+            assert!(Posts::comments_lock_after_by_space_id(SPACE1).is_none());
+        });
+    }
+
+    #[test]
+    fn update_comments_lock_should_fail_when_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _update_comments_lock(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NoPermissionToUpdateCommentsLock
+            );
+        });
+    }
+
+    #[test]
+    fn update_comments_lock_should_fail_when_period_is_zero() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _update_comments_lock(None, Some(Some(0))),
+                PostsError::<TestRuntime>::ZeroCommentsLockPeriod
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_work_when_post_is_active() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_comments_lock(None, None));
+
+            System::set_block_number(COMMENTS_LOCK_AFTER);
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+            assert_eq!(Posts::last_activity_at_by_post_id(POST1), Some(COMMENTS_LOCK_AFTER));
+
+            // A new comment prolongs the post activity.
+            System::set_block_number(COMMENTS_LOCK_AFTER * 2 - 1);
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_post_is_inactive() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_comments_lock(None, None));
+
+            // The post was created at block 1.
+            System::set_block_number(1 + COMMENTS_LOCK_AFTER);
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::CommentsLockedDueToInactivity
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_work_on_inactive_post_with_permission_override() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_comments_lock(None, None));
+
+            // Space owner has `CommentOnInactivePosts` permission by default.
+            System::set_block_number(1 + COMMENTS_LOCK_AFTER);
+            assert_ok!(_create_default_comment());
+        });
+    }
+
+    // Posts by account index tests
+
+    #[test]
+    fn get_post_ids_by_account_in_block_range_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // PostId 1 is created at block 1
+            System::set_block_number(5);
+            assert_ok!(_create_default_post()); // PostId 2
+            System::set_block_number(12);
+            assert_ok!(_create_default_post()); // PostId 3
+            System::set_block_number(25);
+            assert_ok!(_create_default_post()); // PostId 4
+
+            assert_eq!(Posts::post_eras_by_account(ACCOUNT1), vec![0, 10, 20]);
+            assert_eq!(Posts::post_ids_by_account_in_era(ACCOUNT1, 0), vec![POST1, POST2]);
+
+            assert_eq!(Posts::get_post_ids_by_account_in_block_range(ACCOUNT1, 0, 100, 10), vec![POST1, POST2, POST3, POST4]);
+            assert_eq!(Posts::get_post_ids_by_account_in_block_range(ACCOUNT1, 2, 12, 10), vec![POST2, POST3]);
+            assert_eq!(Posts::get_post_ids_by_account_in_block_range(ACCOUNT1, 0, 100, 2), vec![POST1, POST2]);
+            assert!(Posts::get_post_ids_by_account_in_block_range(ACCOUNT2, 0, 100, 10).is_empty());
+        });
+    }
+
+    #[test]
+    fn post_eras_by_account_should_keep_only_latest_eras() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // PostId 1 is created at block 1
+            for (i, block) in [12, 25, 31].iter().enumerate() {
+                System::set_block_number(*block);
+                assert_ok!(_create_default_post()); // PostId 2..4
+                assert_eq!(Posts::post_eras_by_account(ACCOUNT1).len(), (i + 2).min(3));
+            }
+
+            assert_eq!(Posts::post_eras_by_account(ACCOUNT1), vec![10, 20, 30]);
+            assert!(Posts::post_ids_by_account_in_era(ACCOUNT1, 0).is_empty());
+            assert_eq!(Posts::post_ids_by_account_in_era(ACCOUNT1, 30), vec![POST4]);
+        });
+    }
+
+    // Storage keys tests
+
+    #[test]
+    fn storage_keys_should_point_to_stored_values() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            use frame_support::storage::unhashed;
+
+            let post_key = Posts::get_post_storage_key(POST1);
+            assert_eq!(unhashed::get::<Post<TestRuntime>>(&post_key), Posts::post_by_id(POST1));
+
+            let space_keys = Spaces::get_space_storage_keys_by_handle(space_handle());
+            assert_eq!(space_keys.len(), 3);
+            assert_eq!(unhashed::get::<SpaceId>(&space_keys[0]), Some(SPACE1));
+            assert_eq!(unhashed::get(&space_keys[1]), Spaces::space_by_id(SPACE1));
+            assert_eq!(unhashed::get(&space_keys[2]), Some(Spaces::space_stats_by_id(SPACE1)));
+
+            let social_account_key = Profiles::get_social_account_storage_key(ACCOUNT1);
+            assert_eq!(unhashed::get(&social_account_key), Profiles::social_account_by_id(ACCOUNT1));
+        });
+    }
+
+    #[test]
+    fn get_space_storage_keys_by_handle_should_return_only_handle_key_when_not_registered() {
+        ExtBuilder::build().execute_with(|| {
+            let keys = Spaces::get_space_storage_keys_by_handle(space_handle());
+            assert_eq!(keys.len(), 1);
+            assert!(frame_support::storage::unhashed::get_raw(&keys[0]).is_none());
+        });
+    }
+
+    // Content proof tests
+
+    #[test]
+    fn get_content_proof_should_return_content_at_given_block() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            System::set_block_number(5);
+            assert_ok!(_update_post(None, None, Some(post_update(None, Some(updated_post_content()), None))));
+
+            assert_eq!(Posts::content_proofs_by_post_id(POST1).len(), 2);
+            assert!(Posts::get_content_proof(POST1, 0).is_none());
+
+            let original_proof = Posts::get_content_proof(POST1, 4).unwrap();
+            assert_eq!(original_proof.block, 1);
+            assert_eq!(original_proof.content.content_id, post_content_ipfs());
+
+            let updated_proof = Posts::get_content_proof(POST1, 5).unwrap();
+            assert_eq!(updated_proof.block, 5);
+            assert_eq!(updated_proof.content.content_id, updated_post_content());
+            assert_eq!(updated_proof.parent_hash, System::parent_hash().as_ref().to_vec());
+
+            assert_eq!(
+                frame_support::storage::unhashed::get(&updated_proof.storage_key),
+                Some(Posts::content_proofs_by_post_id(POST1))
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_should_keep_only_latest_content_proofs() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            System::set_block_number(5);
+            assert_ok!(_update_post(None, None, Some(post_update(None, Some(updated_post_content()), None))));
+            System::set_block_number(6);
+            assert_ok!(_update_post(None, None, Some(post_update(None, Some(post_content_ipfs()), None))));
+
+            // `MaxContentProofsPerPost` is 2
+            let proofs = Posts::content_proofs_by_post_id(POST1);
+            assert_eq!(proofs.iter().map(|proof| proof.block).collect::<Vec<_>>(), vec![5, 6]);
+            assert!(Posts::get_content_proof(POST1, 4).is_none());
+        });
+    }
+
+    #[test]
+    fn get_content_proof_should_return_none_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert!(Posts::get_content_proof(POST1, 1).is_none());
+        });
+    }
+
+    // Spam score tests
+
+    fn _validate_spam_score(account: AccountId, call: Call) -> TransactionValidity {
+        CheckSpamScore::<TestRuntime>::new().validate(&account, &call, &Default::default(), 0)
+    }
+
+    fn create_post_call() -> Call {
+        Call::Posts(pallet_posts::Call::create_post(Some(SPACE1), extension_regular_post(), post_content_ipfs()))
+    }
+
+    #[test]
+    fn spam_score_should_count_recent_posts_of_new_account() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // ACCOUNT1 has no profile, so it's treated as a new account.
+            assert_eq!(Posts::spam_score(&ACCOUNT1), SPAM_SCORE_FOR_NEW_ACCOUNT + SPAM_SCORE_PER_RECENT_POST);
+
+            assert_ok!(_create_default_post());
+            assert_eq!(Posts::spam_score(&ACCOUNT1), SPAM_SCORE_FOR_NEW_ACCOUNT + SPAM_SCORE_PER_RECENT_POST * 2);
+        });
+    }
+
+    #[test]
+    fn spam_score_should_drop_after_recent_period() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_profile());
+
+            System::set_block_number(1 + RecentPostsPeriod::get());
+            assert_eq!(Posts::spam_score(&ACCOUNT1), 0);
+
+            assert_ok!(_create_default_post());
+            assert_eq!(Posts::recent_posts_count(&ACCOUNT1, System::block_number()), 1);
+            assert_eq!(Posts::spam_score(&ACCOUNT1), SPAM_SCORE_PER_RECENT_POST);
+        });
+    }
+
+    #[test]
+    fn spam_score_should_count_upheld_moderation_flags() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let score_before = Posts::spam_score(&ACCOUNT2);
+
+            // A report alone does not change a spam score:
+            assert_ok!(_report_entity(None, Some(EntityId::Account(ACCOUNT2)), None, None));
+            assert_eq!(Posts::spam_score(&ACCOUNT2), score_before);
+
+            assert_ok!(_update_entity_status(
+                None,
+                Some(EntityId::Account(ACCOUNT2)),
+                None,
+                Some(Some(EntityStatus::Blocked))
+            ));
+            assert_eq!(Posts::spam_score(&ACCOUNT2), score_before + SPAM_SCORE_PER_MODERATION_FLAG);
+
+            // Unblocking an account removes its moderation flag:
+            assert_ok!(_update_entity_status(None, Some(EntityId::Account(ACCOUNT2)), None, None));
+            assert_eq!(Posts::spam_score(&ACCOUNT2), score_before);
+        });
+    }
+
+    #[test]
+    fn check_spam_score_should_lower_priority_as_score_grows() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let score = Posts::spam_score(&ACCOUNT1);
+            let validity = _validate_spam_score(ACCOUNT1, create_post_call()).unwrap();
+            assert_eq!(validity.priority, (MaxSpamScore::get() - score) as u64);
+
+            assert_ok!(_create_default_post());
+            let next_validity = _validate_spam_score(ACCOUNT1, create_post_call()).unwrap();
+            assert!(next_validity.priority < validity.priority);
+        });
+    }
+
+    #[test]
+    fn check_spam_score_should_reject_when_max_score_reached() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            while Posts::spam_score(&ACCOUNT1) < MaxSpamScore::get() {
+                assert_ok!(_create_default_post());
+            }
+
+            assert_eq!(
+                _validate_spam_score(ACCOUNT1, create_post_call()),
+                Err(InvalidTransaction::Custom(0).into())
+            );
+
+            // Calls that don't create social content are not checked.
+            let mute_thread_call = Call::Posts(pallet_posts::Call::mute_thread(POST1));
+            assert_ok!(_validate_spam_score(ACCOUNT1, mute_thread_call));
+        });
+    }
+
+    /// Create posts by ACCOUNT1 in SPACE1 until its spam score reaches `MaxSpamScore`.
+    fn _create_posts_until_max_spam_score() {
+        while Posts::spam_score(&ACCOUNT1) < MaxSpamScore::get() {
+            assert_ok!(_create_default_post());
+        }
+    }
+
+    /// A space without a handle, so that calls creating a space with the default handle still work.
+    fn _create_space_with_no_handle_and_reach_max_spam_score() {
+        assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 1
+        _create_posts_until_max_spam_score();
+    }
+
+    #[test]
+    fn create_post_should_fail_when_spam_score_too_high() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            _create_posts_until_max_spam_score();
+            assert_noop!(_create_default_post(), PostsError::<TestRuntime>::SpamScoreTooHigh);
+        });
+    }
+
+    #[test]
+    fn create_post_with_options_should_fail_when_spam_score_too_high() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            _create_posts_until_max_spam_score();
+            assert_noop!(
+                Posts::create_post_with_options(
+                    Origin::signed(ACCOUNT1),
+                    Some(SPACE1),
+                    extension_regular_post(),
+                    post_content_ipfs(),
+                    PostOptions::default()
+                ),
+                PostsError::<TestRuntime>::SpamScoreTooHigh
+            );
+        });
+    }
+
+    #[test]
+    fn create_scheduled_post_should_fail_when_spam_score_too_high() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            _create_posts_until_max_spam_score();
+            assert_noop!(_create_default_scheduled_post(), PostsError::<TestRuntime>::SpamScoreTooHigh);
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_when_spam_score_too_high() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            _create_posts_until_max_spam_score();
+            assert_noop!(
+                _update_post(None, None, Some(post_update(None, Some(updated_post_content()), None))),
+                PostsError::<TestRuntime>::SpamScoreTooHigh
+            );
+        });
+    }
+
+    #[test]
+    fn import_post_should_fail_when_spam_score_too_high() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_reserve_post_ids(None, 1));
+            _create_posts_until_max_spam_score();
+            assert_noop!(_import_post(None, None), PostsError::<TestRuntime>::SpamScoreTooHigh);
+        });
+    }
+
+    #[test]
+    fn create_space_with_welcome_post_should_fail_when_spam_score_too_high() {
+        ExtBuilder::build().execute_with(|| {
+            _create_space_with_no_handle_and_reach_max_spam_score();
+            assert_noop!(
+                _create_default_space_with_welcome_post(),
+                PostsError::<TestRuntime>::SpamScoreTooHigh
+            );
+        });
+    }
+
+    #[test]
+    fn onboard_should_fail_when_spam_score_too_high() {
+        ExtBuilder::build().execute_with(|| {
+            _create_space_with_no_handle_and_reach_max_spam_score();
+            assert_noop!(_onboard(None, None), PostsError::<TestRuntime>::SpamScoreTooHigh);
+        });
+    }
+
+    // Reaction tests
+    #[test]
+    fn create_post_reaction_should_work_upvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+
+            // Check storages
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
+            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+
+            // Check post reaction counters
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 1);
+            assert_eq!(post.downvotes_count, 0);
+
+            // Check whether data stored correctly
+            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
+            assert_eq!(reaction.created.account, ACCOUNT2);
+            assert_eq!(reaction.kind, reaction_upvote());
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_work_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+
+            // Check storages
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
+            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+
+            // Check post reaction counters
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.downvotes_count, 1);
+
+            // Check whether data stored correctly
+            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
+            assert_eq!(reaction.created.account, ACCOUNT2);
+            assert_eq!(reaction.kind, reaction_downvote());
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_account_has_already_reacted() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            // Try to catch an error creating reaction by the same account
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::AccountAlreadyReacted);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Try to catch an error creating reaction by the same account
+            assert_noop!(_create_default_post_reaction(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_trying_to_react_in_hidden_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+
+            // Hide the space
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenSpaceHidden);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_trying_to_react_on_hidden_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+
+            // Hide the post
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenPostHidden);
+        });
+    }
+
+    #[test]
+    fn toggle_post_reaction_should_create_update_and_delete_reaction() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Create an upvote
+            assert_ok!(_toggle_post_reaction(None, None)); // ReactionId 1
+            assert_eq!(Reactions::post_reaction_id_by_account((ACCOUNT2, POST1)), REACTION1);
+            assert_eq!(Posts::post_by_id(POST1).unwrap().upvotes_count, 1);
+
+            // Change the upvote to a downvote
+            assert_ok!(_toggle_post_reaction(None, Some(reaction_downvote())));
+            assert_eq!(Reactions::reaction_by_id(REACTION1).unwrap().kind, reaction_downvote());
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.downvotes_count, 1);
+
+            // Remove the downvote
+            assert_ok!(_toggle_post_reaction(None, Some(reaction_downvote())));
+            assert!(Reactions::reaction_by_id(REACTION1).is_none());
+            assert!(Reactions::reaction_ids_by_post_id(POST1).is_empty());
+            assert_eq!(Posts::post_by_id(POST1).unwrap().downvotes_count, 0);
+            System::assert_last_event(Event::Reactions(pallet_reactions::RawEvent::PostReactionDeleted(
+                ACCOUNT2, POST1, REACTION1, reaction_downvote()
+            )));
+        });
+    }
+
+    #[test]
+    fn get_post_view_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+
+            let post_view = Reactions::get_post_view(POST1, Some(ACCOUNT2)).unwrap();
+            assert_eq!(post_view.post.id, POST1);
+            assert_eq!(post_view.post.upvotes_count, 1);
+            assert_eq!(post_view.space_handle, Some(space_handle()));
+            assert!(post_view.author_profile.is_some());
+            assert_eq!(post_view.viewer_reaction, Some(reaction_upvote()));
+
+            let anonymous_view = Reactions::get_post_view(POST1, None).unwrap();
+            assert!(anonymous_view.viewer_reaction.is_none());
+
+            assert!(Reactions::get_post_view(POST2, None).is_none());
+        });
+    }
+
+    #[test]
+    fn toggle_post_reaction_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_toggle_post_reaction(None, None), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+// Shares tests
+
+    #[test]
+    fn share_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
+            assert_eq!(Posts::next_post_id(), POST3);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+
+            // Check whether data stored correctly
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+
+            assert_eq!(shared_post.space_id, Some(SPACE2));
+            assert_eq!(shared_post.created.account, ACCOUNT2);
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_when_one_of_roles_is_permitted() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content,
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_for_share_own_post_in_same_own_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
                 None
+            )); // Share PostId 1
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2]);
+            assert_eq!(Posts::next_post_id(), POST3);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+
+            // Check whether data stored correctly
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(shared_post.space_id, Some(SPACE1));
+            assert_eq!(shared_post.created.account, ACCOUNT1);
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_original_post_not_found() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            // Skipped creating PostId 1
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::OriginalPostNotFound);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_trying_to_share_shared_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None)
+            );
+
+            // Try to share post with extension SharedPost
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST2)),
+                None
+            ), PostsError::<TestRuntime>::CannotShareSharingPost);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(None), // No space_handle provided (ok)
+                None, // Default space content,
+                None
+            )); // SpaceId 2 by ACCOUNT1
+
+            // Try to share post with extension SharedPost
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_delete_default_role());
+
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+    #[test]
+    fn update_original_post_content_should_notify_sharing_spaces() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(
+                None,
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2
+
+            assert_eq!(Posts::shared_version_by_sharing_post_id(POST2), 0);
+            assert!(!Posts::is_original_changed_since_shared(POST2, &extension_shared_post(POST1)));
+
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, Some(updated_post_content()), None))
+            ));
+
+            assert_eq!(Posts::content_version_by_post_id(POST1), 1);
+            assert!(Posts::is_original_changed_since_shared(POST2, &extension_shared_post(POST1)));
+            System::assert_has_event(Event::Posts(pallet_posts::RawEvent::SharedOriginalUpdated(SPACE2, POST1, 1)));
+        });
+    }
+
+    #[test]
+    fn update_original_post_content_should_notify_limited_number_of_sharing_spaces() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), Some(extension_shared_post(POST1)), None)); // PostId 2
+            assert_ok!(_create_post(None, Some(Some(SPACE1)), Some(extension_shared_post(POST1)), None)); // PostId 3
+
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, Some(updated_post_content()), None))
             ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
 
-            assert_ok!(_delete_default_role());
+            // `MaxSharesToNotifyOnUpdate` is 1
+            let notified_space_ids: Vec<SpaceId> = System::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    Event::Posts(pallet_posts::RawEvent::SharedOriginalUpdated(space_id, _, _)) => Some(space_id),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(notified_space_ids, vec![SPACE2]);
+
+            // Sharing posts in other spaces can still check whether the original has changed:
+            assert!(Posts::is_original_changed_since_shared(POST3, &extension_shared_post(POST1)));
+        });
+    }
+
+    #[test]
+    fn update_post_visibility_should_not_bump_content_version() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+            assert_eq!(Posts::content_version_by_post_id(POST1), 0);
+        });
+    }
+
+// Profiles tests
+
+    #[test]
+    fn create_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile()); // AccountId 1
+
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.created.account, ACCOUNT1);
+            assert!(profile.updated.is_none());
+            assert_eq!(profile.content, profile_content_ipfs());
+
+            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_profile_is_already_created() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_profile(
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_ok!(_update_profile(
+                None,
+                Some(space_content_ipfs())
+            ));
+
+            // Check whether profile updated correctly
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert!(profile.updated.is_some());
+            assert_eq!(profile.content, space_content_ipfs());
+
+            // Check whether profile history is written correctly
+            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
+            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
+        });
+    }
+
+    #[test]
+    fn update_profile_content_kind_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_profile_content_kind(None, None));
+
+            assert_eq!(Profiles::profile_content_kind_by_account(ACCOUNT1), Some(ContentKind::Markdown));
+        });
+    }
+
+    #[test]
+    fn update_profile_content_kind_should_fail_when_account_has_no_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
+            assert_noop!(
+                _update_profile_content_kind(None, None),
+                ProfilesError::<TestRuntime>::AccountHasNoProfile
+            );
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_social_account_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_account_has_no_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_update_profile(
+                None,
+                None
+            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_noop!(_update_profile(
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn update_profile_avatar_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_profile_avatar(None, None));
+            assert_eq!(Profiles::profile_avatar_by_account(ACCOUNT1), Some(profile_content_ipfs()));
+
+            assert_ok!(_update_profile_avatar(None, Some(None)));
+            assert!(Profiles::profile_avatar_by_account(ACCOUNT1).is_none());
+        });
+    }
+
+    #[test]
+    fn update_profile_banner_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(Profiles::update_profile_banner(Origin::signed(ACCOUNT1), Some(updated_space_content())));
+
+            assert_eq!(Profiles::profile_banner_by_account(ACCOUNT1), Some(updated_space_content()));
+            assert!(Profiles::profile_avatar_by_account(ACCOUNT1).is_none());
+        });
+    }
+
+    #[test]
+    fn update_profile_avatar_should_fail_when_account_has_no_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
+            assert_noop!(_update_profile_avatar(None, None), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+        });
+    }
+
+    #[test]
+    fn update_profile_avatar_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_noop!(
+                _update_profile_avatar(None, Some(Some(invalid_content_ipfs()))),
+                UtilsError::<TestRuntime>::InvalidIpfsCid
+            );
+        });
+    }
+
+    #[test]
+    fn update_profile_avatar_should_fail_when_avatar_is_the_same() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_profile_avatar(None, None));
+            assert_noop!(_update_profile_avatar(None, None), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+        });
+    }
+
+    #[test]
+    fn update_content_defaults_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_update_content_defaults(None, None));
+            assert_eq!(Profiles::content_defaults_by_account(ACCOUNT1), Some(content_defaults()));
+
+            assert_ok!(_update_content_defaults(None, Some(None)));
+            assert!(Profiles::content_defaults_by_account(ACCOUNT1).is_none());
+        });
+    }
+
+    #[test]
+    fn update_content_defaults_should_fail_when_license_is_too_long() {
+        ExtBuilder::build().execute_with(|| {
+            let defaults = ContentDefaults {
+                license: Some(vec![b'A'; MAX_LICENSE_LEN + 1]),
+                ..content_defaults()
+            };
+
+            assert_noop!(
+                _update_content_defaults(None, Some(Some(defaults))),
+                UtilsError::<TestRuntime>::LicenseIsTooLong
+            );
+        });
+    }
+
+    #[test]
+    fn import_profile_settings_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_profile_avatar(None, None));
+            assert_ok!(_update_content_defaults(None, None));
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+
+            assert_ok!(Profiles::export_profile_settings(Origin::signed(ACCOUNT1), ACCOUNT2));
+            assert_ok!(_import_profile_settings(None, None, None));
+
+            assert_eq!(Profiles::profile_settings(&ACCOUNT2), Profiles::profile_settings(&ACCOUNT1));
+            assert_eq!(Profiles::profile_avatar_by_account(ACCOUNT2), Some(profile_content_ipfs()));
+            assert_eq!(Profiles::content_defaults_by_account(ACCOUNT2), Some(content_defaults()));
+
+            assert_noop!(
+                _import_profile_settings(None, None, None),
+                ProfilesError::<TestRuntime>::SettingsBundleAlreadyImported
+            );
+        });
+    }
+
+    #[test]
+    fn import_profile_settings_should_work_when_same_settings_exported_again() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+            assert_ok!(_import_profile_settings(None, None, None));
+
+            System::set_block_number(2);
+            assert_ok!(_import_profile_settings(None, None, None));
+        });
+    }
+
+    #[test]
+    fn import_profile_settings_should_replace_muted_threads() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_profile());
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+            assert_ok!(Posts::mute_thread(Origin::signed(ACCOUNT1), POST1));
+            assert_ok!(Posts::mute_thread(Origin::signed(ACCOUNT2), POST2));
+
+            assert_ok!(_import_profile_settings(None, None, None));
+
+            assert_eq!(Posts::muted_thread_ids_by_account(ACCOUNT2), vec![POST1]);
+            assert!(Posts::thread_muted_by_account((ACCOUNT2, POST1)));
+            assert!(!Posts::thread_muted_by_account((ACCOUNT2, POST2)));
+        });
+    }
+
+    #[test]
+    fn import_profile_settings_should_fail_when_too_many_muted_threads() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+
+            let mut bundle = profile_settings_bundle();
+            bundle.settings.muted_thread_ids = vec![POST1; MaxMutedThreadsPerAccount::get() as usize + 1];
+
+            assert_noop!(
+                _import_profile_settings(None, Some(bundle), None),
+                PostsError::<TestRuntime>::TooManyMutedThreads
+            );
+        });
+    }
+
+    #[test]
+    fn import_profile_settings_should_fail_when_bundle_is_not_signed_by_exporter() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+
+            assert_noop!(
+                _import_profile_settings(None, None, Some(ACCOUNT2)),
+                ProfilesError::<TestRuntime>::InvalidSettingsBundleSignature
+            );
+        });
+    }
+
+    #[test]
+    fn import_profile_settings_should_fail_when_origin_is_not_bundle_recipient() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+
+            assert_noop!(
+                _import_profile_settings(Some(Origin::signed(ACCOUNT3)), None, None),
+                ProfilesError::<TestRuntime>::NotSettingsBundleRecipient
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_should_use_content_defaults_of_creator() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_update_content_defaults(None, None));
+            assert_ok!(_create_default_post()); // PostId 1
+
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Posts::license_by_post_id(POST1), content_defaults().license);
+            assert_eq!(Posts::language_by_post_id(POST1), content_defaults().language);
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).hidden_posts_count, 1);
+        });
+    }
+
+    #[test]
+    fn create_post_with_options_should_override_content_defaults() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_update_content_defaults(None, None));
+            assert_ok!(Posts::create_post_with_options(
+                Origin::signed(ACCOUNT1),
+                Some(SPACE1),
+                extension_regular_post(),
+                post_content_ipfs(),
+                PostOptions {
+                    license: None,
+                    language: Some(b"uk".to_vec()),
+                    hidden: Some(false),
+                    content_kind: None,
+                }
+            )); // PostId 1
+
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Posts::license_by_post_id(POST1), content_defaults().license);
+            assert_eq!(Posts::language_by_post_id(POST1), Some(b"uk".to_vec()));
+        });
+    }
+
+// Profile badges tests
 
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+    #[test]
+    fn first_post_badge_should_be_awarded_once() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_eq!(Profiles::badges_by_account(ACCOUNT1), vec![Badge::FirstPost]);
+
+            assert_ok!(_create_default_post());
+            assert_eq!(Profiles::badges_by_account(ACCOUNT1), vec![Badge::FirstPost]);
         });
     }
 
-// Profiles tests
+    #[test]
+    fn followers_milestone_badge_should_be_awarded_when_threshold_reached() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert!(!Profiles::has_badge(&ACCOUNT1, Badge::FollowersMilestone));
+
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT3)), None));
+            assert!(Profiles::has_badge(&ACCOUNT1, Badge::FollowersMilestone));
+            System::assert_has_event(Event::Profiles(
+                pallet_profiles::RawEvent::BadgeAwarded(ACCOUNT1, Badge::FollowersMilestone)
+            ));
+        });
+    }
 
     #[test]
-    fn create_profile_should_work() {
+    fn account_anniversary_badge_should_be_awarded_after_threshold() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile()); // AccountId 1
+            assert_ok!(_create_default_profile());
 
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert_eq!(profile.created.account, ACCOUNT1);
-            assert!(profile.updated.is_none());
-            assert_eq!(profile.content, profile_content_ipfs());
+            System::set_block_number(AccountAgeBadgeThreshold::get());
+            assert_ok!(_update_profile(None, Some(space_content_ipfs())));
+            assert!(!Profiles::has_badge(&ACCOUNT1, Badge::AccountAnniversary));
 
-            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
+            System::set_block_number(1 + AccountAgeBadgeThreshold::get());
+            assert_ok!(_update_profile(None, Some(post_content_ipfs())));
+            assert!(Profiles::has_badge(&ACCOUNT1, Badge::AccountAnniversary));
         });
     }
 
+// Account deactivation tests
+
     #[test]
-    fn create_profile_should_fail_when_profile_is_already_created() {
-        ExtBuilder::build().execute_with(|| {
+    fn deactivate_account_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
             assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+            assert_ok!(_deactivate_account(None));
+
+            assert_eq!(Profiles::account_deactivated_at(ACCOUNT1), Some(System::block_number()));
+
+            // Profile and posts are kept in storage, but the profile is hidden in RPC
+            // and posts are marked as posts of a deactivated author.
+            assert!(Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.is_some());
+            let flat_social_account = Profiles::get_social_accounts_by_ids(vec![ACCOUNT1]).pop().unwrap();
+            assert!(flat_social_account.profile.is_none());
+
+            let flat_post = Posts::get_posts_by_ids(vec![POST1], 0, 1).pop().unwrap();
+            assert_eq!(flat_post.is_author_deactivated, Some(true));
         });
     }
 
     #[test]
-    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
+    fn deactivate_account_should_fail_when_social_account_not_found() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_create_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+            assert_noop!(_deactivate_account(None), ProfilesError::<TestRuntime>::SocialAccountNotFound);
         });
     }
 
     #[test]
-    fn update_profile_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_ok!(_update_profile(
-                None,
-                Some(space_content_ipfs())
-            ));
+    fn deactivate_account_should_fail_when_account_is_already_deactivated() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_deactivate_account(None));
+            assert_noop!(_deactivate_account(None), ProfilesError::<TestRuntime>::AccountAlreadyDeactivated);
+        });
+    }
 
-            // Check whether profile updated correctly
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert!(profile.updated.is_some());
-            assert_eq!(profile.content, space_content_ipfs());
+    #[test]
+    fn reactivate_account_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_deactivate_account(None));
+            assert_ok!(_reactivate_account(None));
 
-            // Check whether profile history is written correctly
-            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
-            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
+            assert!(Profiles::account_deactivated_at(ACCOUNT1).is_none());
+            assert_ok!(_create_default_post());
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_social_account_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
+    fn reactivate_account_should_fail_when_account_is_not_deactivated() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_reactivate_account(None), ProfilesError::<TestRuntime>::AccountIsNotDeactivated);
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_account_has_no_profile() {
+    fn update_profile_should_fail_when_account_is_deactivated() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+            assert_ok!(_create_default_profile());
+            assert_ok!(_deactivate_account(None));
+
+            assert_noop!(
+                _update_profile(None, Some(space_content_ipfs())),
+                UtilsError::<TestRuntime>::AccountIsDeactivated
+            );
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_update_profile(
-                None,
-                None
-            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+    fn create_post_should_fail_when_account_is_deactivated() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_deactivate_account(None));
+            assert_noop!(_create_default_post(), UtilsError::<TestRuntime>::AccountIsDeactivated);
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            assert_noop!(_update_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+    fn create_post_reaction_should_fail_when_account_is_deactivated() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_deactivate_account(None));
+            assert_noop!(_create_default_post_reaction(), UtilsError::<TestRuntime>::AccountIsDeactivated);
+        });
+    }
+
+    #[test]
+    fn follow_account_should_fail_when_followed_account_is_deactivated() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_deactivate_account(None));
+            assert_noop!(
+                _default_follow_account(), // ACCOUNT2 follows ACCOUNT1
+                ProfileFollowsError::<TestRuntime>::CannotFollowDeactivatedAccount
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_follower_is_deactivated() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+            assert_ok!(_deactivate_account(Some(Origin::signed(ACCOUNT2))));
+
+            assert_noop!(_default_follow_space(), UtilsError::<TestRuntime>::AccountIsDeactivated);
         });
     }
 
@@ -3087,7 +5209,7 @@ mod tests {
         ExtBuilder::build_with_space().execute_with(|| {
             assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).followers_count, 2);
             assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
             assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
             assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), true);
@@ -3130,7 +5252,7 @@ mod tests {
             // Follow SpaceId 1 by ACCOUNT2
             assert_ok!(_default_unfollow_space());
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).followers_count, 1);
             assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
             assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1]);
         });
@@ -3150,6 +5272,194 @@ mod tests {
         });
     }
 
+    #[test]
+    fn follow_spaces_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 2 by ACCOUNT1
+
+            assert_ok!(_follow_spaces(None, Some(vec![SPACE1, SPACE2])));
+
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).followers_count, 2);
+            assert_eq!(Spaces::space_stats_by_id(SPACE2).followers_count, 2);
+            assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1, SPACE2]);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_spaces_count, 2);
+        });
+    }
+
+    #[test]
+    fn follow_spaces_should_skip_spaces_that_cannot_be_followed() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 2 by ACCOUNT1
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            let unknown_space_id = SPACE2 + 1;
+            assert_ok!(_follow_spaces(None, Some(vec![SPACE1, unknown_space_id, SPACE2])));
+
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).followers_count, 2);
+            assert_eq!(Spaces::space_stats_by_id(SPACE2).followers_count, 2);
+            assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1, SPACE2]);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_spaces_count, 2);
+
+            System::assert_last_event(Event::SpaceFollows(pallet_space_follows::RawEvent::SpacesFollowed(
+                ACCOUNT2,
+                vec![SPACE2],
+                vec![SPACE1, unknown_space_id],
+            )));
+        });
+    }
+
+    #[test]
+    fn follow_spaces_should_fail_when_batch_is_empty() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _follow_spaces(None, Some(vec![])),
+                SpaceFollowsError::<TestRuntime>::NoSpacesInBatch
+            );
+        });
+    }
+
+    #[test]
+    fn follow_spaces_should_fail_when_too_many_spaces_in_batch() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _follow_spaces(None, Some(vec![SPACE1; MaxSpacesPerBatch::get() as usize + 1])),
+                SpaceFollowsError::<TestRuntime>::TooManySpacesInBatch
+            );
+        });
+    }
+
+    #[test]
+    fn unfollow_spaces_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 2 by ACCOUNT1
+            assert_ok!(_follow_spaces(None, Some(vec![SPACE1, SPACE2])));
+
+            assert_ok!(_unfollow_spaces(None, Some(vec![SPACE1, SPACE2])));
+
+            assert_eq!(Spaces::space_stats_by_id(SPACE1).followers_count, 1);
+            assert_eq!(Spaces::space_stats_by_id(SPACE2).followers_count, 1);
+            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_spaces_count, 0);
+        });
+    }
+
+    #[test]
+    fn unfollow_spaces_should_skip_spaces_not_followed() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 2 by ACCOUNT1
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_ok!(_unfollow_spaces(None, Some(vec![SPACE2, SPACE1])));
+
+            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
+            System::assert_last_event(Event::SpaceFollows(pallet_space_follows::RawEvent::SpacesUnfollowed(
+                ACCOUNT2,
+                vec![SPACE1],
+                vec![SPACE2],
+            )));
+        });
+    }
+
+// Space-to-space following tests
+
+    fn _follow_space_by_space(
+        origin: Option<Origin>,
+        follower_space_id: Option<SpaceId>,
+        space_id: Option<SpaceId>,
+    ) -> DispatchResult {
+        SpaceFollows::follow_space_by_space(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            follower_space_id.unwrap_or(SPACE2),
+            space_id.unwrap_or(SPACE1),
+        )
+    }
+
+    fn _unfollow_space_by_space(
+        origin: Option<Origin>,
+        follower_space_id: Option<SpaceId>,
+        space_id: Option<SpaceId>,
+    ) -> DispatchResult {
+        SpaceFollows::unfollow_space_by_space(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            follower_space_id.unwrap_or(SPACE2),
+            space_id.unwrap_or(SPACE1),
+        )
+    }
+
+    #[test]
+    fn follow_space_by_space_should_work() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_follow_space_by_space(None, None, None));
+
+            assert!(SpaceFollows::space_followed_by_space(SPACE2, SPACE1));
+            assert_eq!(SpaceFollows::spaces_followed_by_space(SPACE2), vec![SPACE1]);
+            assert_eq!(SpaceFollows::follower_spaces_by_space(SPACE1), vec![SPACE2]);
+
+            assert_eq!(SpaceFollows::space_follows_counts_by_space(SPACE2).following_spaces_count, 1);
+            assert_eq!(SpaceFollows::space_follows_counts_by_space(SPACE1).follower_spaces_count, 1);
+
+            System::assert_last_event(Event::SpaceFollows(
+                pallet_space_follows::RawEvent::SpaceFollowedBySpace(ACCOUNT1, SPACE2, SPACE1)
+            ));
+        });
+    }
+
+    #[test]
+    fn follow_space_by_space_should_fail_when_not_a_follower_space_owner() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_noop!(
+                _follow_space_by_space(Some(Origin::signed(ACCOUNT2)), None, None),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_by_space_should_fail_when_following_itself() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_noop!(
+                _follow_space_by_space(None, Some(SPACE1), Some(SPACE1)),
+                SpaceFollowsError::<TestRuntime>::SpaceCannotFollowItself
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_by_space_should_fail_when_already_following() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_follow_space_by_space(None, None, None));
+            assert_noop!(
+                _follow_space_by_space(None, None, None),
+                SpaceFollowsError::<TestRuntime>::SpaceAlreadyFollowsSpace
+            );
+        });
+    }
+
+    #[test]
+    fn unfollow_space_by_space_should_work() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_follow_space_by_space(None, None, None));
+            assert_ok!(_unfollow_space_by_space(None, None, None));
+
+            assert!(!SpaceFollows::space_followed_by_space(SPACE2, SPACE1));
+            assert!(SpaceFollows::spaces_followed_by_space(SPACE2).is_empty());
+            assert!(SpaceFollows::follower_spaces_by_space(SPACE1).is_empty());
+
+            assert_eq!(SpaceFollows::space_follows_counts_by_space(SPACE2).following_spaces_count, 0);
+            assert_eq!(SpaceFollows::space_follows_counts_by_space(SPACE1).follower_spaces_count, 0);
+        });
+    }
+
+    #[test]
+    fn unfollow_space_by_space_should_fail_when_not_following() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_noop!(
+                _unfollow_space_by_space(None, None, None),
+                SpaceFollowsError::<TestRuntime>::SpaceDoesNotFollowSpace
+            );
+        });
+    }
+
 // Account following tests
 
     #[test]
@@ -3174,44 +5484,97 @@ mod tests {
     }
 
     #[test]
-    fn follow_account_should_fail_when_account_is_already_following_account() {
+    fn follow_account_should_fail_when_account_is_already_following_account() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+
+            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_default_unfollow_account());
+
+            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(ProfileFollows::account_followers(ACCOUNT1).is_empty());
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_unfollow_account(
+                None,
+                Some(ACCOUNT2)
+            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert_ok!(_default_unfollow_account());
+
+            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
+        });
+    }
+
+    #[test]
+    fn follow_accounts_should_work() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
+            assert_ok!(_follow_accounts(None, Some(vec![ACCOUNT1, ACCOUNT3])));
 
-            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
+            assert_eq!(ProfileFollows::accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1, ACCOUNT3]);
+            assert_eq!(ProfileFollows::account_followers(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(ProfileFollows::account_followers(ACCOUNT3), vec![ACCOUNT2]);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_accounts_count, 2);
         });
     }
 
     #[test]
-    fn unfollow_account_should_work() {
+    fn follow_accounts_should_skip_accounts_that_cannot_be_followed() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            // Follow ACCOUNT1 by ACCOUNT2
-            assert_ok!(_default_unfollow_account());
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
 
-            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
-            assert!(ProfileFollows::account_followers(ACCOUNT1).is_empty());
-            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+            assert_ok!(_follow_accounts(None, Some(vec![ACCOUNT1, ACCOUNT2, ACCOUNT3])));
+
+            assert_eq!(ProfileFollows::accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1, ACCOUNT3]);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_accounts_count, 2);
+
+            System::assert_last_event(Event::ProfileFollows(pallet_profile_follows::RawEvent::AccountsFollowed(
+                ACCOUNT2,
+                vec![ACCOUNT3],
+                vec![ACCOUNT1, ACCOUNT2],
+            )));
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
+    fn follow_accounts_should_fail_when_too_many_accounts_in_batch() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_unfollow_account(
-                None,
-                Some(ACCOUNT2)
-            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
+            assert_noop!(
+                _follow_accounts(None, Some(vec![ACCOUNT1; MaxAccountsPerBatch::get() as usize + 1])),
+                ProfileFollowsError::<TestRuntime>::TooManyAccountsInBatch
+            );
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
+    fn unfollow_accounts_should_work() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            assert_ok!(_default_unfollow_account());
+            assert_ok!(_follow_accounts(None, Some(vec![ACCOUNT1, ACCOUNT3])));
 
-            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
+            assert_ok!(_unfollow_accounts(None, Some(vec![ACCOUNT1, ACCOUNT3])));
+
+            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(ProfileFollows::account_followers(ACCOUNT1).is_empty());
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_accounts_count, 0);
         });
     }
 
@@ -3255,6 +5618,72 @@ mod tests {
         });
     }
 
+    #[test]
+    fn transfer_space_ownership_and_freeze_should_freeze_space_until_accepted() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership_and_freeze());
+            assert!(Spaces::is_space_frozen(SPACE1));
+
+            assert_noop!(
+                _update_space(None, None, Some(space_update(Some(Some(b"new_handle".to_vec())), None, None))),
+                SpacesError::<TestRuntime>::SpaceIsFrozen
+            );
+            assert_noop!(_create_default_role(), RolesError::<TestRuntime>::SpaceIsFrozen);
+
+            // Content can still be updated while a space is frozen:
+            assert_ok!(_update_space(None, None, Some(space_update(None, Some(updated_space_content()), None))));
+
+            assert_ok!(_accept_default_pending_ownership());
+            assert!(!Spaces::is_space_frozen(SPACE1));
+        });
+    }
+
+    #[test]
+    fn transfer_space_ownership_should_fail_when_frozen_transfer_redirected_to_another_account() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership_and_freeze());
+            assert_noop!(
+                _transfer_space_ownership(None, None, Some(ACCOUNT3)),
+                SpaceOwnershipError::<TestRuntime>::CannotRedirectFrozenTransfer
+            );
+
+            assert!(Spaces::is_space_frozen(SPACE1));
+        });
+    }
+
+    #[test]
+    fn transfer_space_ownership_should_keep_space_frozen_when_reissued_to_same_recipient() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership_and_freeze());
+            assert_ok!(_transfer_space_ownership(None, None, Some(ACCOUNT2)));
+
+            assert!(Spaces::is_space_frozen(SPACE1));
+        });
+    }
+
+    #[test]
+    fn reject_pending_ownership_should_unfreeze_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership_and_freeze());
+            assert_ok!(_reject_default_pending_ownership());
+
+            assert!(!Spaces::is_space_frozen(SPACE1));
+        });
+    }
+
+    #[test]
+    fn reject_pending_ownership_should_fail_when_frozen_transfer_rejected_by_current_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership_and_freeze());
+            assert_noop!(
+                _reject_default_pending_ownership_by_current_owner(),
+                SpaceOwnershipError::<TestRuntime>::OnlyRecipientCanRejectFrozenTransfer
+            );
+
+            assert!(Spaces::is_space_frozen(SPACE1));
+        });
+    }
+
     #[test]
     fn accept_pending_ownership_should_work() {
         ExtBuilder::build_with_space().execute_with(|| {
@@ -3372,4 +5801,370 @@ mod tests {
             ), SpaceOwnershipError::<TestRuntime>::NotAllowedToRejectOwnershipTransfer); // Rejecting a transfer from ACCOUNT2
         });
     }
+
+    // Space categories tests
+
+    #[test]
+    fn create_category_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_category());
+            assert_ok!(_create_category(None, Some(Some(CATEGORY1))));
+
+            let subcategory = SpaceCategories::category_by_id(CATEGORY2).unwrap();
+            assert_eq!(subcategory.parent_id, Some(CATEGORY1));
+            assert_eq!(subcategory.depth, 1);
+            assert_eq!(SpaceCategories::subcategory_ids_by_category(CATEGORY1), vec![CATEGORY2]);
+            assert_eq!(SpaceCategories::categories_count(), 2);
+        });
+    }
+
+    #[test]
+    fn create_category_should_fail_when_origin_is_not_root() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_category(Some(Origin::signed(ACCOUNT1)), None), DispatchError::BadOrigin);
+        });
+    }
+
+    #[test]
+    fn create_category_should_fail_when_tree_is_too_deep() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_category());
+            assert_ok!(_create_category(None, Some(Some(CATEGORY1))));
+
+            assert_noop!(
+                _create_category(None, Some(Some(CATEGORY2))),
+                SpaceCategoriesError::<TestRuntime>::CategoryTooDeep
+            );
+        });
+    }
+
+    #[test]
+    fn create_category_should_fail_when_too_many_categories() {
+        ExtBuilder::build().execute_with(|| {
+            for _ in 0..MaxCategories::get() {
+                assert_ok!(_create_default_category());
+            }
+
+            assert_noop!(_create_default_category(), SpaceCategoriesError::<TestRuntime>::TooManyCategories);
+        });
+    }
+
+    #[test]
+    fn approve_space_placement_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_category());
+            assert_ok!(_request_default_space_placement());
+            assert_eq!(SpaceCategories::pending_category_by_space(SPACE1), Some(CATEGORY1));
+
+            assert_ok!(_approve_space_placement(None));
+
+            assert!(SpaceCategories::pending_category_by_space(SPACE1).is_none());
+            assert_eq!(SpaceCategories::category_id_by_space(SPACE1), Some(CATEGORY1));
+            assert_eq!(SpaceCategories::spaces_by_category(CATEGORY1), vec![SPACE1]);
+        });
+    }
+
+    #[test]
+    fn approve_space_placement_should_move_space_to_another_category() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_category());
+            assert_ok!(_create_default_category());
+            assert_ok!(_request_default_space_placement());
+            assert_ok!(_approve_space_placement(None));
+
+            assert_ok!(_request_space_placement(None, None, Some(CATEGORY2)));
+            assert_ok!(_approve_space_placement(None));
+
+            assert_eq!(SpaceCategories::category_id_by_space(SPACE1), Some(CATEGORY2));
+            assert!(SpaceCategories::spaces_by_category(CATEGORY1).is_empty());
+            assert_eq!(SpaceCategories::spaces_by_category(CATEGORY2), vec![SPACE1]);
+        });
+    }
+
+    #[test]
+    fn approve_space_placement_should_fail_when_category_is_full() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_default_category());
+            assert_ok!(_request_default_space_placement());
+            assert_ok!(_approve_space_placement(None));
+
+            assert_ok!(_request_space_placement(None, Some(SPACE2), None));
+            assert_noop!(
+                _approve_space_placement(Some(SPACE2)),
+                SpaceCategoriesError::<TestRuntime>::TooManySpacesInCategory
+            );
+        });
+    }
+
+    #[test]
+    fn request_space_placement_should_fail_when_not_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_category());
+
+            assert_noop!(
+                _request_space_placement(Some(Origin::signed(ACCOUNT2)), None, None),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+        });
+    }
+
+    #[test]
+    fn reject_space_placement_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_category());
+            assert_ok!(_request_default_space_placement());
+
+            assert_ok!(SpaceCategories::reject_space_placement(Origin::root(), SPACE1));
+
+            assert!(SpaceCategories::pending_category_by_space(SPACE1).is_none());
+            assert!(SpaceCategories::category_id_by_space(SPACE1).is_none());
+        });
+    }
+
+    #[test]
+    fn remove_space_from_category_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_category());
+            assert_ok!(_request_default_space_placement());
+            assert_ok!(_approve_space_placement(None));
+
+            assert_ok!(SpaceCategories::remove_space_from_category(Origin::signed(ACCOUNT1), SPACE1));
+
+            assert!(SpaceCategories::category_id_by_space(SPACE1).is_none());
+            assert!(SpaceCategories::spaces_by_category(CATEGORY1).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_category_should_fail_when_category_has_spaces_or_subcategories() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_category());
+            assert_ok!(_create_category(None, Some(Some(CATEGORY1))));
+            assert_ok!(_request_space_placement(None, None, Some(CATEGORY2)));
+            assert_ok!(_approve_space_placement(None));
+
+            assert_noop!(
+                SpaceCategories::delete_category(Origin::root(), CATEGORY1),
+                SpaceCategoriesError::<TestRuntime>::CategoryHasSubcategories
+            );
+            assert_noop!(
+                SpaceCategories::delete_category(Origin::root(), CATEGORY2),
+                SpaceCategoriesError::<TestRuntime>::CategoryHasSpaces
+            );
+
+            assert_ok!(SpaceCategories::force_remove_space_from_category(Origin::root(), SPACE1));
+            assert_ok!(SpaceCategories::delete_category(Origin::root(), CATEGORY2));
+            assert_ok!(SpaceCategories::delete_category(Origin::root(), CATEGORY1));
+            assert_eq!(SpaceCategories::categories_count(), 0);
+        });
+    }
+
+    // Space subscriptions tests
+
+    #[test]
+    fn create_tier_should_fail_when_not_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_tier(Some(Origin::signed(ACCOUNT2)), None, None),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+        });
+    }
+
+    #[test]
+    fn create_tier_should_fail_when_period_is_too_short() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_tier(None, Some(MinSubscriptionPeriod::get() - 1), None),
+                SpaceSubscriptionsError::<TestRuntime>::SubscriptionPeriodTooShort
+            );
+        });
+    }
+
+    #[test]
+    fn create_tier_should_fail_when_price_is_zero() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                SpaceSubscriptions::create_tier(
+                    Origin::signed(ACCOUNT1),
+                    SPACE1,
+                    0,
+                    TIER_PERIOD,
+                    None,
+                    space_content_ipfs(),
+                ),
+                SpaceSubscriptionsError::<TestRuntime>::ZeroTierPrice
+            );
+        });
+    }
+
+    #[test]
+    fn subscribe_should_charge_first_period_and_grant_role() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_role()); // RoleId 1
+            assert_ok!(_create_default_tier()); // TierId 1
+            assert_ok!(_default_follow_space());
+
+            assert_ok!(_default_subscribe());
+
+            assert_eq!(Balances::free_balance(ACCOUNT2), 100 - TIER_PRICE);
+            assert_eq!(Balances::free_balance(ACCOUNT1), 100 + TIER_PRICE);
+            assert!(account2_has_role1());
+
+            let subscription = SpaceSubscriptions::subscription_by_account(ACCOUNT2, TIER1).unwrap();
+            assert_eq!(subscription.renews_at, 1 + TIER_PERIOD);
+            assert_eq!(SpaceSubscriptions::renewals_at(1 + TIER_PERIOD), vec![(ACCOUNT2, TIER1)]);
+        });
+    }
+
+    #[test]
+    fn subscribe_should_fail_when_account_is_not_space_follower() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_tier());
+            assert_noop!(_default_subscribe(), SpaceSubscriptionsError::<TestRuntime>::NotSpaceFollower);
+        });
+    }
+
+    #[test]
+    fn subscription_should_be_renewed_at_the_end_of_period() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_role());
+            assert_ok!(_create_default_tier());
+            assert_ok!(_default_follow_space());
+            assert_ok!(_default_subscribe());
+
+            SpaceSubscriptions::on_initialize(1 + TIER_PERIOD);
+
+            assert_eq!(Balances::free_balance(ACCOUNT2), 100 - 2 * TIER_PRICE);
+            assert!(account2_has_role1());
+
+            let subscription = SpaceSubscriptions::subscription_by_account(ACCOUNT2, TIER1).unwrap();
+            assert_eq!(subscription.renews_at, 1 + 2 * TIER_PERIOD);
+        });
+    }
+
+    #[test]
+    fn subscription_should_expire_and_revoke_role_when_subscriber_cannot_pay() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_role());
+            assert_ok!(_create_default_tier());
+            assert_ok!(_default_follow_space());
+            assert_ok!(_default_subscribe());
+
+            // Account 2 can pay for three periods only and must keep its existential deposit.
+            SpaceSubscriptions::on_initialize(1 + TIER_PERIOD);
+            SpaceSubscriptions::on_initialize(1 + 2 * TIER_PERIOD);
+            assert!(SpaceSubscriptions::subscription_by_account(ACCOUNT2, TIER1).is_some());
+
+            SpaceSubscriptions::on_initialize(1 + 3 * TIER_PERIOD);
+            assert!(SpaceSubscriptions::subscription_by_account(ACCOUNT2, TIER1).is_none());
+            assert_eq!(Balances::free_balance(ACCOUNT2), 100 - 3 * TIER_PRICE);
+            assert!(!account2_has_role1());
+        });
+    }
+
+    #[test]
+    fn cancelled_subscription_should_expire_at_the_end_of_period() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_role());
+            assert_ok!(_create_default_tier());
+            assert_ok!(_default_follow_space());
+            assert_ok!(_default_subscribe());
+
+            assert_ok!(SpaceSubscriptions::cancel_subscription(Origin::signed(ACCOUNT2), TIER1));
+            assert!(account2_has_role1());
+
+            SpaceSubscriptions::on_initialize(1 + TIER_PERIOD);
+            assert!(SpaceSubscriptions::subscription_by_account(ACCOUNT2, TIER1).is_none());
+            assert_eq!(Balances::free_balance(ACCOUNT2), 100 - TIER_PRICE);
+            assert!(!account2_has_role1());
+        });
+    }
+
+    #[test]
+    fn renewals_should_be_moved_to_next_block_when_block_is_full() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_tier(None, None, None));
+            assert_ok!(_default_follow_space());
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT3)), None));
+
+            assert_ok!(_default_subscribe());
+            assert_ok!(SpaceSubscriptions::subscribe(Origin::signed(ACCOUNT3), TIER1));
+
+            // `MaxRenewalsPerBlock` is 1
+            assert_eq!(SpaceSubscriptions::renewals_at(1 + TIER_PERIOD), vec![(ACCOUNT2, TIER1)]);
+            assert_eq!(SpaceSubscriptions::renewals_at(2 + TIER_PERIOD), vec![(ACCOUNT3, TIER1)]);
+        });
+    }
+
+    #[test]
+    fn subscribe_should_fail_when_renewal_queue_is_full() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_tier(None, None, None));
+            assert_ok!(_default_follow_space());
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT3)), None));
+
+            assert_ok!(_default_subscribe());
+            assert_ok!(SpaceSubscriptions::subscribe(Origin::signed(ACCOUNT3), TIER1));
+
+            // `MaxRenewalsPerBlock` is 1 and `MaxRenewalDelay` is 1
+            assert_noop!(
+                SpaceSubscriptions::subscribe(Origin::signed(ACCOUNT1), TIER1),
+                SpaceSubscriptionsError::<TestRuntime>::RenewalQueueIsFull
+            );
+        });
+    }
+
+    #[test]
+    fn expired_subscription_should_not_revoke_role_granted_manually() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_role());
+            assert_ok!(_grant_default_role());
+            assert_ok!(_create_default_tier());
+            assert_ok!(_default_follow_space());
+            assert_ok!(_default_subscribe());
+
+            let subscription = SpaceSubscriptions::subscription_by_account(ACCOUNT2, TIER1).unwrap();
+            assert_eq!(subscription.granted_role_id, None);
+
+            assert_ok!(SpaceSubscriptions::cancel_subscription(Origin::signed(ACCOUNT2), TIER1));
+            SpaceSubscriptions::on_initialize(1 + TIER_PERIOD);
+
+            assert!(SpaceSubscriptions::subscription_by_account(ACCOUNT2, TIER1).is_none());
+            assert!(account2_has_role1());
+        });
+    }
+
+    // Activity log tests
+
+    #[test]
+    fn activity_log_should_keep_only_recent_activities() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            let activities: Vec<(ActivityEntity, ActivityAction)> = ActivityLog::get_recent_activities(10)
+                .into_iter()
+                .map(|activity| (activity.entity, activity.action))
+                .collect();
+
+            // The first space creation was overwritten, as `MaxActivities` is 3
+            assert_eq!(ActivityLog::activities_count(), 4);
+            assert_eq!(activities, vec![
+                (ActivityEntity::Post(POST1), ActivityAction::PostReacted),
+                (ActivityEntity::Space(SPACE2), ActivityAction::SpaceCreated),
+                (ActivityEntity::Post(POST1), ActivityAction::PostCreated),
+            ]);
+
+            assert_eq!(ActivityLog::get_recent_activities(1).len(), 1);
+        });
+    }
+
+    #[test]
+    fn activity_log_should_not_log_scheduled_posts() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let count_before = ActivityLog::activities_count();
+
+            assert_ok!(_create_default_scheduled_post());
+            assert_eq!(ActivityLog::activities_count(), count_before);
+        });
+    }
 }
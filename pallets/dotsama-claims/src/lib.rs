@@ -31,13 +31,13 @@ pub mod pallet {
     use super::*;
     use frame_support::{
         ensure, pallet_prelude::*,
-        dispatch::{DispatchResult, DispatchResultWithPostInfo},
+        dispatch::{DispatchResult, DispatchResultWithPostInfo, PostDispatchInfo},
         traits::{Currency, ExistenceRequirement},
         weights::{DispatchClass, Pays},
     };
     use frame_system::pallet_prelude::*;
     use sp_runtime::traits::Zero;
-    use sp_std::vec::Vec;
+    use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
 
     use pallet_utils::BalanceOf;
 
@@ -83,7 +83,9 @@ pub mod pallet {
     pub enum Event<T: Config> {
         RewardsSenderSet(T::AccountId),
         RewardsSenderRemoved(),
-        EligibleAccountsAdded(u16),
+        /// Eligible accounts were added: the number of newly added accounts
+        /// and the number of accounts that were already eligible.
+        EligibleAccountsAdded(/* added */ u16, /* already present */ u16),
         TokensClaimed(T::AccountId, BalanceOf<T>),
     }
 
@@ -152,11 +154,17 @@ pub mod pallet {
             Ok(Pays::No.into())
         }
 
-        #[pallet::weight(
-            <T as Config>::WeightInfo::add_eligible_accounts(
+        /// Add accounts that are eligible to claim tokens.
+        /// Duplicates and accounts that are already eligible are skipped,
+        /// and the weight of skipped accounts is refunded.
+        #[pallet::weight((
+            Pallet::<T>::add_eligible_accounts_weight(
+                eligible_accounts.len() as u32,
                 eligible_accounts.len() as u32
-            )
-        )]
+            ),
+            DispatchClass::Normal,
+            Pays::No
+        ))]
         pub fn add_eligible_accounts(
             origin: OriginFor<T>,
             eligible_accounts: Vec<T::AccountId>,
@@ -168,16 +176,37 @@ pub mod pallet {
 
             ensure!(accounts_len <= accounts_set_limit, Error::<T>::AddingTooManyAccountsAtOnce);
 
-            for eligible_account in eligible_accounts {
+            let unique_accounts: BTreeSet<T::AccountId> = eligible_accounts.into_iter().collect();
+            let unique_len = unique_accounts.len() as u32;
+
+            let mut added: u32 = 0;
+            for eligible_account in unique_accounts {
+                if Self::eligible_accounts(&eligible_account) {
+                    continue;
+                }
+
                 <EligibleAccounts<T>>::insert(&eligible_account, true);
+                added += 1;
             }
 
-            Self::deposit_event(Event::EligibleAccountsAdded(accounts_len as u16));
-            Ok(Pays::No.into())
+            let already_present = unique_len - added;
+            Self::deposit_event(Event::EligibleAccountsAdded(added as u16, already_present as u16));
+
+            Ok(PostDispatchInfo {
+                actual_weight: Some(Self::add_eligible_accounts_weight(unique_len, added)),
+                pays_fee: Pays::No,
+            })
         }
     }
 
     impl<T: Config> Pallet<T> {
+        /// A weight of `add_eligible_accounts` that checks `checked` accounts
+        /// and inserts `added` of them.
+        pub(super) fn add_eligible_accounts_weight(checked: u32, added: u32) -> Weight {
+            <T as Config>::WeightInfo::add_eligible_accounts(added)
+                .saturating_add(T::DbWeight::get().reads(checked as Weight))
+        }
+
         pub(super) fn ensure_allowed_to_claim_tokens(who: &T::AccountId) -> DispatchResult {
             ensure!(Self::eligible_accounts(who), Error::<T>::AccountNotEligible);
             ensure!(Self::tokens_claimed_by_account(who).is_zero(), Error::<T>::TokensAlreadyClaimed);
@@ -93,6 +93,7 @@ impl pallet_utils::Config for Test {
     type Currency = Balances;
     type MinHandleLen = ();
     type MaxHandleLen = ();
+    type DeferredJobHandler = ();
 }
 
 parameter_types! {
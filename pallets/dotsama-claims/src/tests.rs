@@ -1,5 +1,5 @@
 use crate::{Error, mock::*, EligibleAccounts};
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{assert_ok, assert_noop, weights::Pays};
 use sp_runtime::DispatchError::BadOrigin;
 
 // Test `fn claim_tokens(..)`
@@ -132,3 +132,31 @@ fn add_eligible_accounts_should_fail_when_trying_to_add_accounts_over_limit() {
         assert_noop!(_add_eligible_accounts_over_limit(), Error::<Test>::AddingTooManyAccountsAtOnce);
     });
 }
+
+#[test]
+fn add_eligible_accounts_should_skip_duplicates_and_already_eligible_accounts() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_add_eligible_accounts(None, vec![ACCOUNT1]));
+
+        assert_ok!(_add_eligible_accounts(None, vec![ACCOUNT1, ACCOUNT2, ACCOUNT2, 3]));
+        System::assert_last_event(Event::DotsamaClaims(crate::Event::EligibleAccountsAdded(2, 1)));
+
+        assert_eq!(EligibleAccounts::<Test>::iter().count(), 3);
+        assert_eq!(DotsamaClaims::eligible_accounts(ACCOUNT2), true);
+        assert_eq!(DotsamaClaims::eligible_accounts(3), true);
+    });
+}
+
+#[test]
+fn add_eligible_accounts_should_refund_weight_of_skipped_accounts() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_add_eligible_accounts(None, vec![ACCOUNT1, ACCOUNT2]));
+
+        let post_info = _add_eligible_accounts(None, vec![ACCOUNT1, ACCOUNT2, ACCOUNT2]).unwrap();
+        System::assert_last_event(Event::DotsamaClaims(crate::Event::EligibleAccountsAdded(0, 2)));
+
+        assert_eq!(post_info.actual_weight, Some(DotsamaClaims::add_eligible_accounts_weight(2, 0)));
+        assert!(post_info.actual_weight.unwrap() <= DotsamaClaims::add_eligible_accounts_weight(3, 3));
+        assert_eq!(post_info.pays_fee, Pays::No);
+    });
+}
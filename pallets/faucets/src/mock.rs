@@ -102,6 +102,7 @@ impl pallet_utils::Config for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type DeferredJobHandler = ();
 }
 
 impl Config for Test {
@@ -1,6 +1,7 @@
 use crate::*;
 
 use frame_support::dispatch::DispatchError;
+use sp_runtime::traits::{One, Saturating};
 use pallet_posts::Module as Posts;
 use pallet_spaces::Space;
 use pallet_space_follows::Module as SpaceFollows;
@@ -11,6 +12,10 @@ impl<T: Config> Module<T> {
         Ok(Self::report_by_id(report_id).ok_or(Error::<T>::ReportNotFound)?)
     }
 
+    pub fn require_appeal(appeal_id: AppealId) -> Result<Appeal<T>, DispatchError> {
+        Ok(Self::appeal_by_id(appeal_id).ok_or(Error::<T>::AppealNotFound)?)
+    }
+
     /// Get entity space_id if it exists.
     /// Content and Account has no scope, consider check with `if let Some`
     fn get_entity_scope(entity: &EntityId<T::AccountId>) -> Result<Option<SpaceId>, DispatchError> {
@@ -55,10 +60,46 @@ impl<T: Config> Module<T> {
             EntityId::Space(space_id) => Spaces::<T>::try_move_space_to_root(*space_id)?,
             EntityId::Post(post_id) => Posts::<T>::delete_post_from_space(*post_id)?,
         }
-        StatusByEntityInSpace::<T>::insert(entity, scope, EntityStatus::Blocked);
+        Self::set_entity_status(entity, scope, Some(EntityStatus::Blocked));
+        Self::remove_block_expiry(entity, scope);
         Ok(())
     }
 
+    /// Set (or remove, if `None`) a status of an entity in a space
+    /// and notify `T::OnEntityStatusChanged` if the status has changed.
+    pub(crate) fn set_entity_status(
+        entity: &EntityId<T::AccountId>,
+        scope: SpaceId,
+        status_opt: Option<EntityStatus>,
+    ) {
+        let old_status = Self::status_by_entity_in_space(entity, scope);
+
+        match &status_opt {
+            Some(status) => StatusByEntityInSpace::<T>::insert(entity, scope, status),
+            None => StatusByEntityInSpace::<T>::remove(entity, scope),
+        }
+
+        if status_opt == Some(EntityStatus::Blocked) {
+            if old_status != status_opt {
+                BlockedAt::<T>::insert(entity, scope, <system::Pallet<T>>::block_number());
+            }
+        } else {
+            BlockedAt::<T>::remove(entity, scope);
+        }
+
+        if old_status != status_opt {
+            if let EntityId::Account(account) = entity {
+                if status_opt == Some(EntityStatus::Blocked) {
+                    BlocksCountByAccount::<T>::mutate(account, |count| *count = count.saturating_add(1));
+                } else if old_status == Some(EntityStatus::Blocked) {
+                    BlocksCountByAccount::<T>::mutate(account, |count| *count = count.saturating_sub(1));
+                }
+            }
+
+            T::OnEntityStatusChanged::on_entity_status_changed(entity, scope, old_status, status_opt);
+        }
+    }
+
     pub(crate) fn ensure_account_status_manager(who: T::AccountId, space: &Space<T>) -> DispatchResult {
         Spaces::<T>::ensure_account_has_space_permission(
             who,
@@ -68,6 +109,31 @@ impl<T: Config> Module<T> {
         )
     }
 
+    /// Moderators can always suggest a status. Followers of a space can suggest a status
+    /// too, if the space settings allow them to. Returns whether an account is a moderator.
+    pub(crate) fn ensure_account_can_suggest_status(
+        who: T::AccountId,
+        space: &Space<T>,
+        suggesters: StatusSuggesters,
+    ) -> Result<bool, DispatchError> {
+        let is_moderator = Spaces::<T>::ensure_account_has_space_permission(
+            who.clone(),
+            &space,
+            pallet_permissions::SpacePermission::SuggestEntityStatus,
+            Error::<T>::NoPermissionToSuggestEntityStatus.into(),
+        ).is_ok();
+
+        if !is_moderator {
+            ensure!(
+                suggesters == StatusSuggesters::ModeratorsAndFollowers
+                    && SpaceFollows::<T>::space_followed_by_account((who, space.id)),
+                Error::<T>::NoPermissionToSuggestEntityStatus
+            );
+        }
+
+        Ok(is_moderator)
+    }
+
     pub(crate) fn ensure_entity_in_scope(entity: &EntityId<T::AccountId>, scope: SpaceId) -> DispatchResult {
         if let Some(entity_scope) = Self::get_entity_scope(entity)? {
             ensure!(entity_scope == scope, Error::<T>::EntityNotInScope);
@@ -75,9 +141,158 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
-    pub fn default_autoblock_threshold_as_settings() -> SpaceModerationSettings {
+    /// An account can appeal a status of an entity it owns.
+    /// Content has no owner on-chain, so any account can appeal its status.
+    pub(crate) fn ensure_account_can_appeal(who: &T::AccountId, entity: &EntityId<T::AccountId>) -> DispatchResult {
+        let is_owner = match entity {
+            EntityId::Content(_) => true,
+            EntityId::Account(account_id) => account_id == who,
+            EntityId::Space(space_id) => Spaces::<T>::require_space(*space_id)?.is_owner(who),
+            EntityId::Post(post_id) => Posts::<T>::require_post(*post_id)?.is_owner(who),
+        };
+
+        ensure!(is_owner, Error::<T>::NoPermissionToAppealEntityStatus);
+        Ok(())
+    }
+
+    /// Entities blocked before `BlockedAt` was tracked can be appealed at any time.
+    pub(crate) fn ensure_appeal_window_not_expired(entity: &EntityId<T::AccountId>, scope: SpaceId) -> DispatchResult {
+        let appeal_window_opt = Self::moderation_settings_or_default(scope).appeal_window;
+
+        if let (Some(appeal_window), Some(blocked_at)) = (appeal_window_opt, Self::blocked_at(entity, scope)) {
+            let now = <system::Pallet<T>>::block_number();
+            ensure!(now <= blocked_at.saturating_add(appeal_window), Error::<T>::AppealWindowExpired);
+        }
+        Ok(())
+    }
+
+    /// Get an entity status in a space, considering that a temporary block may have expired.
+    pub fn entity_status_in_scope(entity: &EntityId<T::AccountId>, scope: SpaceId) -> Option<EntityStatus> {
+        let status = Self::status_by_entity_in_space(entity, scope);
+
+        if status == Some(EntityStatus::Blocked) {
+            if let Some(expires_at) = Self::block_expires_at(entity, scope) {
+                if <system::Pallet<T>>::block_number() >= expires_at {
+                    return None;
+                }
+            }
+        }
+
+        status
+    }
+
+    pub fn is_entity_blocked_in_scope(entity: &EntityId<T::AccountId>, scope: SpaceId) -> bool {
+        Self::entity_status_in_scope(entity, scope) == Some(EntityStatus::Blocked)
+    }
+
+    /// Make a `Blocked` status of an entity in a space expire after `expires_in` blocks.
+    /// Returns a block number at which the block expires.
+    pub(crate) fn schedule_block_expiry(
+        entity: &EntityId<T::AccountId>,
+        scope: SpaceId,
+        expires_in: T::BlockNumber,
+    ) -> T::BlockNumber {
+        let now = <system::Pallet<T>>::block_number();
+        let expires_at = now.saturating_add(expires_in);
+
+        BlockExpiresAt::<T>::insert(entity, scope, expires_at);
+        ExpiringBlocksAt::<T>::mutate(expires_at, |entities| entities.push((entity.clone(), scope)));
+
+        if Self::next_block_to_sweep().is_none() {
+            NextBlockToSweep::<T>::put(now);
+        }
+
+        expires_at
+    }
+
+    /// Removes an expiry of an entity block, if any. The entity stays in `ExpiringBlocksAt`
+    /// until it's swept, but is ignored there, as its expiry no longer matches.
+    pub(crate) fn remove_block_expiry(entity: &EntityId<T::AccountId>, scope: SpaceId) {
+        BlockExpiresAt::<T>::remove(entity, scope);
+    }
+
+    /// Removes expired blocks from storage, until `remaining_weight` is exhausted.
+    /// A bucket of `ExpiringBlocksAt` can be swept partially: its unprocessed entities are
+    /// written back and the sweep continues from them on the next `on_idle`.
+    /// Side effects of a block (e.g. a post removed from a space) are not reverted.
+    pub(crate) fn sweep_expired_blocks(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut consumed_weight = db_weight.reads_writes(1, 1);
+
+        if consumed_weight > remaining_weight {
+            return 0;
+        }
+
+        let mut block_to_sweep = match Self::next_block_to_sweep() {
+            Some(block_number) => block_number,
+            None => return consumed_weight,
+        };
+
+        // Reading a bucket and then removing or rewriting it.
+        let bucket_weight = db_weight.reads_writes(1, 1);
+        // Checking an entity expiry and resetting its status.
+        let entity_weight = db_weight.reads_writes(3, 4);
+
+        while block_to_sweep <= now {
+            if consumed_weight.saturating_add(bucket_weight) > remaining_weight {
+                break;
+            }
+            consumed_weight = consumed_weight.saturating_add(bucket_weight);
+
+            let mut expiring_blocks = Self::expiring_blocks_at(block_to_sweep);
+            let mut swept_count = 0;
+
+            for (entity, scope) in expiring_blocks.iter() {
+                if consumed_weight.saturating_add(entity_weight) > remaining_weight {
+                    break;
+                }
+                consumed_weight = consumed_weight.saturating_add(entity_weight);
+                swept_count += 1;
+
+                if Self::block_expires_at(entity, *scope) == Some(block_to_sweep) {
+                    Self::set_entity_status(entity, *scope, None);
+                    BlockExpiresAt::<T>::remove(entity, *scope);
+                    Self::deposit_event(RawEvent::EntityBlockExpired(*scope, entity.clone()));
+                }
+            }
+
+            if swept_count < expiring_blocks.len() {
+                // Keep the rest of the bucket to continue from it on the next sweep.
+                expiring_blocks.drain(..swept_count);
+                ExpiringBlocksAt::<T>::insert(block_to_sweep, expiring_blocks);
+                break;
+            }
+
+            ExpiringBlocksAt::<T>::remove(block_to_sweep);
+            block_to_sweep = block_to_sweep.saturating_add(One::one());
+        }
+
+        NextBlockToSweep::<T>::put(block_to_sweep);
+        consumed_weight
+    }
+
+    pub fn default_moderation_settings() -> SpaceModerationSettings<T::BlockNumber> {
         SpaceModerationSettings {
-            autoblock_threshold: Some(T::DefaultAutoblockThreshold::get())
+            autoblock_threshold: Some(T::DefaultAutoblockThreshold::get()),
+            accepted_entity_kinds: None,
+            appeal_window: None,
+            status_suggesters: StatusSuggesters::default(),
+        }
+    }
+
+    /// Get custom moderation settings of a space or the default ones, if there are none.
+    pub fn moderation_settings_or_default(space_id: SpaceId) -> SpaceModerationSettings<T::BlockNumber> {
+        Self::moderation_settings(space_id).unwrap_or_else(Self::default_moderation_settings)
+    }
+}
+
+impl<AccountId> EntityId<AccountId> {
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            EntityId::Content(_) => EntityKind::Content,
+            EntityId::Account(_) => EntityKind::Account,
+            EntityId::Space(_) => EntityKind::Space,
+            EntityId::Post(_) => EntityKind::Post,
         }
     }
 }
@@ -100,11 +315,37 @@ impl<T: Config> Report<T> {
     }
 }
 
+impl<T: Config> Appeal<T> {
+    pub fn new(
+        id: AppealId,
+        created_by: T::AccountId,
+        appealed_entity: EntityId<T::AccountId>,
+        scope: SpaceId,
+        reason: Content
+    ) -> Self {
+        Self {
+            id,
+            created: WhoAndWhen::<T>::new(created_by),
+            appealed_entity,
+            appealed_within: scope,
+            reason,
+            status: AppealStatus::Pending,
+            resolved: None,
+        }
+    }
+}
+
 impl<T: Config> SuggestedStatus<T> {
-    pub fn new(who: T::AccountId, status: Option<EntityStatus>, report_id: Option<ReportId>) -> Self {
+    pub fn new(
+        who: T::AccountId,
+        status: Option<EntityStatus>,
+        report_id: Option<ReportId>,
+        by_moderator: bool,
+    ) -> Self {
         Self {
             suggested: WhoAndWhen::<T>::new(who),
             status,
+            by_moderator,
             report_id
         }
     }
@@ -115,13 +356,19 @@ impl<T: Config> IsAccountBlocked<T::AccountId> for Module<T> {
     fn is_blocked_account(account: T::AccountId, scope: SpaceId) -> bool {
         let entity = EntityId::Account(account);
 
-        Self::status_by_entity_in_space(entity, scope) == Some(EntityStatus::Blocked)
+        Self::is_entity_blocked_in_scope(&entity, scope)
     }
 
     fn is_allowed_account(account: T::AccountId, scope: SpaceId) -> bool {
         let entity = EntityId::Account(account);
 
-        Self::status_by_entity_in_space(entity, scope) != Some(EntityStatus::Blocked)
+        !Self::is_entity_blocked_in_scope(&entity, scope)
+    }
+}
+
+impl<T: Config> AccountModerationFlags<T::AccountId> for Module<T> {
+    fn moderation_flags_count(account: T::AccountId) -> u32 {
+        Self::blocks_count_by_account(account)
     }
 }
 
@@ -129,13 +376,13 @@ impl<T: Config> IsSpaceBlocked for Module<T> {
     fn is_blocked_space(space_id: SpaceId, scope: SpaceId) -> bool {
         let entity = EntityId::Space(space_id);
 
-        Self::status_by_entity_in_space(entity, scope) == Some(EntityStatus::Blocked)
+        Self::is_entity_blocked_in_scope(&entity, scope)
     }
 
     fn is_allowed_space(space_id: SpaceId, scope: SpaceId) -> bool {
         let entity = EntityId::Space(space_id);
 
-        Self::status_by_entity_in_space(entity, scope) != Some(EntityStatus::Blocked)
+        !Self::is_entity_blocked_in_scope(&entity, scope)
     }
 }
 
@@ -143,13 +390,13 @@ impl<T: Config> IsPostBlocked<PostId> for Module<T> {
     fn is_blocked_post(post_id: PostId, scope: SpaceId) -> bool {
         let entity = EntityId::Post(post_id);
 
-        Self::status_by_entity_in_space(entity, scope) == Some(EntityStatus::Blocked)
+        Self::is_entity_blocked_in_scope(&entity, scope)
     }
 
     fn is_allowed_post(post_id: PostId, scope: SpaceId) -> bool {
         let entity = EntityId::Post(post_id);
 
-        Self::status_by_entity_in_space(entity, scope) != Some(EntityStatus::Blocked)
+        !Self::is_entity_blocked_in_scope(&entity, scope)
     }
 }
 
@@ -157,12 +404,12 @@ impl<T: Config> IsContentBlocked for Module<T> {
     fn is_blocked_content(content: Content, scope: SpaceId) -> bool {
         let entity = EntityId::Content(content);
 
-        Self::status_by_entity_in_space(entity, scope) == Some(EntityStatus::Blocked)
+        Self::is_entity_blocked_in_scope(&entity, scope)
     }
 
     fn is_allowed_content(content: Content, scope: SpaceId) -> bool {
         let entity = EntityId::Content(content);
 
-        Self::status_by_entity_in_space(entity, scope) != Some(EntityStatus::Blocked)
+        !Self::is_entity_blocked_in_scope(&entity, scope)
     }
 }
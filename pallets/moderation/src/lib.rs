@@ -15,21 +15,35 @@
 //!
 //! - A post cannot be added to a space if an IPFS CID of this post is blocked in this space.
 //! - An account cannot create posts in a space if this account is blocked in this space.
+//!
+//! An owner of a blocked entity can appeal its status within a space. Any account with
+//! a permission to update entity statuses in this space can then either accept the appeal,
+//! which lifts the block, or reject it.
+//!
+//! A block can be temporary: a space owner can set it to expire after a number of blocks.
+//! An expired block is no longer taken into account and is removed from storage in `on_idle`.
+//!
+//! Each space can have its own moderation settings, editable by accounts with a permission
+//! to update space settings: an autoblock threshold, what kinds of entities can be reported
+//! in the space, how long a blocked entity can be appealed, and who can suggest statuses.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Encode, Decode};
 use scale_info::TypeInfo;
-use sp_std::prelude::*;
-use sp_runtime::RuntimeDebug;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+use sp_runtime::{RuntimeDebug, traits::{Saturating, Zero}};
 use frame_support::{
     decl_module, decl_storage, decl_event, decl_error, ensure,
     dispatch::DispatchResult,
     traits::Get,
+    weights::Weight,
 };
 use frame_system::{self as system, ensure_signed};
 
-use pallet_utils::{Content, WhoAndWhen, SpaceId, Module as Utils, PostId};
+use pallet_utils::{Content, WhoAndWhen, SpaceId, Module as Utils, PostId, remove_from_vec};
 use pallet_spaces::Module as Spaces;
 
 // TODO: move all tests to df-integration-tests
@@ -41,9 +55,13 @@ mod tests;
 
 pub mod functions;
 
+pub mod rpc;
+
 pub type ReportId = u64;
+pub type AppealId = u64;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum EntityId<AccountId> {
     Content(Content),
     Account(AccountId),
@@ -51,10 +69,21 @@ pub enum EntityId<AccountId> {
     Post(PostId),
 }
 
+/// A kind of `EntityId`, used to restrict what entities can be reported in a space.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EntityKind {
+    Content,
+    Account,
+    Space,
+    Post,
+}
+
 /// Entity status is used in two cases: when moderators suggest a moderation status
 /// for a reported entity; or when a space owner makes a final decision to either block
 /// or allow this entity within the space.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Deserialize))]
 pub enum EntityStatus {
     Allowed,
     Blocked,
@@ -84,18 +113,69 @@ pub struct SuggestedStatus<T: Config> {
     status: Option<EntityStatus>,
     /// `None` if a suggested status is not based on any reports.
     report_id: Option<ReportId>,
+    /// Whether this status was suggested by an account with a `SuggestEntityStatus` permission.
+    /// Only such suggestions count towards an autoblock threshold.
+    by_moderator: bool,
+}
+
+/// A state of an appeal against an entity status.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum AppealStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Appeal<T: Config> {
+    id: AppealId,
+    created: WhoAndWhen<T>,
+    /// An id of appealed entity: account, space, post or IPFS CID.
+    appealed_entity: EntityId<T::AccountId>,
+    /// Within what space (scope) this entity has been blocked.
+    appealed_within: SpaceId,
+    /// A reason should describe why this entity should be unblocked in this space.
+    reason: Content,
+    status: AppealStatus,
+    /// An account id of a moderator who resolved this appeal.
+    resolved: Option<WhoAndWhen<T>>,
+}
+
+/// Who can suggest entity statuses in a space.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum StatusSuggesters {
+    /// Only accounts with a `SuggestEntityStatus` permission in a space.
+    Moderators,
+    /// Accounts with a `SuggestEntityStatus` permission and followers of a space.
+    ModeratorsAndFollowers,
+}
+
+impl Default for StatusSuggesters {
+    fn default() -> Self {
+        StatusSuggesters::Moderators
+    }
 }
 
 // TODO rename to ModerationSettings?
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct SpaceModerationSettings {
-    autoblock_threshold: Option<u16>
+pub struct SpaceModerationSettings<BlockNumber> {
+    pub autoblock_threshold: Option<u16>,
+    /// Kinds of entities that can be reported in a space. `None` if any entity can be reported.
+    pub accepted_entity_kinds: Option<BTreeSet<EntityKind>>,
+    /// A number of blocks after an entity was blocked, during which it can be appealed.
+    /// `None` if a blocked entity can be appealed at any time.
+    pub appeal_window: Option<BlockNumber>,
+    pub status_suggesters: StatusSuggesters,
 }
 
 // TODO rename to ModerationSettingsUpdate?
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct SpaceModerationSettingsUpdate {
-    pub autoblock_threshold: Option<Option<u16>>
+pub struct SpaceModerationSettingsUpdate<BlockNumber> {
+    pub autoblock_threshold: Option<Option<u16>>,
+    pub accepted_entity_kinds: Option<Option<BTreeSet<EntityKind>>>,
+    pub appeal_window: Option<Option<BlockNumber>>,
+    pub status_suggesters: Option<StatusSuggesters>,
 }
 
 /// The pallet's configuration trait.
@@ -109,9 +189,31 @@ pub trait Config: system::Config
     type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
 
     type DefaultAutoblockThreshold: Get<u16>;
+
+    /// Hooks that let other pallets enforce moderation decisions (e.g. hide posts or deny free calls).
+    type OnEntityStatusChanged: OnEntityStatusChanged<Self>;
+
+    /// Max number of temporary blocks that can expire at the same block.
+    type MaxBlocksExpiringAtBlock: Get<u32>;
+
+    /// Max number of appeals in a space that are not resolved yet.
+    type MaxPendingAppealsPerSpace: Get<u32>;
+}
+
+/// Handler that will be called after a status of an entity in a space has changed,
+/// whether by a space owner, by autoblock, by an accepted appeal or by a block expiry.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait OnEntityStatusChanged<T: Config> {
+    fn on_entity_status_changed(
+        entity: &EntityId<T::AccountId>,
+        scope: SpaceId,
+        old_status: Option<EntityStatus>,
+        new_status: Option<EntityStatus>,
+    );
 }
 
 pub const FIRST_REPORT_ID: u64 = 1;
+pub const FIRST_APPEAL_ID: u64 = 1;
 
 // This pallet's storage items.
 decl_storage! {
@@ -141,6 +243,11 @@ decl_storage! {
             hasher(twox_64_concat) SpaceId
             => Vec<ReportId>;
 
+        /// The number of spaces in which a given account (key) is blocked,
+        /// i.e. in which reports against this account were upheld.
+        pub BlocksCountByAccount get(fn blocks_count_by_account):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+
         /// An entity (key 1) status (`Blocked` or `Allowed`) in this space (key 2).
         pub StatusByEntityInSpace get(fn status_by_entity_in_space): double_map
             hasher(twox_64_concat) EntityId<T::AccountId>,
@@ -156,7 +263,50 @@ decl_storage! {
         /// A custom moderation settings for a certain space (key).
         pub ModerationSettings get(fn moderation_settings):
             map hasher(twox_64_concat) SpaceId
-            => Option<SpaceModerationSettings>;
+            => Option<SpaceModerationSettings<T::BlockNumber>>;
+
+        /// The next moderation appeal id.
+        pub NextAppealId get(fn next_appeal_id): AppealId = FIRST_APPEAL_ID;
+
+        /// Appeal details by its id (key).
+        pub AppealById get(fn appeal_by_id):
+            map hasher(twox_64_concat) AppealId
+            => Option<Appeal<T>>;
+
+        /// Ids of pending appeals sent to this space (key).
+        /// An appeal id is removed from here when the appeal is resolved.
+        pub AppealIdsBySpaceId get(fn appeal_ids_by_space_id):
+            map hasher(twox_64_concat) SpaceId
+            => Vec<AppealId>;
+
+        /// An id of a pending appeal against an entity (key 1) status in this space (key 2).
+        pub PendingAppealIdByEntityInSpace get(fn pending_appeal_id_by_entity_in_space): double_map
+            hasher(twox_64_concat) EntityId<T::AccountId>,
+            hasher(twox_64_concat) SpaceId
+            => Option<AppealId>;
+
+        /// A block number at which a temporary block of an entity (key 1)
+        /// in this space (key 2) expires.
+        pub BlockExpiresAt get(fn block_expires_at): double_map
+            hasher(twox_64_concat) EntityId<T::AccountId>,
+            hasher(twox_64_concat) SpaceId
+            => Option<T::BlockNumber>;
+
+        /// Entities and spaces, in which their temporary blocks expire at this block number (key).
+        pub ExpiringBlocksAt get(fn expiring_blocks_at):
+            map hasher(twox_64_concat) T::BlockNumber
+            => Vec<(EntityId<T::AccountId>, SpaceId)>;
+
+        /// A block number at which an entity (key 1) was blocked in this space (key 2).
+        /// Used to check whether a blocked entity can still be appealed.
+        pub BlockedAt get(fn blocked_at): double_map
+            hasher(twox_64_concat) EntityId<T::AccountId>,
+            hasher(twox_64_concat) SpaceId
+            => Option<T::BlockNumber>;
+
+        /// The next block number to look for expired blocks in `on_idle`.
+        /// `None` if no temporary block has ever been set.
+        pub NextBlockToSweep get(fn next_block_to_sweep): Option<T::BlockNumber>;
     }
 }
 
@@ -164,6 +314,7 @@ decl_storage! {
 decl_event!(
     pub enum Event<T> where
         AccountId = <T as system::Config>::AccountId,
+        BlockNumber = <T as system::Config>::BlockNumber,
         EntityId = EntityId<<T as system::Config>::AccountId>
     {
         EntityReported(AccountId, SpaceId, EntityId, ReportId),
@@ -171,6 +322,10 @@ decl_event!(
         EntityStatusUpdated(AccountId, SpaceId, EntityId, Option<EntityStatus>),
         EntityStatusDeleted(AccountId, SpaceId, EntityId),
         ModerationSettingsUpdated(AccountId, SpaceId),
+        EntityStatusAppealed(AccountId, SpaceId, EntityId, AppealId),
+        AppealResolved(AccountId, SpaceId, EntityId, AppealId, AppealStatus),
+        EntityBlockExpiresAt(SpaceId, EntityId, BlockNumber),
+        EntityBlockExpired(SpaceId, EntityId),
     }
 );
 
@@ -206,6 +361,30 @@ decl_error! {
         SuggestedStatusInWrongScope,
         /// Entity status has already been suggested by this moderator account.
         AlreadySuggestedEntityStatus,
+        /// Appeal was not found by its id.
+        AppealNotFound,
+        /// Appeal has already been accepted or rejected.
+        AppealAlreadyResolved,
+        /// There is already a pending appeal against this entity status in this space.
+        AlreadyAppealedEntity,
+        /// Only blocked entities can be appealed.
+        EntityIsNotBlocked,
+        /// Account is not an owner of this entity, so it cannot appeal the entity status.
+        NoPermissionToAppealEntityStatus,
+        /// Appeal can only be resolved as either accepted or rejected.
+        InvalidAppealResolution,
+        /// Only a `Blocked` entity status can have an expiry.
+        ExpiryIsOnlyForBlockedStatus,
+        /// A temporary block should last at least one block.
+        ZeroBlockExpiry,
+        /// Entities of this kind cannot be reported in this space.
+        EntityKindNotAccepted,
+        /// An appeal window of this blocked entity has already passed.
+        AppealWindowExpired,
+        /// Max number of temporary blocks already expire at this block.
+        TooManyBlocksExpiringAtBlock,
+        /// Max number of pending appeals are already sent to this space.
+        TooManyPendingAppeals,
     }
 }
 
@@ -222,9 +401,13 @@ decl_module! {
         // Initializing events
         fn deposit_event() = default;
 
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::sweep_expired_blocks(now, remaining_weight)
+        }
+
         /// Report any entity by any person with mandatory reason.
         /// `entity` scope and the `scope` provided mustn't differ
-        #[weight = 10_000 + T::DbWeight::get().reads_writes(6, 5)]
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 4)]
         pub fn report_entity(
             origin,
             entity: EntityId<T::AccountId>,
@@ -241,6 +424,11 @@ decl_module! {
             ensure!(Spaces::<T>::require_space(scope).is_ok(), Error::<T>::ScopeNotFound);
             Self::ensure_entity_in_scope(&entity, scope)?;
 
+            let settings = Self::moderation_settings_or_default(scope);
+            if let Some(accepted_entity_kinds) = settings.accepted_entity_kinds {
+                ensure!(accepted_entity_kinds.contains(&entity.kind()), Error::<T>::EntityKindNotAccepted);
+            }
+
             let not_reported_yet = Self::report_id_by_account((&entity, &who)).is_none();
             ensure!(not_reported_yet, Error::<T>::AlreadyReportedEntity);
 
@@ -274,31 +462,24 @@ decl_module! {
                 ensure!(scope == report.reported_within, Error::<T>::SuggestedStatusInWrongScope);
             }
 
-            let entity_status = StatusByEntityInSpace::<T>::get(&entity, scope);
+            let entity_status = Self::entity_status_in_scope(&entity, scope);
             ensure!(!(entity_status.is_some() && status == entity_status), Error::<T>::SuggestedSameEntityStatus);
 
             let space = Spaces::<T>::require_space(scope).map_err(|_| Error::<T>::ScopeNotFound)?;
-            Spaces::<T>::ensure_account_has_space_permission(
-                who.clone(),
-                &space,
-                pallet_permissions::SpacePermission::SuggestEntityStatus,
-                Error::<T>::NoPermissionToSuggestEntityStatus.into(),
-            )?;
+            let settings = Self::moderation_settings_or_default(scope);
+            let is_moderator = Self::ensure_account_can_suggest_status(who.clone(), &space, settings.status_suggesters)?;
 
             let mut suggestions = SuggestedStatusesByEntityInSpace::<T>::get(&entity, scope);
             let is_already_suggested = suggestions.iter().any(|suggestion| suggestion.suggested.account == who);
             ensure!(!is_already_suggested, Error::<T>::AlreadySuggestedEntityStatus);
-            suggestions.push(SuggestedStatus::new(who.clone(), status.clone(), report_id_opt));
+            suggestions.push(SuggestedStatus::new(who.clone(), status.clone(), report_id_opt, is_moderator));
 
+            // Followers cannot autoblock an entity, otherwise sybil accounts could reach a threshold.
             let block_suggestions_total = suggestions.iter()
-                .filter(|suggestion| suggestion.status == Some(EntityStatus::Blocked))
+                .filter(|suggestion| suggestion.by_moderator && suggestion.status == Some(EntityStatus::Blocked))
                 .count();
 
-            let autoblock_threshold_opt = Self::moderation_settings(scope)
-                .unwrap_or_else(Self::default_autoblock_threshold_as_settings)
-                .autoblock_threshold;
-
-            if let Some(autoblock_threshold) = autoblock_threshold_opt {
+            if let Some(autoblock_threshold) = settings.autoblock_threshold {
                 if block_suggestions_total >= autoblock_threshold as usize {
                     Self::block_entity_in_scope(&entity, scope)?;
                 }
@@ -311,31 +492,52 @@ decl_module! {
         }
 
         /// Allows a space owner/admin to update the final moderation status of a reported entity.
-        #[weight = 10_000 /* TODO + T::DbWeight::get().reads_writes(_, _) */]
+        /// `block_expires_in_opt` - a number of blocks after which a `Blocked` status lapses.
+        /// `None` makes the status permanent.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
         pub fn update_entity_status(
             origin,
             entity: EntityId<T::AccountId>,
             scope: SpaceId,
-            status_opt: Option<EntityStatus>
+            status_opt: Option<EntityStatus>,
+            block_expires_in_opt: Option<T::BlockNumber>
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             // TODO: add `forbid_content` parameter and track entity Content blocking via OCW
             //  - `forbid_content` - whether to block `Content` provided with entity.
 
+            if let Some(block_expires_in) = block_expires_in_opt {
+                ensure!(status_opt == Some(EntityStatus::Blocked), Error::<T>::ExpiryIsOnlyForBlockedStatus);
+                ensure!(!block_expires_in.is_zero(), Error::<T>::ZeroBlockExpiry);
+
+                let expires_at = <system::Pallet<T>>::block_number().saturating_add(block_expires_in);
+                ensure!(
+                    Self::expiring_blocks_at(expires_at).len() < T::MaxBlocksExpiringAtBlock::get() as usize,
+                    Error::<T>::TooManyBlocksExpiringAtBlock
+                );
+            }
+
             let space = Spaces::<T>::require_space(scope).map_err(|_| Error::<T>::ScopeNotFound)?;
             Self::ensure_account_status_manager(who.clone(), &space)?;
 
+            Self::remove_block_expiry(&entity, scope);
+
             if let Some(status) = &status_opt {
                 let is_entity_in_scope = Self::ensure_entity_in_scope(&entity, scope).is_ok();
 
                 if is_entity_in_scope && status == &EntityStatus::Blocked {
                     Self::block_entity_in_scope(&entity, scope)?;
                 } else {
-                    StatusByEntityInSpace::<T>::insert(entity.clone(), scope, status);
+                    Self::set_entity_status(&entity, scope, Some(status.clone()));
                 }
             } else {
-                StatusByEntityInSpace::<T>::remove(entity.clone(), scope);
+                Self::set_entity_status(&entity, scope, None);
+            }
+
+            if let Some(block_expires_in) = block_expires_in_opt {
+                let expires_at = Self::schedule_block_expiry(&entity, scope, block_expires_in);
+                Self::deposit_event(RawEvent::EntityBlockExpiresAt(scope, entity.clone(), expires_at));
             }
 
             Self::deposit_event(RawEvent::EntityStatusUpdated(who, scope, entity, status_opt));
@@ -357,7 +559,8 @@ decl_module! {
             let space = Spaces::<T>::require_space(scope).map_err(|_| Error::<T>::ScopeNotFound)?;
             Self::ensure_account_status_manager(who.clone(), &space)?;
 
-            StatusByEntityInSpace::<T>::remove(&entity, scope);
+            Self::set_entity_status(&entity, scope, None);
+            Self::remove_block_expiry(&entity, scope);
 
             Self::deposit_event(RawEvent::EntityStatusDeleted(who, scope, entity));
             Ok(())
@@ -370,11 +573,16 @@ decl_module! {
         fn update_moderation_settings(
             origin,
             space_id: SpaceId,
-            update: SpaceModerationSettingsUpdate
+            update: SpaceModerationSettingsUpdate<T::BlockNumber>
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            let has_updates = update.autoblock_threshold.is_some();
+            let has_updates =
+                update.autoblock_threshold.is_some() ||
+                update.accepted_entity_kinds.is_some() ||
+                update.appeal_window.is_some() ||
+                update.status_suggesters.is_some();
+
             ensure!(has_updates, Error::<T>::NoUpdatesForModerationSettings);
 
             let space = Spaces::<T>::require_space(space_id)?;
@@ -389,8 +597,7 @@ decl_module! {
             // `true` if there is at least one updated field.
             let mut should_update = false;
 
-            let mut settings = Self::moderation_settings(space_id)
-                .unwrap_or_else(Self::default_autoblock_threshold_as_settings);
+            let mut settings = Self::moderation_settings_or_default(space_id);
 
             if let Some(autoblock_threshold) = update.autoblock_threshold {
                 if autoblock_threshold != settings.autoblock_threshold {
@@ -399,11 +606,111 @@ decl_module! {
                 }
             }
 
+            if let Some(accepted_entity_kinds) = update.accepted_entity_kinds {
+                if accepted_entity_kinds != settings.accepted_entity_kinds {
+                    settings.accepted_entity_kinds = accepted_entity_kinds;
+                    should_update = true;
+                }
+            }
+
+            if let Some(appeal_window) = update.appeal_window {
+                if appeal_window != settings.appeal_window {
+                    settings.appeal_window = appeal_window;
+                    should_update = true;
+                }
+            }
+
+            if let Some(status_suggesters) = update.status_suggesters {
+                if status_suggesters != settings.status_suggesters {
+                    settings.status_suggesters = status_suggesters;
+                    should_update = true;
+                }
+            }
+
             if should_update {
-                ModerationSettings::insert(space_id, settings);
+                ModerationSettings::<T>::insert(space_id, settings);
                 Self::deposit_event(RawEvent::ModerationSettingsUpdated(who, space_id));
             }
             Ok(())
         }
+
+        /// Appeal a `Blocked` status of an entity in a space with mandatory reason.
+        /// `origin` - an owner of the entity: the account itself, an owner of a space or a post.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 4)]
+        pub fn appeal_entity_status(
+            origin,
+            entity: EntityId<T::AccountId>,
+            scope: SpaceId,
+            reason: Content
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Utils::<T>::ensure_content_is_some(&reason).map_err(|_| Error::<T>::ReasonIsEmpty)?;
+
+            Utils::<T>::is_valid_content(reason.clone())?;
+
+            ensure!(Self::is_entity_blocked_in_scope(&entity, scope), Error::<T>::EntityIsNotBlocked);
+
+            let not_appealed_yet = Self::pending_appeal_id_by_entity_in_space(&entity, scope).is_none();
+            ensure!(not_appealed_yet, Error::<T>::AlreadyAppealedEntity);
+
+            Self::ensure_account_can_appeal(&who, &entity)?;
+            Self::ensure_appeal_window_not_expired(&entity, scope)?;
+
+            let mut pending_appeal_ids = Self::appeal_ids_by_space_id(scope);
+            ensure!(
+                pending_appeal_ids.len() < T::MaxPendingAppealsPerSpace::get() as usize,
+                Error::<T>::TooManyPendingAppeals
+            );
+
+            let appeal_id = Self::next_appeal_id();
+            let new_appeal = Appeal::<T>::new(appeal_id, who.clone(), entity.clone(), scope, reason);
+
+            AppealById::<T>::insert(appeal_id, new_appeal);
+            pending_appeal_ids.push(appeal_id);
+            AppealIdsBySpaceId::insert(scope, pending_appeal_ids);
+            PendingAppealIdByEntityInSpace::<T>::insert(&entity, scope, appeal_id);
+            NextAppealId::mutate(|n| { *n += 1; });
+
+            Self::deposit_event(RawEvent::EntityStatusAppealed(who, scope, entity, appeal_id));
+            Ok(())
+        }
+
+        /// Allows a space owner/admin to either accept or reject a pending appeal.
+        /// If an appeal is accepted, the entity status is removed from the space.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
+        pub fn resolve_appeal(
+            origin,
+            appeal_id: AppealId,
+            resolution: AppealStatus
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(resolution != AppealStatus::Pending, Error::<T>::InvalidAppealResolution);
+
+            let mut appeal = Self::require_appeal(appeal_id)?;
+            ensure!(appeal.status == AppealStatus::Pending, Error::<T>::AppealAlreadyResolved);
+
+            let scope = appeal.appealed_within;
+            let space = Spaces::<T>::require_space(scope).map_err(|_| Error::<T>::ScopeNotFound)?;
+            Self::ensure_account_status_manager(who.clone(), &space)?;
+
+            let entity = appeal.appealed_entity.clone();
+            if resolution == AppealStatus::Accepted {
+                Self::set_entity_status(&entity, scope, None);
+                Self::remove_block_expiry(&entity, scope);
+                // Otherwise the entity would be autoblocked again on the next suggestion.
+                SuggestedStatusesByEntityInSpace::<T>::remove(&entity, scope);
+            }
+
+            appeal.status = resolution.clone();
+            appeal.resolved = Some(WhoAndWhen::<T>::new(who.clone()));
+            AppealById::<T>::insert(appeal_id, appeal);
+            PendingAppealIdByEntityInSpace::<T>::remove(&entity, scope);
+            AppealIdsBySpaceId::mutate(scope, |ids| remove_from_vec(ids, appeal_id));
+
+            Self::deposit_event(RawEvent::AppealResolved(who, scope, entity, appeal_id, resolution));
+            Ok(())
+        }
     }
 }
@@ -2,13 +2,16 @@ use super::*;
 
 use crate as moderation;
 
-use frame_support::{assert_ok, dispatch::DispatchResult, parameter_types, StorageMap, traits::Everything};
+use frame_support::{
+    assert_ok, dispatch::DispatchResult, parameter_types, StorageMap, traits::Everything,
+    weights::constants::RocksDbWeight,
+};
 use frame_system as system;
 
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 
@@ -64,7 +67,7 @@ impl system::Config for Test {
     type Header = Header;
     type Event = Event;
     type BlockHashCount = BlockHashCount;
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Version = ();
     type PalletInfo = PalletInfo;
     type AccountData = pallet_balances::AccountData<u64>;
@@ -96,6 +99,7 @@ impl pallet_utils::Config for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type DeferredJobHandler = Roles;
 }
 
 parameter_types! {
@@ -125,56 +129,113 @@ impl pallet_spaces::Config for Test {
     type SpaceFollows = SpaceFollows;
     type BeforeSpaceCreated = SpaceFollows;
     type AfterSpaceUpdated = ();
+    type SpaceHooks = ();
+    type WelcomePost = Posts;
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
+    type IsAccountActive = Profiles;
     type HandleDeposit = ();
 }
 
+parameter_types! {
+    pub const MaxSpacesPerBatch: u16 = 10;
+}
+
 impl pallet_space_follows::Config for Test {
     type Event = Event;
     type BeforeSpaceFollowed = ();
     type BeforeSpaceUnfollowed = ();
+    type MaxSpacesPerBatch = MaxSpacesPerBatch;
 }
 
 parameter_types! {
     pub const MaxCommentDepth: u32 = 10;
+    pub const MaxMutedThreadsPerAccount: u16 = 100;
+    pub const MaxScheduledPostsPerBlock: u16 = 10;
+    pub const RecentPostsPeriod: u64 = 10;
+    pub const MaxSpamScore: u32 = 100;
+    pub const PostsByAccountEraLength: u64 = 10;
+    pub const MaxPostsPerHideByAuthor: u32 = 2;
+    pub const MaxPostIdsPerReservation: u32 = 3;
+    pub const PostIdReservationPeriod: u64 = 10;
+    pub const MaxContentProofsPerPost: u32 = 3;
+    pub const MaxSharesToNotifyOnUpdate: u32 = 3;
+    pub const MaxScheduledPostsPerAccount: u16 = 3;
+    pub const MaxPostErasPerAccount: u32 = 10;
 }
 
 impl pallet_posts::Config for Test {
     type Event = Event;
     type MaxCommentDepth = MaxCommentDepth;
     type AfterPostUpdated = ();
+    type PostHooks = ();
     type IsPostBlocked = Moderation;
+    type MaxMutedThreadsPerAccount = MaxMutedThreadsPerAccount;
+    type MaxScheduledPostsPerBlock = MaxScheduledPostsPerBlock;
+    type RecentPostsPeriod = RecentPostsPeriod;
+    type MaxSpamScore = MaxSpamScore;
+    type AccountCreatedAt = Profiles;
+    type AccountModerationFlags = Moderation;
+    type ContentDefaults = Profiles;
+    type PostsByAccountEraLength = PostsByAccountEraLength;
+    type MaxPostsPerHideByAuthor = MaxPostsPerHideByAuthor;
+    type MaxPostIdsPerReservation = MaxPostIdsPerReservation;
+    type PostIdReservationDeposit = ();
+    type PostIdReservationPeriod = PostIdReservationPeriod;
+    type MaxContentProofsPerPost = MaxContentProofsPerPost;
+    type MaxSharesToNotifyOnUpdate = MaxSharesToNotifyOnUpdate;
+    type MaxScheduledPostsPerAccount = MaxScheduledPostsPerAccount;
+    type MaxPostErasPerAccount = MaxPostErasPerAccount;
 }
 
 parameter_types! {
     pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+    pub const MaxUsersPerRoleChange: u16 = 40;
+    pub const MaxRolesPerSpace: u16 = 10;
 }
 
 impl pallet_roles::Config for Test {
     type Event = Event;
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxUsersPerRoleChange = MaxUsersPerRoleChange;
+    type MaxRolesPerSpace = MaxRolesPerSpace;
     type Spaces = Spaces;
     type SpaceFollows = SpaceFollows;
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
 }
 
+parameter_types! {
+    pub const FollowersBadgeThreshold: u32 = 100;
+    pub const AccountAgeBadgeThreshold: u64 = 100;
+}
+
 impl pallet_profiles::Config for Test {
     type Event = Event;
     type AfterProfileUpdated = ();
+    type FollowersBadgeThreshold = FollowersBadgeThreshold;
+    type AccountAgeBadgeThreshold = AccountAgeBadgeThreshold;
+    type Signature = TestSignature;
+    type Signer = UintAuthorityId;
+    type MutedThreads = Posts;
 }
 
 parameter_types! {
     pub const DefaultAutoblockThreshold: u16 = 3;
+    pub const MaxBlocksExpiringAtBlock: u32 = 2;
+    pub const MaxPendingAppealsPerSpace: u32 = 1;
 }
 
 impl Config for Test {
     type Event = Event;
     type DefaultAutoblockThreshold = DefaultAutoblockThreshold;
+    type OnEntityStatusChanged = ();
+    type MaxBlocksExpiringAtBlock = MaxBlocksExpiringAtBlock;
+    type MaxPendingAppealsPerSpace = MaxPendingAppealsPerSpace;
 }
 
 pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
 
 pub struct ExtBuilder;
 
@@ -262,6 +323,26 @@ impl ExtBuilder {
 
         ext
     }
+
+    pub fn build_with_blocked_post() -> TestExternalities {
+        let mut ext = Self::build_with_space_and_post_then_report();
+
+        ext.execute_with(|| {
+            assert_ok!(_update_entity_status(None, None, None, Some(Some(EntityStatus::Blocked))));
+        });
+
+        ext
+    }
+
+    pub fn build_with_blocked_post_then_appeal() -> TestExternalities {
+        let mut ext = Self::build_with_blocked_post();
+
+        ext.execute_with(|| {
+            assert_ok!(_appeal_default_post());
+        });
+
+        ext
+    }
 }
 
 pub(crate) const ACCOUNT_SCOPE_OWNER: AccountId = 1;
@@ -276,19 +357,28 @@ pub(crate) const POST1: PostId = 1;
 pub(crate) const REPORT1: ReportId = 1;
 pub(crate) const REPORT2: ReportId = 2;
 
+pub(crate) const APPEAL1: AppealId = 1;
+pub(crate) const APPEAL2: AppealId = 2;
+
 pub(crate) const MODERATOR_ROLE_ID: RoleId = 1;
 
 pub(crate) const AUTOBLOCK_THRESHOLD: u16 = 5;
 
-pub(crate) const fn new_autoblock_threshold() -> SpaceModerationSettingsUpdate {
+pub(crate) const fn new_autoblock_threshold() -> SpaceModerationSettingsUpdate<BlockNumber> {
     SpaceModerationSettingsUpdate {
-        autoblock_threshold: Some(Some(AUTOBLOCK_THRESHOLD))
+        autoblock_threshold: Some(Some(AUTOBLOCK_THRESHOLD)),
+        accepted_entity_kinds: None,
+        appeal_window: None,
+        status_suggesters: None,
     }
 }
 
-pub(crate) const fn empty_moderation_settings_update() -> SpaceModerationSettingsUpdate {
+pub(crate) const fn empty_moderation_settings_update() -> SpaceModerationSettingsUpdate<BlockNumber> {
     SpaceModerationSettingsUpdate {
-        autoblock_threshold: None
+        autoblock_threshold: None,
+        accepted_entity_kinds: None,
+        appeal_window: None,
+        status_suggesters: None,
     }
 }
 
@@ -375,6 +465,27 @@ pub(crate) fn _update_entity_status(
         entity.unwrap_or(EntityId::Post(POST1)),
         scope.unwrap_or(SPACE1),
         status_opt.unwrap_or(Some(EntityStatus::Allowed)),
+        None,
+    )
+}
+
+pub(crate) const BLOCK_EXPIRES_IN: u64 = 10;
+
+pub(crate) fn _block_post_temporarily() -> DispatchResult {
+    _update_entity_status_with_expiry(None, None, Some(BLOCK_EXPIRES_IN))
+}
+
+pub(crate) fn _update_entity_status_with_expiry(
+    origin: Option<Origin>,
+    status_opt: Option<Option<EntityStatus>>,
+    block_expires_in_opt: Option<u64>,
+) -> DispatchResult {
+    Moderation::update_entity_status(
+        origin.unwrap_or_else(|| Origin::signed(ACCOUNT_SCOPE_OWNER)),
+        EntityId::Post(POST1),
+        SPACE1,
+        status_opt.unwrap_or(Some(EntityStatus::Blocked)),
+        block_expires_in_opt,
     )
 }
 
@@ -401,7 +512,7 @@ pub(crate) fn _update_autoblock_threshold_in_moderation_settings() -> DispatchRe
 pub(crate) fn _update_moderation_settings(
     origin: Option<Origin>,
     space_id: Option<SpaceId>,
-    settings_update: Option<SpaceModerationSettingsUpdate>,
+    settings_update: Option<SpaceModerationSettingsUpdate<BlockNumber>>,
 ) -> DispatchResult {
     Moderation::update_moderation_settings(
         origin.unwrap_or_else(|| Origin::signed(ACCOUNT_SCOPE_OWNER)),
@@ -409,3 +520,45 @@ pub(crate) fn _update_moderation_settings(
         settings_update.unwrap_or_else(new_autoblock_threshold),
     )
 }
+
+pub(crate) const APPEAL_WINDOW: u64 = 10;
+
+pub(crate) fn _update_moderation_settings_with(
+    settings_update: SpaceModerationSettingsUpdate<BlockNumber>,
+) -> DispatchResult {
+    _update_moderation_settings(None, None, Some(settings_update))
+}
+
+pub(crate) fn _appeal_default_post() -> DispatchResult {
+    _appeal_entity_status(None, None, None, None)
+}
+
+pub(crate) fn _appeal_entity_status(
+    origin: Option<Origin>,
+    entity: Option<EntityId<AccountId>>,
+    scope: Option<SpaceId>,
+    reason: Option<Content>,
+) -> DispatchResult {
+    Moderation::appeal_entity_status(
+        origin.unwrap_or_else(|| Origin::signed(ACCOUNT_SCOPE_OWNER)),
+        entity.unwrap_or(EntityId::Post(POST1)),
+        scope.unwrap_or(SPACE1),
+        reason.unwrap_or_else(valid_content_ipfs),
+    )
+}
+
+pub(crate) fn _accept_default_appeal() -> DispatchResult {
+    _resolve_appeal(None, None, None)
+}
+
+pub(crate) fn _resolve_appeal(
+    origin: Option<Origin>,
+    appeal_id: Option<AppealId>,
+    resolution: Option<AppealStatus>,
+) -> DispatchResult {
+    Moderation::resolve_appeal(
+        origin.unwrap_or_else(|| Origin::signed(ACCOUNT_SCOPE_OWNER)),
+        appeal_id.unwrap_or(APPEAL1),
+        resolution.unwrap_or(AppealStatus::Accepted),
+    )
+}
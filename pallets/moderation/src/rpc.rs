@@ -0,0 +1,141 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+use frame_support::dispatch::DispatchError;
+use pallet_posts::{Module as Posts, PostExtension};
+use pallet_space_follows::Module as SpaceFollows;
+use pallet_spaces::Module as Spaces;
+use pallet_utils::{PostId, SpaceId, rpc::{FlatContent, FlatWhoAndWhen}};
+
+use crate::{Config, EntityId, EntityStatus, Module, Report, ReportId};
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatReport<AccountId, BlockNumber> {
+    pub id: ReportId,
+    #[cfg_attr(feature = "std", serde(flatten))]
+    pub who_and_when: FlatWhoAndWhen<AccountId, BlockNumber>,
+    pub reported_entity: EntityId<AccountId>,
+    pub reported_within: SpaceId,
+    pub reason: FlatContent,
+}
+
+impl<T: Config> From<Report<T>> for FlatReport<T::AccountId, T::BlockNumber> {
+    fn from(from: Report<T>) -> Self {
+        let Report { id, created, reported_entity, reported_within, reason } = from;
+
+        Self {
+            id,
+            who_and_when: created.into(),
+            reported_entity,
+            reported_within,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// What would change if a moderation status was applied to an entity in a space.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ModerationEffects<AccountId> {
+    /// Whether a new status differs from the current status of an entity in this space.
+    pub is_status_changed: bool,
+    /// Whether an entity belongs to this space. Side effects of a block
+    /// (the fields below) are applied only to entities within a space.
+    pub is_entity_in_scope: bool,
+    /// Owners of the entity whose content or membership in this space would be affected.
+    pub impacted_accounts: Vec<AccountId>,
+    /// Accounts that would be forced to unfollow this space.
+    pub unfollowed_accounts: Vec<AccountId>,
+    /// Posts (or comments) that would be removed from this space.
+    pub removed_post_ids: Vec<PostId>,
+    /// Subspaces that would be moved to the root.
+    pub moved_space_ids: Vec<SpaceId>,
+    /// A change of `followers_count` of this space.
+    pub space_followers_count_diff: i32,
+    /// A change of `posts_count` of this space.
+    pub space_posts_count_diff: i32,
+    /// A change of `hidden_posts_count` of this space.
+    pub space_hidden_posts_count_diff: i32,
+}
+
+impl<T: Config> Module<T> {
+    /// Get reports sent to a space, newest first.
+    ///
+    /// Reports are public: like any other on-chain data, they can be read from the storage
+    /// by anyone, so there is no permission check for viewing them.
+    pub fn get_reports_in_space(
+        space_id: SpaceId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatReport<T::AccountId, T::BlockNumber>>, DispatchError> {
+        Spaces::<T>::require_space(space_id)?;
+
+        Ok(Self::report_ids_by_space_id(space_id)
+            .into_iter()
+            .rev()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .filter_map(Self::report_by_id)
+            .map(|report| report.into())
+            .collect())
+    }
+
+    /// Preview what `update_entity_status` would change, without applying anything.
+    pub fn moderation_effects(
+        entity: EntityId<T::AccountId>,
+        scope: SpaceId,
+        status_opt: Option<EntityStatus>,
+    ) -> Result<ModerationEffects<T::AccountId>, DispatchError> {
+        Spaces::<T>::require_space(scope)?;
+
+        let mut effects = ModerationEffects {
+            is_status_changed: Self::entity_status_in_scope(&entity, scope) != status_opt,
+            is_entity_in_scope: Self::ensure_entity_in_scope(&entity, scope).is_ok(),
+            ..Default::default()
+        };
+
+        let is_block_applied = effects.is_status_changed
+            && effects.is_entity_in_scope
+            && status_opt == Some(EntityStatus::Blocked);
+
+        if !is_block_applied {
+            return Ok(effects);
+        }
+
+        match entity {
+            EntityId::Content(_) => (),
+            EntityId::Account(account) => {
+                if SpaceFollows::<T>::space_followed_by_account((account.clone(), scope)) {
+                    effects.unfollowed_accounts.push(account.clone());
+                    effects.space_followers_count_diff = -1;
+                }
+                effects.impacted_accounts.push(account);
+            },
+            EntityId::Space(space_id) => {
+                let space = Spaces::<T>::require_space(space_id)?;
+                effects.moved_space_ids.push(space_id);
+                effects.impacted_accounts.push(space.owner);
+            },
+            EntityId::Post(post_id) => {
+                let post = Posts::<T>::require_post(post_id)?;
+
+                if !matches!(post.extension, PostExtension::Comment(_)) {
+                    effects.space_posts_count_diff = -1;
+                    if post.hidden {
+                        effects.space_hidden_posts_count_diff = -1;
+                    }
+                }
+
+                effects.removed_post_ids.push(post_id);
+                effects.impacted_accounts.push(post.owner);
+            },
+        }
+
+        Ok(effects)
+    }
+}
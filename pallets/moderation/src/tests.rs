@@ -1,7 +1,12 @@
 use crate::{Error, mock::*};
 use crate::*;
 
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{
+    assert_ok, assert_noop, StorageMap,
+    traits::{Get, OnIdle},
+    weights::Weight,
+};
+use df_traits::moderation::IsPostBlocked;
 use pallet_posts::PostById;
 use pallet_utils::{
     Error as UtilsError,
@@ -65,6 +70,19 @@ fn report_entity_should_fail_when_entity_already_reported() {
     });
 }
 
+#[test]
+fn report_entity_should_fail_when_entity_kind_not_accepted_in_space() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        assert_ok!(_update_moderation_settings_with(SpaceModerationSettingsUpdate {
+            accepted_entity_kinds: Some(Some(vec![EntityKind::Account].into_iter().collect())),
+            ..empty_moderation_settings_update()
+        }));
+
+        assert_noop!(_report_default_post(), Error::<Test>::EntityKindNotAccepted);
+        assert_ok!(_report_entity(None, Some(EntityId::Account(ACCOUNT_NOT_MODERATOR)), None, None));
+    });
+}
+
 // Suggest entity status
 //-------------------------------------------------------------------------
 
@@ -153,13 +171,35 @@ fn suggest_entity_status_should_fail_when_origin_has_no_permission() {
     });
 }
 
+#[test]
+fn suggest_entity_status_should_work_for_space_follower_when_allowed_in_settings() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(SpaceFollows::follow_space(Origin::signed(ACCOUNT_NOT_MODERATOR), SPACE1));
+
+        let suggest_as_follower = || _suggest_entity_status(
+            Some(Origin::signed(ACCOUNT_NOT_MODERATOR)),
+            None,
+            None,
+            None,
+            None
+        );
+        assert_noop!(suggest_as_follower(), Error::<Test>::NoPermissionToSuggestEntityStatus);
+
+        assert_ok!(_update_moderation_settings_with(SpaceModerationSettingsUpdate {
+            status_suggesters: Some(StatusSuggesters::ModeratorsAndFollowers),
+            ..empty_moderation_settings_update()
+        }));
+        assert_ok!(suggest_as_follower());
+    });
+}
+
 #[test]
 fn suggest_entity_status_should_autoblock_and_kick_entity_when_threshold_reached() {
     ExtBuilder::build_with_report_then_grant_role_to_suggest_entity_status().execute_with(|| {
-        let space_before_autoblock = Spaces::<Test>::space_by_id(SPACE1).unwrap();
+        let space_stats_before_autoblock = Spaces::<Test>::space_stats_by_id(SPACE1);
         let post_before_autoblock = Posts::post_by_id(POST1).unwrap();
 
-        assert!(space_before_autoblock.posts_count == 1);
+        assert!(space_stats_before_autoblock.posts_count == 1);
         assert!(post_before_autoblock.space_id == Some(SPACE1));
         assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
 
@@ -174,15 +214,32 @@ fn suggest_entity_status_should_autoblock_and_kick_entity_when_threshold_reached
             }
         }
 
-        let space_after_autoblock = Spaces::<Test>::space_by_id(SPACE1).unwrap();
+        let space_stats_after_autoblock = Spaces::<Test>::space_stats_by_id(SPACE1);
         let post_after_autoblock = Posts::post_by_id(POST1).unwrap();
 
-        assert!(space_after_autoblock.posts_count == 0);
+        assert!(space_stats_after_autoblock.posts_count == 0);
         assert!(post_after_autoblock.space_id.is_none());
         assert!(Posts::post_ids_by_space_id(SPACE1).is_empty());
     });
 }
 
+#[test]
+fn suggest_entity_status_should_not_autoblock_by_followers() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(SpaceFollows::follow_space(Origin::signed(ACCOUNT_NOT_MODERATOR), SPACE1));
+        assert_ok!(_update_moderation_settings_with(SpaceModerationSettingsUpdate {
+            autoblock_threshold: Some(Some(1)),
+            status_suggesters: Some(StatusSuggesters::ModeratorsAndFollowers),
+            ..empty_moderation_settings_update()
+        }));
+
+        assert_ok!(_suggest_entity_status(Some(Origin::signed(ACCOUNT_NOT_MODERATOR)), None, None, None, None));
+
+        assert!(Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1).is_none());
+        assert_eq!(Posts::post_by_id(POST1).unwrap().space_id, Some(SPACE1));
+    });
+}
+
 // Update entity status
 //----------------------------------------------------------------------------
 
@@ -238,6 +295,139 @@ fn update_entity_status_should_fail_when_origin_has_no_permission() {
     });
 }
 
+#[test]
+fn update_entity_status_should_block_entity_temporarily() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_block_post_temporarily());
+
+        let expires_at = 1 + BLOCK_EXPIRES_IN;
+        assert_eq!(Moderation::block_expires_at(EntityId::Post(POST1), SPACE1), Some(expires_at));
+        assert_eq!(
+            Moderation::expiring_blocks_at(expires_at),
+            vec![(EntityId::Post(POST1), SPACE1)]
+        );
+        assert!(Moderation::is_blocked_post(POST1, SPACE1));
+
+        // The block lapses lazily, even before it's swept from storage.
+        System::set_block_number(expires_at);
+        assert!(!Moderation::is_blocked_post(POST1, SPACE1));
+        assert_eq!(
+            Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1),
+            Some(EntityStatus::Blocked)
+        );
+    });
+}
+
+#[test]
+fn update_entity_status_should_make_temporary_block_permanent() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_block_post_temporarily());
+        assert_ok!(_update_entity_status_with_expiry(None, None, None));
+
+        assert!(Moderation::block_expires_at(EntityId::Post(POST1), SPACE1).is_none());
+
+        System::set_block_number(1 + BLOCK_EXPIRES_IN);
+        assert!(Moderation::is_blocked_post(POST1, SPACE1));
+    });
+}
+
+#[test]
+fn update_entity_status_should_fail_when_expiry_provided_for_allowed_status() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_noop!(
+            _update_entity_status_with_expiry(
+                None,
+                Some(Some(EntityStatus::Allowed)),
+                Some(BLOCK_EXPIRES_IN)
+            ), Error::<Test>::ExpiryIsOnlyForBlockedStatus
+        );
+    });
+}
+
+#[test]
+fn update_entity_status_should_fail_when_expiry_is_zero() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_noop!(
+            _update_entity_status_with_expiry(None, None, Some(0)),
+            Error::<Test>::ZeroBlockExpiry
+        );
+    });
+}
+
+// Sweep expired blocks
+//---------------------------------------------------------------------------
+
+#[test]
+fn update_entity_status_should_fail_when_too_many_blocks_expire_at_block() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        let expires_at = 1 + BLOCK_EXPIRES_IN;
+        let other_entity = (EntityId::Account(ACCOUNT_NOT_MODERATOR), SPACE1);
+        ExpiringBlocksAt::<Test>::insert(expires_at, vec![other_entity.clone(), other_entity]);
+
+        assert_noop!(_block_post_temporarily(), Error::<Test>::TooManyBlocksExpiringAtBlock);
+    });
+}
+
+#[test]
+fn on_idle_should_remove_expired_blocks() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_block_post_temporarily());
+
+        let expires_at = 1 + BLOCK_EXPIRES_IN;
+
+        // Nothing is removed before the block expires.
+        Moderation::on_idle(expires_at - 1, Weight::max_value());
+        assert!(Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1).is_some());
+        assert_eq!(Moderation::next_block_to_sweep(), Some(expires_at));
+
+        Moderation::on_idle(expires_at, Weight::max_value());
+        assert!(Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1).is_none());
+        assert!(Moderation::block_expires_at(EntityId::Post(POST1), SPACE1).is_none());
+        assert!(Moderation::expiring_blocks_at(expires_at).is_empty());
+        assert_eq!(Moderation::next_block_to_sweep(), Some(expires_at + 1));
+    });
+}
+
+#[test]
+fn on_idle_should_sweep_expiring_blocks_partially() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_block_post_temporarily());
+
+        let expires_at = 1 + BLOCK_EXPIRES_IN;
+        let other_entity = EntityId::Account(ACCOUNT_NOT_MODERATOR);
+        ExpiringBlocksAt::<Test>::mutate(expires_at, |entities| entities.push((other_entity.clone(), SPACE1)));
+
+        // Enough weight to sweep only the first entity of the bucket.
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        let remaining_weight = db_weight.reads_writes(2, 2) + db_weight.reads_writes(3, 4);
+
+        Moderation::on_idle(expires_at, remaining_weight);
+        assert!(Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1).is_none());
+        assert_eq!(Moderation::expiring_blocks_at(expires_at), vec![(other_entity, SPACE1)]);
+        assert_eq!(Moderation::next_block_to_sweep(), Some(expires_at));
+
+        Moderation::on_idle(expires_at, Weight::max_value());
+        assert!(Moderation::expiring_blocks_at(expires_at).is_empty());
+        assert_eq!(Moderation::next_block_to_sweep(), Some(expires_at + 1));
+    });
+}
+
+#[test]
+fn on_idle_should_keep_block_when_its_expiry_was_removed() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_block_post_temporarily());
+        assert_ok!(_update_entity_status_with_expiry(None, None, None));
+
+        let expires_at = 1 + BLOCK_EXPIRES_IN;
+        Moderation::on_idle(expires_at, Weight::max_value());
+
+        assert_eq!(
+            Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1),
+            Some(EntityStatus::Blocked)
+        );
+    });
+}
+
 // Delete entity status
 //---------------------------------------------------------------------------
 
@@ -335,3 +525,235 @@ fn update_moderation_settings_should_fail_when_origin_has_no_permission() {
         );
     });
 }
+
+// Appeal entity status
+//----------------------------------------------------------------------------
+
+#[test]
+fn appeal_entity_status_should_work() {
+    ExtBuilder::build_with_blocked_post_then_appeal().execute_with(|| {
+        assert_eq!(Moderation::next_appeal_id(), APPEAL2);
+
+        let appeal = Moderation::appeal_by_id(APPEAL1).unwrap();
+        assert_eq!(appeal.id, APPEAL1);
+        assert_eq!(appeal.created.account, ACCOUNT_SCOPE_OWNER);
+        assert_eq!(appeal.appealed_entity, EntityId::Post(POST1));
+        assert_eq!(appeal.appealed_within, SPACE1);
+        assert_eq!(appeal.reason, valid_content_ipfs());
+        assert_eq!(appeal.status, AppealStatus::Pending);
+        assert!(appeal.resolved.is_none());
+
+        assert_eq!(Moderation::appeal_ids_by_space_id(SPACE1), vec![APPEAL1]);
+        assert_eq!(
+            Moderation::pending_appeal_id_by_entity_in_space(EntityId::Post(POST1), SPACE1),
+            Some(APPEAL1)
+        );
+    });
+}
+
+#[test]
+fn appeal_entity_status_should_fail_when_no_reason_provided() {
+    ExtBuilder::build_with_blocked_post().execute_with(|| {
+        assert_noop!(
+            _appeal_entity_status(None, None, None, Some(Content::None)),
+            Error::<Test>::ReasonIsEmpty
+        );
+    });
+}
+
+#[test]
+fn appeal_entity_status_should_fail_when_entity_is_not_blocked() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_noop!(_appeal_default_post(), Error::<Test>::EntityIsNotBlocked);
+
+        assert_ok!(_update_post_status_to_allowed());
+        assert_noop!(_appeal_default_post(), Error::<Test>::EntityIsNotBlocked);
+    });
+}
+
+#[test]
+fn appeal_entity_status_should_fail_when_entity_already_appealed() {
+    ExtBuilder::build_with_blocked_post_then_appeal().execute_with(|| {
+        assert_noop!(_appeal_default_post(), Error::<Test>::AlreadyAppealedEntity);
+    });
+}
+
+#[test]
+fn appeal_entity_status_should_fail_when_too_many_pending_appeals_in_space() {
+    ExtBuilder::build_with_blocked_post().execute_with(|| {
+        // Emulate a pending appeal against another entity.
+        AppealIdsBySpaceId::insert(SPACE1, vec![APPEAL2]);
+
+        assert_noop!(_appeal_default_post(), Error::<Test>::TooManyPendingAppeals);
+    });
+}
+
+#[test]
+fn appeal_entity_status_should_fail_when_origin_is_not_entity_owner() {
+    ExtBuilder::build_with_blocked_post().execute_with(|| {
+        assert_noop!(
+            _appeal_entity_status(
+                Some(Origin::signed(ACCOUNT_NOT_MODERATOR)),
+                None,
+                None,
+                None
+            ), Error::<Test>::NoPermissionToAppealEntityStatus
+        );
+    });
+}
+
+#[test]
+fn appeal_entity_status_should_fail_when_appeal_window_expired() {
+    ExtBuilder::build_with_blocked_post().execute_with(|| {
+        assert_ok!(_update_moderation_settings_with(SpaceModerationSettingsUpdate {
+            appeal_window: Some(Some(APPEAL_WINDOW)),
+            ..empty_moderation_settings_update()
+        }));
+        assert_eq!(Moderation::blocked_at(EntityId::Post(POST1), SPACE1), Some(1));
+
+        System::set_block_number(2 + APPEAL_WINDOW);
+        assert_noop!(_appeal_default_post(), Error::<Test>::AppealWindowExpired);
+
+        System::set_block_number(1 + APPEAL_WINDOW);
+        assert_ok!(_appeal_default_post());
+    });
+}
+
+// Resolve appeal
+//----------------------------------------------------------------------------
+
+#[test]
+fn resolve_appeal_should_unblock_entity_when_accepted() {
+    ExtBuilder::build_with_blocked_post_then_appeal().execute_with(|| {
+        assert_ok!(_accept_default_appeal());
+
+        let appeal = Moderation::appeal_by_id(APPEAL1).unwrap();
+        assert_eq!(appeal.status, AppealStatus::Accepted);
+        assert_eq!(appeal.resolved.unwrap().account, ACCOUNT_SCOPE_OWNER);
+
+        assert!(Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1).is_none());
+        assert!(Moderation::pending_appeal_id_by_entity_in_space(EntityId::Post(POST1), SPACE1).is_none());
+        assert!(Moderation::appeal_ids_by_space_id(SPACE1).is_empty());
+    });
+}
+
+#[test]
+fn resolve_appeal_should_keep_entity_blocked_when_rejected() {
+    ExtBuilder::build_with_blocked_post_then_appeal().execute_with(|| {
+        assert_ok!(_resolve_appeal(None, None, Some(AppealStatus::Rejected)));
+
+        let appeal = Moderation::appeal_by_id(APPEAL1).unwrap();
+        assert_eq!(appeal.status, AppealStatus::Rejected);
+
+        assert_eq!(
+            Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1),
+            Some(EntityStatus::Blocked)
+        );
+
+        // The entity can be appealed again after its previous appeal was rejected.
+        assert_ok!(_appeal_default_post());
+        assert_eq!(
+            Moderation::pending_appeal_id_by_entity_in_space(EntityId::Post(POST1), SPACE1),
+            Some(APPEAL2)
+        );
+    });
+}
+
+#[test]
+fn resolve_appeal_should_fail_when_appeal_not_found() {
+    ExtBuilder::build_with_blocked_post().execute_with(|| {
+        assert_noop!(_accept_default_appeal(), Error::<Test>::AppealNotFound);
+    });
+}
+
+#[test]
+fn resolve_appeal_should_fail_when_appeal_already_resolved() {
+    ExtBuilder::build_with_blocked_post_then_appeal().execute_with(|| {
+        assert_ok!(_accept_default_appeal());
+        assert_noop!(_accept_default_appeal(), Error::<Test>::AppealAlreadyResolved);
+    });
+}
+
+#[test]
+fn resolve_appeal_should_fail_when_resolution_is_pending() {
+    ExtBuilder::build_with_blocked_post_then_appeal().execute_with(|| {
+        assert_noop!(
+            _resolve_appeal(None, None, Some(AppealStatus::Pending)),
+            Error::<Test>::InvalidAppealResolution
+        );
+    });
+}
+
+#[test]
+fn resolve_appeal_should_fail_when_origin_has_no_permission() {
+    ExtBuilder::build_with_blocked_post_then_appeal().execute_with(|| {
+        assert_noop!(
+            _resolve_appeal(Some(Origin::signed(ACCOUNT_NOT_MODERATOR)), None, None),
+            Error::<Test>::NoPermissionToUpdateEntityStatus
+        );
+    });
+}
+
+#[test]
+fn moderation_effects_should_preview_post_block() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        let effects = Moderation::moderation_effects(
+            EntityId::Post(POST1),
+            SPACE1,
+            Some(EntityStatus::Blocked)
+        ).unwrap();
+
+        assert!(effects.is_status_changed);
+        assert!(effects.is_entity_in_scope);
+        assert_eq!(effects.removed_post_ids, vec![POST1]);
+        assert_eq!(effects.impacted_accounts, vec![ACCOUNT_SCOPE_OWNER]);
+        assert_eq!(effects.space_posts_count_diff, -1);
+
+        // Nothing is applied:
+        assert!(Moderation::status_by_entity_in_space(EntityId::Post(POST1), SPACE1).is_none());
+        assert_eq!(Posts::post_by_id(POST1).unwrap().space_id, Some(SPACE1));
+    });
+}
+
+#[test]
+fn moderation_effects_should_preview_account_block() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        assert_ok!(SpaceFollows::follow_space(Origin::signed(ACCOUNT_NOT_MODERATOR), SPACE1));
+
+        let effects = Moderation::moderation_effects(
+            EntityId::Account(ACCOUNT_NOT_MODERATOR),
+            SPACE1,
+            Some(EntityStatus::Blocked)
+        ).unwrap();
+
+        assert_eq!(effects.unfollowed_accounts, vec![ACCOUNT_NOT_MODERATOR]);
+        assert_eq!(effects.space_followers_count_diff, -1);
+        assert!(SpaceFollows::space_followed_by_account((ACCOUNT_NOT_MODERATOR, SPACE1)));
+    });
+}
+
+#[test]
+fn moderation_effects_should_be_empty_when_status_is_unchanged() {
+    ExtBuilder::build_with_blocked_post().execute_with(|| {
+        let effects = Moderation::moderation_effects(
+            EntityId::Post(POST1),
+            SPACE1,
+            Some(EntityStatus::Blocked)
+        ).unwrap();
+
+        assert!(!effects.is_status_changed);
+        assert!(effects.removed_post_ids.is_empty());
+    });
+}
+
+#[test]
+fn get_reports_in_space_should_work() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        let reports = Moderation::get_reports_in_space(SPACE1, 0, 10).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].id, REPORT1);
+        assert_eq!(reports[0].reported_entity, EntityId::Post(POST1));
+
+        assert!(Moderation::get_reports_in_space(SPACE1, 1, 10).unwrap().is_empty());
+    });
+}
@@ -0,0 +1,86 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use frame_support::{
+    decl_event, decl_module,
+    dispatch::DispatchResult,
+    traits::Get,
+    transactional,
+};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use frame_system::{self as system, ensure_signed};
+
+use pallet_permissions::SpacePermissions;
+use pallet_posts::{Module as Posts, PostExtension, PostOptions};
+use pallet_profiles::Module as Profiles;
+use pallet_spaces::Module as Spaces;
+use pallet_utils::{Content, PostId, SpaceId};
+
+/// Options of a space that is created for a new user during onboarding.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct OnboardingSpace {
+    pub parent_id: Option<SpaceId>,
+    pub handle: Option<Vec<u8>>,
+    pub content: Content,
+    pub permissions: Option<SpacePermissions>,
+}
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_profiles::Config
+    + pallet_spaces::Config
+    + pallet_posts::Config
+{
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Config>::AccountId,
+    {
+        /// An account has created its profile, space and first post.
+        AccountOnboarded(AccountId, SpaceId, PostId),
+    }
+);
+
+// The pallet's dispatchable functions.
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    /// Create a profile, a space and a first post in this space on behalf of a new user.
+    /// Either all of them are created or none of them.
+    #[weight = 700_000 + T::DbWeight::get().reads_writes(24, 24)]
+    #[transactional]
+    pub fn onboard(
+      origin,
+      profile_content: Content,
+      space_opts: OnboardingSpace,
+      first_post_content: Content
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Profiles::<T>::do_create_profile(who.clone(), profile_content)?;
+
+      let OnboardingSpace { parent_id, handle, content, permissions } = space_opts;
+      let space_id = Spaces::<T>::do_create_space(who.clone(), parent_id, handle, content, permissions)?;
+
+      let post_id = Posts::<T>::do_create_post_with_options(
+        who.clone(),
+        Some(space_id),
+        PostExtension::RegularPost,
+        first_post_content,
+        PostOptions::default()
+      )?;
+
+      Self::deposit_event(RawEvent::AccountOnboarded(who, space_id, post_id));
+      Ok(())
+    }
+  }
+}
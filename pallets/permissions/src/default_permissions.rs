@@ -56,6 +56,8 @@ parameter_types! {
       SP::UpdateEntityStatus,
 
       SP::UpdateSpaceSettings,
+
+      SP::CommentOnInactivePosts,
     ].into_iter().collect()),
   };
 }
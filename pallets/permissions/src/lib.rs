@@ -11,6 +11,7 @@ use frame_support::{
 use sp_runtime::RuntimeDebug;
 use sp_std::{
   collections::btree_set::BTreeSet,
+  vec,
   prelude::*
 };
 use frame_system::{self as system};
@@ -86,6 +87,9 @@ pub enum SpacePermission {
 
   /// Allows to update space settings across different pallets.
   UpdateSpaceSettings,
+
+  /// Create comments on posts that are locked for comments due to inactivity.
+  CommentOnInactivePosts,
 }
 
 pub type SpacePermissionSet = BTreeSet<SpacePermission>;
@@ -146,6 +150,46 @@ decl_module! {
 }
 
 impl SpacePermission {
+  /// All space permissions, e.g. to check which of them an account has in a space.
+  pub fn all() -> Vec<SpacePermission> {
+    use SpacePermission::*;
+
+    vec![
+      ManageRoles,
+      RepresentSpaceInternally,
+      RepresentSpaceExternally,
+      UpdateSpace,
+      CreateSubspaces,
+      UpdateOwnSubspaces,
+      DeleteOwnSubspaces,
+      HideOwnSubspaces,
+      UpdateAnySubspace,
+      DeleteAnySubspace,
+      HideAnySubspace,
+      CreatePosts,
+      UpdateOwnPosts,
+      DeleteOwnPosts,
+      HideOwnPosts,
+      UpdateAnyPost,
+      DeleteAnyPost,
+      HideAnyPost,
+      CreateComments,
+      UpdateOwnComments,
+      DeleteOwnComments,
+      HideOwnComments,
+      HideAnyComment,
+      Upvote,
+      Downvote,
+      Share,
+      OverrideSubspacePermissions,
+      OverridePostPermissions,
+      SuggestEntityStatus,
+      UpdateEntityStatus,
+      UpdateSpaceSettings,
+      CommentOnInactivePosts,
+    ]
+  }
+
   fn is_present_in_role(&self, perms_opt: Option<SpacePermissionSet>) -> bool {
     if let Some(perms) = perms_opt {
       if perms.contains(self) {
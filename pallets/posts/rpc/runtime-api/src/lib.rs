@@ -4,7 +4,8 @@ use codec::Codec;
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::vec::Vec;
 
-use pallet_posts::rpc::{FlatPost, FlatPostKind, RepliesByPostId};
+use pallet_posts::rpc::{FlatContentProof, FlatPost, FlatPostKind, FlatSpaceView, RepliesByPostId};
+use pallet_spaces::rpc::SpaceIdOrHandle;
 use pallet_utils::{PostId, SpaceId};
 
 sp_api::decl_runtime_apis! {
@@ -35,5 +36,21 @@ sp_api::decl_runtime_apis! {
         fn get_replies_by_parent_ids(parent_ids: Vec<PostId>, offset: u64, limit: u16) -> RepliesByPostId<AccountId, BlockNumber>;
 
         fn get_feed(account: AccountId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
+
+        fn get_post_ids_by_account_in_block_range(
+            account: AccountId,
+            from_block: BlockNumber,
+            to_block: BlockNumber,
+            limit: u16
+        ) -> Vec<PostId>;
+
+        fn get_post_storage_key(post_id: PostId) -> Vec<u8>;
+
+        fn get_space_view(
+            space_id_or_handle: SpaceIdOrHandle,
+            viewer: Option<AccountId>
+        ) -> Option<FlatSpaceView<AccountId, BlockNumber>>;
+
+        fn get_content_proof(post_id: PostId, at_block: BlockNumber) -> Option<FlatContentProof<BlockNumber>>;
     }
 }
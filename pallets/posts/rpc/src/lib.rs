@@ -5,8 +5,10 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
+use sp_core::Bytes;
 
-use pallet_posts::rpc::{FlatPost, FlatPostKind, RepliesByPostId};
+use pallet_posts::rpc::{FlatContentProof, FlatPost, FlatPostKind, FlatSpaceView, RepliesByPostId};
+use pallet_spaces::rpc::SpaceIdOrHandle;
 use pallet_utils::{PostId, SpaceId, rpc::map_rpc_error};
 pub use posts_runtime_api::PostsApi as PostsRuntimeApi;
 
@@ -105,6 +107,39 @@ pub trait PostsApi<BlockHash, AccountId, BlockNumber> {
         offset: u64,
         limit: u16,
     ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "posts_getPostIdsByAccountInBlockRange")]
+    fn get_post_ids_by_account_in_block_range(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        limit: u16,
+    ) -> Result<Vec<PostId>>;
+
+    #[rpc(name = "posts_getPostStorageKey")]
+    fn get_post_storage_key(
+        &self,
+        at: Option<BlockHash>,
+        post_id: PostId,
+    ) -> Result<Bytes>;
+
+    #[rpc(name = "posts_getSpaceView")]
+    fn get_space_view(
+        &self,
+        at: Option<BlockHash>,
+        space_id_or_handle: SpaceIdOrHandle,
+        viewer: Option<AccountId>,
+    ) -> Result<Option<FlatSpaceView<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "posts_getContentProof")]
+    fn get_content_proof(
+        &self,
+        at: Option<BlockHash>,
+        post_id: PostId,
+        at_block: BlockNumber,
+    ) -> Result<Option<FlatContentProof<BlockNumber>>>;
 }
 
 pub struct Posts<C, M> {
@@ -267,4 +302,53 @@ where
         let runtime_api_result = api.get_feed(&at, account, offset, limit);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_post_ids_by_account_in_block_range(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        limit: u16
+    ) -> Result<Vec<PostId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_post_ids_by_account_in_block_range(&at, account, from_block, to_block, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_post_storage_key(&self, at: Option<<Block as BlockT>::Hash>, post_id: PostId) -> Result<Bytes> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_post_storage_key(&at, post_id);
+        runtime_api_result.map(Bytes).map_err(map_rpc_error)
+    }
+
+    fn get_space_view(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        space_id_or_handle: SpaceIdOrHandle,
+        viewer: Option<AccountId>,
+    ) -> Result<Option<FlatSpaceView<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_space_view(&at, space_id_or_handle, viewer);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_content_proof(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        post_id: PostId,
+        at_block: BlockNumber,
+    ) -> Result<Option<FlatContentProof<BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_content_proof(&at, post_id, at_block);
+        runtime_api_result.map_err(map_rpc_error)
+    }
 }
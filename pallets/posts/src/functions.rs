@@ -1,4 +1,6 @@
 use frame_support::dispatch::DispatchResult;
+use sp_runtime::traits::Saturating;
+use sp_std::collections::btree_set::BTreeSet;
 
 use pallet_utils::{SpaceId, remove_from_vec};
 
@@ -161,7 +163,8 @@ impl Default for PostUpdate {
         PostUpdate {
             space_id: None,
             content: None,
-            hidden: None
+            hidden: None,
+            content_kind: None,
         }
     }
 }
@@ -218,6 +221,246 @@ impl<T: Config> Module<T> {
         Ok(Self::post_by_id(post_id).ok_or(Error::<T>::PostNotFound)?)
     }
 
+    /// Create a post filling omitted options with the content defaults of a creator.
+    pub fn do_create_post_with_options(
+        creator: T::AccountId,
+        space_id_opt: Option<SpaceId>,
+        extension: PostExtension,
+        content: Content,
+        options: PostOptions,
+    ) -> Result<PostId, DispatchError> {
+        let defaults = T::ContentDefaults::content_defaults(&creator).unwrap_or_default();
+
+        let license = options.license.or(defaults.license);
+        let language = options.language.or(defaults.language);
+        let hidden = options.hidden.unwrap_or(defaults.hidden_by_default);
+
+        Utils::<T>::ensure_license_and_language_are_valid(&license, &language)?;
+
+        if options.content_kind.is_some() {
+            Utils::<T>::ensure_content_is_some(&content)?;
+        }
+
+        let post_id = Self::do_create_post(creator, space_id_opt, extension, content, hidden)?;
+
+        if let Some(license) = license {
+            LicenseByPostId::insert(post_id, license);
+        }
+        if let Some(language) = language {
+            LanguageByPostId::insert(post_id, language);
+        }
+        if let Some(content_kind) = options.content_kind {
+            ContentKindByPostId::insert(post_id, content_kind);
+        }
+
+        Ok(post_id)
+    }
+
+    /// Create a new post (or comment, or sharing post) on behalf of `creator`.
+    /// A root post can be created `hidden`; its space counters are updated accordingly.
+    pub fn do_create_post(
+        creator: T::AccountId,
+        space_id_opt: Option<SpaceId>,
+        extension: PostExtension,
+        content: Content,
+        hidden: bool,
+    ) -> Result<PostId, DispatchError> {
+        let new_post_id = Self::next_post_id();
+        Self::do_create_post_with_id(creator, new_post_id, space_id_opt, extension, content, hidden)?;
+
+        NextPostId::mutate(|n| { *n += 1; });
+        Ok(new_post_id)
+    }
+
+    /// Create a post with a given id. The caller is responsible for the id being unused,
+    /// e.g. it is the next post id or an id reserved for an import.
+    pub(crate) fn do_create_post_with_id(
+        creator: T::AccountId,
+        new_post_id: PostId,
+        space_id_opt: Option<SpaceId>,
+        extension: PostExtension,
+        content: Content,
+        hidden: bool,
+    ) -> DispatchResult {
+        T::IsAccountActive::ensure_account_is_active(&creator)?;
+        Self::ensure_spam_score_below_max(&creator)?;
+        Utils::<T>::is_valid_content(content.clone())?;
+
+        let mut new_post: Post<T> = Post::new(new_post_id, creator.clone(), space_id_opt, extension, content.clone());
+
+        // Get space from either space_id_opt or Comment if a comment provided
+        let space = &new_post.get_space()?;
+        ensure!(!space.hidden, Error::<T>::CannotCreateInHiddenScope);
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(creator.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+        ensure!(T::IsContentBlocked::is_allowed_content(content, space.id), UtilsError::<T>::ContentIsBlocked);
+
+        let root_post = &mut new_post.get_root_post()?;
+        ensure!(!root_post.hidden, Error::<T>::CannotCreateInHiddenScope);
+
+        // Check whether account has permission to create Post (by extension)
+        let mut permission_to_check = SpacePermission::CreatePosts;
+        let mut error_on_permission_failed = Error::<T>::NoPermissionToCreatePosts;
+
+        if let PostExtension::Comment(_) = extension {
+            permission_to_check = SpacePermission::CreateComments;
+            error_on_permission_failed = Error::<T>::NoPermissionToCreateComments;
+        }
+
+        Spaces::ensure_account_has_space_permission(
+            creator.clone(),
+            &space,
+            permission_to_check,
+            error_on_permission_failed.into()
+        )?;
+
+        match extension {
+            PostExtension::RegularPost => (),
+            PostExtension::SharedPost(post_id) => Self::create_sharing_post(&creator, new_post_id, post_id)?,
+            PostExtension::Comment(comment_ext) => {
+                Self::ensure_comments_not_locked(&creator, root_post, space)?;
+                Self::create_comment(new_post_id, comment_ext, root_post)?
+            },
+        }
+
+        if new_post.is_root_post() {
+            new_post.hidden = hidden;
+
+            Spaces::<T>::mutate_space_stats(space.id, |stats| {
+                stats.inc_posts();
+                if hidden {
+                    stats.inc_hidden_posts();
+                }
+            });
+
+            PostIdsBySpaceId::mutate(space.id, |ids| ids.push(new_post_id));
+        }
+
+        PostById::insert(new_post_id, new_post.clone());
+        Self::record_content_proof(&new_post);
+        Self::note_recent_post(&creator);
+        Self::index_post_by_account(&creator, new_post_id);
+
+        T::PostHooks::on_post_created(&creator, &new_post);
+
+        Self::deposit_event(RawEvent::PostCreated(creator, new_post_id));
+        Ok(())
+    }
+
+    /// Make visible all scheduled posts that should be published at block `now`.
+    pub(crate) fn publish_scheduled_posts(now: T::BlockNumber) -> Weight {
+        let scheduled_post_ids = ScheduledPostIdsAt::<T>::take(now);
+        let posts_count = scheduled_post_ids.len() as Weight;
+
+        for post_id in scheduled_post_ids {
+            // A publication might have been canceled, e.g. when a post was unhidden manually.
+            if Self::publish_at_by_post_id(post_id) != Some(now) {
+                continue;
+            }
+
+            if let Some(mut post) = Self::post_by_id(post_id) {
+                Self::cancel_scheduled_publication(&post);
+
+                if !post.hidden {
+                    continue;
+                }
+
+                if let Some(space_id) = post.try_get_space_id() {
+                    Spaces::<T>::mutate_space_stats(space_id, |stats| stats.dec_hidden_posts());
+                }
+
+                post.hidden = false;
+                let owner = post.owner.clone();
+                PostById::insert(post_id, post.clone());
+
+                T::PostHooks::on_post_hidden(&owner, &post);
+
+                Self::deposit_event(RawEvent::ScheduledPostPublished(owner, post_id));
+            }
+        }
+
+        T::DbWeight::get().reads_writes(
+            1 + posts_count.saturating_mul(4),
+            1 + posts_count.saturating_mul(4),
+        )
+    }
+
+    /// Cancel a pending publication of a scheduled post, if any,
+    /// and release a scheduled posts slot of its creator.
+    pub(crate) fn cancel_scheduled_publication(post: &Post<T>) {
+        if PublishAtByPostId::<T>::take(post.id).is_some() {
+            ScheduledPostsCountByAccount::<T>::mutate(&post.created.account, |count| {
+                *count = count.saturating_sub(1)
+            });
+        }
+    }
+
+    /// Hide or unhide the next chunk of root posts of `author` in a space, starting from
+    /// a saved cursor. Returns the number of changed posts and whether all posts were checked.
+    pub(crate) fn hide_next_posts_by_author(
+        moderator: &T::AccountId,
+        space_id: SpaceId,
+        author: &T::AccountId,
+        hide: bool,
+    ) -> (u32, bool) {
+        let cursor_key = (space_id, author.clone());
+        let post_ids = Self::post_ids_by_space_id(space_id);
+
+        // Start over if there is no cursor or it was saved for the opposite visibility.
+        let start = match Self::hide_posts_by_author_cursor(&cursor_key) {
+            Some((cursor_hide, next_index)) if cursor_hide == hide => post_ids.len().min(next_index as usize),
+            _ => 0,
+        };
+        let end = post_ids.len().min(start.saturating_add(T::MaxPostsPerHideByAuthor::get() as usize));
+
+        let mut changed_count: u32 = 0;
+        for post_id in &post_ids[start..end] {
+            if let Some(mut post) = Self::post_by_id(post_id) {
+                if post.owner != *author {
+                    continue;
+                }
+
+                // A scheduled post is already hidden, but it should not be published later.
+                Self::cancel_scheduled_publication(&post);
+
+                if post.hidden == hide {
+                    continue;
+                }
+
+                let old_data = PostUpdate { hidden: Some(post.hidden), ..Default::default() };
+                post.hidden = hide;
+                post.updated = Some(WhoAndWhen::<T>::new(moderator.clone()));
+                PostById::<T>::insert(post.id, post.clone());
+
+                T::PostHooks::on_post_updated(moderator, &post, &old_data);
+                T::PostHooks::on_post_hidden(moderator, &post);
+                T::AfterPostUpdated::after_post_updated(moderator.clone(), &post, old_data);
+                changed_count += 1;
+            }
+        }
+
+        if changed_count > 0 {
+            Spaces::<T>::mutate_space_stats(space_id, |stats| {
+                for _ in 0..changed_count {
+                    if hide {
+                        stats.inc_hidden_posts();
+                    } else {
+                        stats.dec_hidden_posts();
+                    }
+                }
+            });
+        }
+
+        let finished = end == post_ids.len();
+        if finished {
+            HidePostsByAuthorCursor::<T>::remove(&cursor_key);
+        } else {
+            HidePostsByAuthorCursor::<T>::insert(&cursor_key, (hide, end as u32));
+        }
+
+        (changed_count, finished)
+    }
+
     fn share_post(
         account: T::AccountId,
         original_post: &mut Post<T>,
@@ -228,12 +471,99 @@ impl<T: Config> Module<T> {
         let original_post_id = original_post.id;
         PostById::insert(original_post_id, original_post.clone());
         SharedPostIdsByOriginalPostId::mutate(original_post_id, |ids| ids.push(shared_post_id));
+        SharedVersionBySharingPostId::insert(shared_post_id, Self::content_version_by_post_id(original_post_id));
 
         Self::deposit_event(RawEvent::PostShared(account, original_post_id));
 
         Ok(())
     }
 
+    /// Record a proof of the current content of a post at the current block.
+    /// Only the latest `MaxContentProofsPerPost` proofs are kept.
+    pub(crate) fn record_content_proof(post: &Post<T>) {
+        let proof = ContentProof {
+            content: post.content.clone(),
+            block: <system::Pallet<T>>::block_number(),
+            parent_hash: <system::Pallet<T>>::parent_hash(),
+            time: <pallet_timestamp::Pallet<T>>::now(),
+        };
+
+        let max_proofs = T::MaxContentProofsPerPost::get() as usize;
+        ContentProofsByPostId::<T>::mutate(post.id, |proofs| {
+            proofs.push(proof);
+            if proofs.len() > max_proofs {
+                proofs.drain(..proofs.len() - max_proofs);
+            }
+        });
+    }
+
+    /// Increase a content version of a post and, if this post has been shared,
+    /// notify spaces it was shared into that the original content changed.
+    ///
+    /// Only spaces of the first `MaxSharesToNotifyOnUpdate` sharing posts are notified.
+    /// Any sharing post can still be checked with `is_original_changed_since_shared`.
+    pub(crate) fn bump_content_version(post: &Post<T>) {
+        let version = ContentVersionByPostId::mutate(post.id, |version| {
+            *version = version.saturating_add(1);
+            *version
+        });
+
+        if post.shares_count == 0 {
+            return;
+        }
+
+        let mut notified_space_ids: Vec<SpaceId> = Vec::new();
+        let sharing_post_ids = Self::shared_post_ids_by_original_post_id(post.id);
+
+        let max_notified_shares = (post.shares_count as u32).min(T::MaxSharesToNotifyOnUpdate::get());
+
+        for sharing_post_id in sharing_post_ids.into_iter().take(max_notified_shares as usize) {
+            let space_id_opt = Self::post_by_id(sharing_post_id).and_then(|sharing_post| sharing_post.space_id);
+
+            if let Some(space_id) = space_id_opt {
+                if !notified_space_ids.contains(&space_id) {
+                    notified_space_ids.push(space_id);
+                    Self::deposit_event(RawEvent::SharedOriginalUpdated(space_id, post.id, version));
+                }
+            }
+        }
+    }
+
+    /// Get a start block of an era that contains a given block.
+    pub fn posts_era_start(block: T::BlockNumber) -> T::BlockNumber {
+        let era_length = T::PostsByAccountEraLength::get();
+        if era_length.is_zero() {
+            return Zero::zero();
+        }
+        block - block % era_length
+    }
+
+    fn index_post_by_account(account: &T::AccountId, post_id: PostId) {
+        let era_start = Self::posts_era_start(<system::Pallet<T>>::block_number());
+
+        PostErasByAccount::<T>::mutate(account, |eras| {
+            if eras.last() != Some(&era_start) {
+                eras.push(era_start);
+            }
+
+            if eras.len() > T::MaxPostErasPerAccount::get() as usize {
+                let oldest_era = eras.remove(0);
+                PostIdsByAccountInEra::<T>::remove(account, oldest_era);
+            }
+        });
+        PostIdsByAccountInEra::<T>::mutate(account, era_start, |post_ids| post_ids.push(post_id));
+    }
+
+    /// Whether the content of an original post was edited after it was shared by a given post.
+    pub fn is_original_changed_since_shared(sharing_post_id: PostId, extension: &PostExtension) -> bool {
+        match extension {
+            PostExtension::SharedPost(original_post_id) =>
+                Self::content_version_by_post_id(original_post_id)
+                    > Self::shared_version_by_sharing_post_id(sharing_post_id),
+            _ => false,
+        }
+    }
+
     pub fn is_root_post_hidden(post_id: PostId) -> Result<bool, DispatchError> {
         let post = Self::require_post(post_id)?;
         let root_post = post.get_root_post()?;
@@ -329,7 +659,8 @@ impl<T: Config> Module<T> {
             ensure!(parent_comment.is_comment(), Error::<T>::NotACommentByParentId);
 
             let ancestors = Self::get_post_ancestors(parent_id);
-            ensure!(ancestors.len() < T::MaxCommentDepth::get() as usize, Error::<T>::MaxCommentDepthReached);
+            let max_comment_depth = Self::max_comment_depth_in_space(root_post.space_id);
+            ensure!(ancestors.len() < max_comment_depth as usize, Error::<T>::MaxCommentDepthReached);
 
             commented_post_id = parent_id;
         }
@@ -337,17 +668,52 @@ impl<T: Config> Module<T> {
         root_post.inc_replies();
 
         Self::for_each_post_ancestor(commented_post_id, |post| post.inc_replies())?;
+        LastActivityAtByPostId::<T>::insert(root_post.id, <system::Pallet<T>>::block_number());
         PostById::insert(root_post.id, root_post);
         ReplyIdsByPostId::mutate(commented_post_id, |reply_ids| reply_ids.push(new_post_id));
 
         Ok(())
     }
 
+    /// Get the max comment depth set by a space, that cannot exceed `MaxCommentDepth`.
+    pub fn max_comment_depth_in_space(space_id_opt: Option<SpaceId>) -> u32 {
+        let max_comment_depth = T::MaxCommentDepth::get();
+
+        space_id_opt
+            .and_then(Spaces::<T>::space_by_id)
+            .and_then(|space| space.max_comment_depth)
+            .map_or(max_comment_depth, |depth| depth.min(max_comment_depth))
+    }
+
+    /// Check that a root post is not locked for new comments because of its inactivity,
+    /// otherwise an account should have a permission to comment on inactive posts.
+    pub(crate) fn ensure_comments_not_locked(
+        account: &T::AccountId,
+        root_post: &Post<T>,
+        space: &Space<T>
+    ) -> DispatchResult {
+        if let Some(lock_after) = Self::comments_lock_after_by_space_id(space.id) {
+            let last_activity_at = Self::last_activity_at_by_post_id(root_post.id)
+                .unwrap_or(root_post.created.block);
+
+            let now = <system::Pallet<T>>::block_number();
+            if now >= last_activity_at.saturating_add(lock_after) {
+                Spaces::ensure_account_has_space_permission(
+                    account.clone(),
+                    space,
+                    SpacePermission::CommentOnInactivePosts,
+                    Error::<T>::CommentsLockedDueToInactivity.into()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn create_sharing_post(
         creator: &T::AccountId,
         new_post_id: PostId,
         original_post_id: PostId,
-        space: &mut Space<T>
     ) -> DispatchResult {
         let original_post = &mut Self::post_by_id(original_post_id)
             .ok_or(Error::<T>::OriginalPostNotFound)?;
@@ -362,8 +728,6 @@ impl<T: Config> Module<T> {
             Error::<T>::NoPermissionToShare.into()
         )?;
 
-        space.inc_posts();
-
         Self::share_post(creator.clone(), original_post, new_post_id)
     }
 
@@ -372,12 +736,16 @@ impl<T: Config> Module<T> {
         post: &Post<T>,
         mut f: F
     ) -> DispatchResult {
-        Spaces::<T>::mutate_space_by_id(space_id, |space: &mut Space<T>| {
-            f(&mut space.posts_count);
+        Spaces::<T>::ensure_space_exists(space_id)?;
+
+        Spaces::<T>::mutate_space_stats(space_id, |stats: &mut SpaceStats| {
+            f(&mut stats.posts_count);
             if post.hidden {
-                f(&mut space.hidden_posts_count);
+                f(&mut stats.hidden_posts_count);
             }
-        }).map(|_| ())
+        });
+
+        Ok(())
     }
 
     pub(crate) fn move_post_to_space(
@@ -411,6 +779,7 @@ impl<T: Config> Module<T> {
             PostExtension::RegularPost | PostExtension::SharedPost(_) => {
 
                 if let Some(old_space_id) = old_space_id_opt {
+                    Self::unpin_post_in_space(old_space_id, post.id);
 
                     // Decrease the number of posts on the old space
                     Self::mutate_posts_count_on_space(
@@ -463,6 +832,7 @@ impl<T: Config> Module<T> {
             // If post is not a comment:
 
             let space_id = post.get_space_id()?;
+            Self::unpin_post_in_space(space_id, post_id);
 
             // Decrease the number of posts on the space
             Self::mutate_posts_count_on_space(
@@ -480,6 +850,12 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    fn unpin_post_in_space(space_id: SpaceId, post_id: PostId) {
+        if Self::pinned_post_id_by_space_id(space_id) == Some(post_id) {
+            PinnedPostIdBySpaceId::remove(space_id);
+        }
+    }
+
     /// Rewrite ancestor counters when Post hidden status changes
     /// Warning: This will affect storage state!
     pub(crate) fn update_counters_on_comment_hidden_change(
@@ -502,3 +878,40 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 }
+
+impl<T: Config> MutedThreadsProvider<T::AccountId> for Module<T> {
+    fn muted_thread_ids(account: &T::AccountId) -> Vec<PostId> {
+        Self::muted_thread_ids_by_account(account)
+    }
+
+    fn set_muted_thread_ids(account: &T::AccountId, thread_ids: Vec<PostId>) -> DispatchResult {
+        ensure!(
+            thread_ids.len() <= T::MaxMutedThreadsPerAccount::get() as usize,
+            Error::<T>::TooManyMutedThreads
+        );
+
+        let new_thread_ids: Vec<PostId> = thread_ids.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+
+        for thread_id in &new_thread_ids {
+            ensure!(Self::require_post(*thread_id)?.is_root_post(), Error::<T>::CannotMuteComment);
+        }
+
+        for thread_id in MutedThreadIdsByAccount::<T>::take(account) {
+            ThreadMutedByAccount::<T>::remove((account.clone(), thread_id));
+        }
+        for thread_id in &new_thread_ids {
+            ThreadMutedByAccount::<T>::insert((account.clone(), *thread_id), true);
+        }
+        MutedThreadIdsByAccount::<T>::insert(account, new_thread_ids);
+
+        Ok(())
+    }
+}
+
+impl<T: Config> CreateWelcomePost<T> for Module<T> {
+    fn create_welcome_post(owner: T::AccountId, space_id: SpaceId, content: Content) -> DispatchResult {
+        let post_id = Self::do_create_post(owner, Some(space_id), PostExtension::RegularPost, content, false)?;
+        PinnedPostIdBySpaceId::insert(space_id, post_id);
+        Ok(())
+    }
+}
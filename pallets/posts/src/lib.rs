@@ -19,24 +19,32 @@ use scale_info::TypeInfo;
 use serde::{Serialize, Deserialize};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, fail,
-    dispatch::{DispatchError, DispatchResult}, ensure, traits::Get,
+    dispatch::{DispatchError, DispatchResult}, ensure,
+    traits::{Currency, Get, ReservableCurrency},
+    weights::Weight,
 };
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, traits::{Saturating, Zero}};
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use df_traits::moderation::{IsAccountBlocked, IsContentBlocked, IsPostBlocked};
+use df_traits::{
+    AccountCreatedAt, ContentDefaultsProvider, IsAccountActive, MutedThreadsProvider,
+    moderation::{AccountModerationFlags, IsAccountBlocked, IsContentBlocked, IsPostBlocked},
+};
 use pallet_permissions::SpacePermission;
-use pallet_spaces::{Module as Spaces, Space, SpaceById};
+use pallet_spaces::{Module as Spaces, Space, SpaceStats, CreateWelcomePost};
 use pallet_utils::{
     Module as Utils, Error as UtilsError,
-    SpaceId, WhoAndWhen, Content, PostId
+    SpaceId, WhoAndWhen, Content, ContentKind, PostId, remove_from_vec,
 };
 
 pub mod functions;
 
 pub mod rpc;
 
+pub mod spam_score;
+pub use spam_score::CheckSpamScore;
+
 /// Information about a post's owner, its' related space, content, and visibility.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
@@ -90,6 +98,57 @@ pub struct PostUpdate {
 
     pub content: Option<Content>,
     pub hidden: Option<bool>,
+    /// A content kind hint of the new `content`. The previous hint is dropped
+    /// when the content changes, so it's ignored if `content` is not updated.
+    pub content_kind: Option<ContentKind>,
+}
+
+/// Optional fields of a new post. Omitted fields are taken from the content defaults
+/// of a post creator, if any.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PostOptions {
+    pub license: Option<Vec<u8>>,
+    pub language: Option<Vec<u8>>,
+    /// Applies to root posts only.
+    pub hidden: Option<bool>,
+    pub content_kind: Option<ContentKind>,
+}
+
+/// Information about the origin of a post imported from outside of the chain.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PostProvenance {
+    /// A Unix timestamp (in milliseconds) at which the original post was created.
+    pub original_created_at: u64,
+}
+
+/// A range of post ids reserved in a space for a bulk import.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct PostIdsReservation<T: Config> {
+    /// An account that reserved post ids and holds a deposit for the ids not imported yet.
+    pub owner: T::AccountId,
+    /// The next post id to import.
+    pub next_id: PostId,
+    /// The last reserved post id (inclusive).
+    pub last_id: PostId,
+    /// A block after which no more posts can be imported,
+    /// and anyone can release the remaining post ids.
+    pub expires_at: T::BlockNumber,
+}
+
+/// A record of a content that a post had since a given block.
+/// Together with a storage proof of `ContentProofsByPostId`, it can be used to prove
+/// that a post had this content at that block, e.g. in an authorship dispute.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ContentProof<T: Config> {
+    pub content: Content,
+    pub block: T::BlockNumber,
+    /// A hash of the block preceding `block`,
+    /// because a hash of `block` itself is not known while it is being built.
+    pub parent_hash: T::Hash,
+    pub time: T::Moment,
 }
 
 /// Post extension provides specific information necessary for different kinds
@@ -116,6 +175,9 @@ impl Default for PostExtension {
     }
 }
 
+type BalanceOf<T> =
+    <<T as pallet_spaces::Config>::Currency as Currency<<T as system::Config>::AccountId>>::Balance;
+
 /// The pallet's configuration trait.
 pub trait Config: system::Config
     + pallet_utils::Config
@@ -130,7 +192,60 @@ pub trait Config: system::Config
 
     type AfterPostUpdated: AfterPostUpdated<Self>;
 
+    /// Hooks that let other pallets react to post lifecycle changes.
+    type PostHooks: PostHooks<Self>;
+
     type IsPostBlocked: IsPostBlocked<PostId>;
+
+    /// Max number of threads (root posts) an account can mute.
+    type MaxMutedThreadsPerAccount: Get<u16>;
+
+    /// Max number of posts that can be scheduled to be published at the same block.
+    type MaxScheduledPostsPerBlock: Get<u16>;
+
+    /// A period (in blocks) within which posts of an account are counted as recent
+    /// when computing its spam score. Accounts younger than this period are treated as new.
+    type RecentPostsPeriod: Get<Self::BlockNumber>;
+
+    /// A spam score at which social-content transactions of an account are rejected.
+    type MaxSpamScore: Get<u32>;
+
+    type AccountCreatedAt: AccountCreatedAt<Self::AccountId, Self::BlockNumber>;
+
+    type AccountModerationFlags: AccountModerationFlags<Self::AccountId>;
+
+    /// Provides account-level defaults for fields omitted when creating a post.
+    type ContentDefaults: ContentDefaultsProvider<Self::AccountId>;
+
+    /// Max number of posts of a space checked by a single `hide_posts_by_author` call.
+    type MaxPostsPerHideByAuthor: Get<u32>;
+
+    /// The length (in blocks) of an era used to group post ids of an account into pages.
+    type PostsByAccountEraLength: Get<Self::BlockNumber>;
+
+    /// Max number of post ids a space owner can reserve at once for a bulk import.
+    type MaxPostIdsPerReservation: Get<u32>;
+
+    /// A deposit reserved for every post id reserved for an import.
+    /// It is returned as posts are imported or when the remaining ids are released.
+    type PostIdReservationDeposit: Get<BalanceOf<Self>>;
+
+    /// A period (in blocks) within which posts can be imported into reserved post ids.
+    type PostIdReservationPeriod: Get<Self::BlockNumber>;
+
+    /// Max number of the latest content proofs kept per post.
+    /// Older proofs are dropped when a post content is updated.
+    type MaxContentProofsPerPost: Get<u32>;
+
+    /// Max number of sharing posts whose spaces are notified when an original post content is updated.
+    type MaxSharesToNotifyOnUpdate: Get<u32>;
+
+    /// Max number of posts that can be scheduled by one account and not yet published.
+    type MaxScheduledPostsPerAccount: Get<u16>;
+
+    /// Max number of the latest eras kept in `PostErasByAccount` of an account.
+    /// Post ids of older eras are removed from the index.
+    type MaxPostErasPerAccount: Get<u32>;
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(10)]
@@ -138,8 +253,26 @@ pub trait AfterPostUpdated<T: Config> {
     fn after_post_updated(account: T::AccountId, post: &Post<T>, old_data: PostUpdate);
 }
 
+/// Hooks called by this pallet after a post (or comment) has been saved,
+/// so that other pallets (notifications, trending, analytics, etc.) can react to it.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait PostHooks<T: Config> {
+    /// Called after a post, comment or sharing post is created.
+    fn on_post_created(_account: &T::AccountId, _post: &Post<T>) {}
+
+    /// Called after a post is updated or moved. `old_data` holds the previous values of changed fields.
+    fn on_post_updated(_account: &T::AccountId, _post: &Post<T>, _old_data: &PostUpdate) {}
+
+    /// Called after a post is hidden or unhidden, including a publication of a scheduled post.
+    /// The new visibility can be checked with `post.hidden`.
+    fn on_post_hidden(_account: &T::AccountId, _post: &Post<T>) {}
+}
+
 pub const FIRST_POST_ID: u64 = 1;
 
+/// A weight of reading one post id of a space, see `hide_posts_by_author`.
+pub const WEIGHT_PER_SPACE_POST_ID: Weight = 1_000;
+
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Config> as PostsModule {
@@ -163,18 +296,131 @@ decl_storage! {
         /// Get the ids of all posts that have shared a given original post id.
         pub SharedPostIdsByOriginalPostId get(fn shared_post_ids_by_original_post_id):
             map hasher(twox_64_concat) PostId => Vec<PostId>;
+
+        /// Get the ids of all threads (root posts) muted by a given account.
+        pub MutedThreadIdsByAccount get(fn muted_thread_ids_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Vec<PostId>;
+
+        /// Whether an account has muted replies in a thread (root post).
+        pub ThreadMutedByAccount get(fn thread_muted_by_account):
+            map hasher(blake2_128_concat) (T::AccountId, PostId) => bool;
+
+        /// Get the ids of scheduled posts that should be published at a given block.
+        pub ScheduledPostIdsAt get(fn scheduled_post_ids_at):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<PostId>;
+
+        /// Get a content kind hint of a given post (or comment).
+        pub ContentKindByPostId get(fn content_kind_by_post_id):
+            map hasher(twox_64_concat) PostId => Option<ContentKind>;
+
+        /// Get an id of a post pinned in a given space, e.g. a welcome post.
+        pub PinnedPostIdBySpaceId get(fn pinned_post_id_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Option<PostId>;
+
+        /// Get a block number at which a scheduled post should be published.
+        pub PublishAtByPostId get(fn publish_at_by_post_id):
+            map hasher(twox_64_concat) PostId => Option<T::BlockNumber>;
+
+        /// Get a number of posts scheduled by a given account, that are not published yet.
+        pub ScheduledPostsCountByAccount get(fn scheduled_posts_count_by_account):
+            map hasher(blake2_128_concat) T::AccountId => u16;
+
+        /// Get a number of blocks of inactivity after which posts in a given space
+        /// are locked for new comments.
+        pub CommentsLockAfterBySpaceId get(fn comments_lock_after_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Option<T::BlockNumber>;
+
+        /// Get a block number of the last comment in a given root post.
+        /// If there are no comments yet, a block at which the post was created is used.
+        pub LastActivityAtByPostId get(fn last_activity_at_by_post_id):
+            map hasher(twox_64_concat) PostId => Option<T::BlockNumber>;
+
+        /// Get a block at which the current recent period of an account started
+        /// and the number of posts this account created since then.
+        pub RecentPostsByAccount get(fn recent_posts_by_account):
+            map hasher(blake2_128_concat) T::AccountId => (T::BlockNumber, u16);
+
+        /// Get the number of times the content of a given post has been edited.
+        pub ContentVersionByPostId get(fn content_version_by_post_id):
+            map hasher(twox_64_concat) PostId => u32;
+
+        /// Get a content version of an original post at the moment it was shared
+        /// by a given sharing post.
+        pub SharedVersionBySharingPostId get(fn shared_version_by_sharing_post_id):
+            map hasher(twox_64_concat) PostId => u32;
+
+        /// Get the ids of posts created by a given account (key 1) within an era
+        /// that starts at a given block (key 2).
+        /// Posts created before this index was introduced are not included.
+        pub PostIdsByAccountInEra get(fn post_ids_by_account_in_era): double_map
+            hasher(blake2_128_concat) T::AccountId,
+            hasher(twox_64_concat) T::BlockNumber
+            => Vec<PostId>;
+
+        /// Get the start blocks of eras in which a given account created posts, in ascending order.
+        /// Only the latest `MaxPostErasPerAccount` eras are kept.
+        pub PostErasByAccount get(fn post_eras_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Vec<T::BlockNumber>;
+
+        /// Get a license identifier of a given post (or comment).
+        pub LicenseByPostId get(fn license_by_post_id):
+            map hasher(twox_64_concat) PostId => Option<Vec<u8>>;
+
+        /// Get a language tag of a given post (or comment).
+        pub LanguageByPostId get(fn language_by_post_id):
+            map hasher(twox_64_concat) PostId => Option<Vec<u8>>;
+
+        /// Get a visibility being applied to posts of an author (key 2) in a space (key 1)
+        /// and an index in `PostIdsBySpaceId` from which `hide_posts_by_author` continues.
+        pub HidePostsByAuthorCursor get(fn hide_posts_by_author_cursor):
+            map hasher(blake2_128_concat) (SpaceId, T::AccountId) => Option<(bool, u32)>;
+
+        /// Get a range of post ids reserved for an import into a given space.
+        pub ReservedPostIdsBySpaceId get(fn reserved_post_ids_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Option<PostIdsReservation<T>>;
+
+        /// Get the provenance of a given imported post.
+        pub ProvenanceByPostId get(fn provenance_by_post_id):
+            map hasher(twox_64_concat) PostId => Option<PostProvenance>;
+
+        /// Get the proofs of the latest `MaxContentProofsPerPost` contents a given post has had,
+        /// from the oldest to the newest.
+        pub ContentProofsByPostId get(fn content_proofs_by_post_id):
+            map hasher(twox_64_concat) PostId => Vec<ContentProof<T>>;
     }
 }
 
 decl_event!(
     pub enum Event<T> where
         <T as system::Config>::AccountId,
+        <T as system::Config>::BlockNumber,
     {
         PostCreated(AccountId, PostId),
         PostUpdated(AccountId, PostId),
         PostDeleted(AccountId, PostId),
         PostShared(AccountId, PostId),
         PostMoved(AccountId, PostId),
+        ThreadMuted(AccountId, PostId),
+        ThreadUnmuted(AccountId, PostId),
+        PostScheduled(AccountId, PostId, BlockNumber),
+        ScheduledPostPublished(AccountId, PostId),
+        CommentsLockUpdated(AccountId, SpaceId, Option<BlockNumber>),
+        /// The content of an original post was edited after it had been shared into a space.
+        SharedOriginalUpdated(/* sharing space */ SpaceId, /* original post */ PostId, /* version */ u32),
+        /// A chunk of posts of an author in a space was hidden or unhidden by a moderator.
+        PostsByAuthorHidden(
+            /* moderator */ AccountId,
+            SpaceId,
+            /* author */ AccountId,
+            /* hidden */ bool,
+            /* changed posts */ u32,
+            /* finished */ bool,
+        ),
+        /// A range of post ids was reserved for an import into a space.
+        PostIdsReserved(AccountId, SpaceId, /* first */ PostId, /* last */ PostId),
+        PostImported(AccountId, PostId),
+        /// Post ids reserved in a space, that were not imported yet, were released.
+        PostIdsReleased(AccountId, SpaceId),
     }
 );
 
@@ -222,6 +468,42 @@ decl_error! {
         /// This post's extension is not a `Comment`.
         NotComment,
 
+        // Thread muting related errors:
+
+        /// Only a root post (not a comment) can be muted as a thread.
+        CannotMuteComment,
+        /// Account has already muted this thread.
+        AlreadyMutedThread,
+        /// Account has not muted this thread.
+        ThreadIsNotMuted,
+        /// Account has reached the max number of muted threads.
+        TooManyMutedThreads,
+
+        // Scheduled posts related errors:
+
+        /// Only root posts can be scheduled, not comments.
+        CannotScheduleComment,
+        /// A block to publish a post at should be in the future.
+        PublishAtIsInThePast,
+        /// Max number of posts are already scheduled to be published at this block.
+        TooManyPostsScheduledAtBlock,
+        /// Account has reached the max number of scheduled posts that are not published yet.
+        TooManyScheduledPostsByAccount,
+
+        // Comments lock related errors:
+
+        /// New comments lock period is the same as the current one.
+        NoUpdatesForCommentsLock,
+        /// Comments lock period should be greater than zero.
+        ZeroCommentsLockPeriod,
+        /// Post is locked for new comments because it had no activity for too long.
+        CommentsLockedDueToInactivity,
+
+        // Spam score related errors:
+
+        /// Account's spam score reached `MaxSpamScore`, so it cannot create or update posts for now.
+        SpamScoreTooHigh,
+
         // Permissions related errors:
 
         /// User has no permission to create root posts in this space.
@@ -236,6 +518,29 @@ decl_error! {
         NoPermissionToUpdateOwnPosts,
         /// A comment owner is not allowed to update their own comments in this space.
         NoPermissionToUpdateOwnComments,
+        /// User has no permission to update a comments lock in this space.
+        NoPermissionToUpdateCommentsLock,
+        /// User has no permission to hide any posts in this space.
+        NoPermissionToHideAnyPost,
+        /// A given number of posts in a space is less than the actual one.
+        SpacePostIdsCountTooLow,
+
+        // Post import related errors:
+
+        /// Number of post ids to reserve should be greater than zero.
+        ZeroPostIdsToReserve,
+        /// Cannot reserve more post ids at once than allowed by `MaxPostIdsPerReservation`.
+        TooManyPostIdsToReserve,
+        /// Space already has reserved post ids that are not used by an import yet.
+        PostIdsAlreadyReserved,
+        /// Space has no reserved post ids left to import posts into.
+        NoReservedPostIds,
+        /// Imported comment should reply to a post in the space that reserved post ids.
+        CannotImportIntoAnotherSpace,
+        /// Reserved post ids have expired, so posts cannot be imported into them.
+        PostIdsReservationExpired,
+        /// Only an account that reserved post ids can release them before they expire.
+        PostIdsReservationNotExpired,
     }
 }
 
@@ -244,13 +549,43 @@ decl_module! {
 
     const MaxCommentDepth: u32 = T::MaxCommentDepth::get();
 
+    const MaxMutedThreadsPerAccount: u16 = T::MaxMutedThreadsPerAccount::get();
+
+    const MaxScheduledPostsPerBlock: u16 = T::MaxScheduledPostsPerBlock::get();
+
+    const RecentPostsPeriod: T::BlockNumber = T::RecentPostsPeriod::get();
+
+    const MaxSpamScore: u32 = T::MaxSpamScore::get();
+
+    const PostsByAccountEraLength: T::BlockNumber = T::PostsByAccountEraLength::get();
+
+    const MaxPostsPerHideByAuthor: u32 = T::MaxPostsPerHideByAuthor::get();
+
+    const MaxPostIdsPerReservation: u32 = T::MaxPostIdsPerReservation::get();
+
+    const PostIdReservationDeposit: BalanceOf<T> = T::PostIdReservationDeposit::get();
+
+    const PostIdReservationPeriod: T::BlockNumber = T::PostIdReservationPeriod::get();
+
+    const MaxContentProofsPerPost: u32 = T::MaxContentProofsPerPost::get();
+
+    const MaxSharesToNotifyOnUpdate: u32 = T::MaxSharesToNotifyOnUpdate::get();
+
+    const MaxScheduledPostsPerAccount: u16 = T::MaxScheduledPostsPerAccount::get();
+
+    const MaxPostErasPerAccount: u32 = T::MaxPostErasPerAccount::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(8, 8)]
+    fn on_initialize(now: T::BlockNumber) -> Weight {
+      Self::publish_scheduled_posts(now)
+    }
+
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(17, 16)]
     pub fn create_post(
       origin,
       space_id_opt: Option<SpaceId>,
@@ -258,57 +593,66 @@ decl_module! {
       content: Content
     ) -> DispatchResult {
       let creator = ensure_signed(origin)?;
+      Self::do_create_post_with_options(creator, space_id_opt, extension, content, PostOptions::default())
+        .map(|_| ())
+    }
 
-      Utils::<T>::is_valid_content(content.clone())?;
-
-      let new_post_id = Self::next_post_id();
-      let new_post: Post<T> = Post::new(new_post_id, creator.clone(), space_id_opt, extension, content.clone());
-
-      // Get space from either space_id_opt or Comment if a comment provided
-      let space = &mut new_post.get_space()?;
-      ensure!(!space.hidden, Error::<T>::CannotCreateInHiddenScope);
-
-      ensure!(T::IsAccountBlocked::is_allowed_account(creator.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
-      ensure!(T::IsContentBlocked::is_allowed_content(content, space.id), UtilsError::<T>::ContentIsBlocked);
-
-      let root_post = &mut new_post.get_root_post()?;
-      ensure!(!root_post.hidden, Error::<T>::CannotCreateInHiddenScope);
+    /// Create a post with a license, a language and a visibility.
+    /// Omitted options are taken from the content defaults of the caller.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(17, 17)]
+    pub fn create_post_with_options(
+      origin,
+      space_id_opt: Option<SpaceId>,
+      extension: PostExtension,
+      content: Content,
+      options: PostOptions
+    ) -> DispatchResult {
+      let creator = ensure_signed(origin)?;
+      Self::do_create_post_with_options(creator, space_id_opt, extension, content, options).map(|_| ())
+    }
 
-      // Check whether account has permission to create Post (by extension)
-      let mut permission_to_check = SpacePermission::CreatePosts;
-      let mut error_on_permission_failed = Error::<T>::NoPermissionToCreatePosts;
+    /// Create a post that stays hidden until `publish_at` block,
+    /// then it becomes visible automatically.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(18, 15)]
+    pub fn create_scheduled_post(
+      origin,
+      space_id_opt: Option<SpaceId>,
+      extension: PostExtension,
+      content: Content,
+      publish_at: T::BlockNumber
+    ) -> DispatchResult {
+      let creator = ensure_signed(origin)?;
 
-      if let PostExtension::Comment(_) = extension {
-        permission_to_check = SpacePermission::CreateComments;
-        error_on_permission_failed = Error::<T>::NoPermissionToCreateComments;
-      }
+      ensure!(!matches!(extension, PostExtension::Comment(_)), Error::<T>::CannotScheduleComment);
+      ensure!(publish_at > <system::Pallet<T>>::block_number(), Error::<T>::PublishAtIsInThePast);
 
-      Spaces::ensure_account_has_space_permission(
-        creator.clone(),
-        &space,
-        permission_to_check,
-        error_on_permission_failed.into()
-      )?;
+      let mut scheduled_post_ids = Self::scheduled_post_ids_at(publish_at);
+      ensure!(
+        scheduled_post_ids.len() < T::MaxScheduledPostsPerBlock::get() as usize,
+        Error::<T>::TooManyPostsScheduledAtBlock
+      );
 
-      match extension {
-        PostExtension::RegularPost => space.inc_posts(),
-        PostExtension::SharedPost(post_id) => Self::create_sharing_post(&creator, new_post_id, post_id, space)?,
-        PostExtension::Comment(comment_ext) => Self::create_comment(new_post_id, comment_ext, root_post)?,
-      }
+      let scheduled_posts_count = Self::scheduled_posts_count_by_account(&creator);
+      ensure!(
+        scheduled_posts_count < T::MaxScheduledPostsPerAccount::get(),
+        Error::<T>::TooManyScheduledPostsByAccount
+      );
 
-      if new_post.is_root_post() {
-        SpaceById::insert(space.id, space.clone());
-        PostIdsBySpaceId::mutate(space.id, |ids| ids.push(new_post_id));
-      }
+      let post_id = Self::do_create_post(creator.clone(), space_id_opt, extension, content, true)?;
 
-      PostById::insert(new_post_id, new_post);
-      NextPostId::mutate(|n| { *n += 1; });
+      scheduled_post_ids.push(post_id);
+      ScheduledPostIdsAt::<T>::insert(publish_at, scheduled_post_ids);
+      PublishAtByPostId::<T>::insert(post_id, publish_at);
+      ScheduledPostsCountByAccount::<T>::insert(&creator, scheduled_posts_count.saturating_add(1));
 
-      Self::deposit_event(RawEvent::PostCreated(creator, new_post_id));
+      Self::deposit_event(RawEvent::PostScheduled(creator, post_id, publish_at));
       Ok(())
     }
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(5, 3)]
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(
+      16 + T::MaxSharesToNotifyOnUpdate::get() as Weight,
+      9
+    )]
     pub fn update_post(origin, post_id: PostId, update: PostUpdate) -> DispatchResult {
       let editor = ensure_signed(origin)?;
 
@@ -318,8 +662,11 @@ decl_module! {
 
       ensure!(has_updates, Error::<T>::NoUpdatesForPost);
 
+      T::IsAccountActive::ensure_account_is_active(&editor)?;
+      Self::ensure_spam_score_below_max(&editor)?;
+
       let mut post = Self::require_post(post_id)?;
-      let mut space_opt = post.try_get_space();
+      let space_opt = post.try_get_space();
 
       if let Some(space) = &space_opt {
         ensure!(T::IsAccountBlocked::is_allowed_account(editor.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
@@ -327,6 +674,7 @@ decl_module! {
       }
 
       let mut is_update_applied = false;
+      let mut is_content_updated = false;
       let mut old_data = PostUpdate::default();
 
       if let Some(content) = update.content {
@@ -340,28 +688,28 @@ decl_module! {
             );
           }
 
+          if update.content_kind.is_some() {
+            Utils::<T>::ensure_content_is_some(&content)?;
+          }
+
           old_data.content = Some(post.content.clone());
+          old_data.content_kind = Self::content_kind_by_post_id(post.id);
           post.content = content;
+          ContentKindByPostId::mutate_exists(post.id, |kind| *kind = update.content_kind);
           is_update_applied = true;
+          is_content_updated = true;
         }
       }
 
       if let Some(hidden) = update.hidden {
         if hidden != post.hidden {
-          space_opt = space_opt.map(|mut space| {
-            if hidden {
-              space.inc_hidden_posts();
-            } else {
-              space.dec_hidden_posts();
-            }
-
-            space
-          });
-
           if let PostExtension::Comment(comment_ext) = post.extension {
             Self::update_counters_on_comment_hidden_change(&comment_ext, hidden)?;
           }
 
+          // Changing visibility manually cancels a scheduled publication.
+          Self::cancel_scheduled_publication(&post);
+
           old_data.hidden = Some(post.hidden);
           post.hidden = hidden;
           is_update_applied = true;
@@ -372,19 +720,80 @@ decl_module! {
       if is_update_applied {
         post.updated = Some(WhoAndWhen::<T>::new(editor.clone()));
 
-        if let Some(space) = space_opt {
-          <SpaceById<T>>::insert(space.id, space);
+        if let (Some(space), Some(was_hidden)) = (&space_opt, old_data.hidden) {
+          Spaces::<T>::mutate_space_stats(space.id, |stats| {
+            if was_hidden {
+              stats.dec_hidden_posts();
+            } else {
+              stats.inc_hidden_posts();
+            }
+          });
         }
 
         <PostById<T>>::insert(post.id, post.clone());
+
+        T::PostHooks::on_post_updated(&editor, &post, &old_data);
+        if old_data.hidden.is_some() {
+          T::PostHooks::on_post_hidden(&editor, &post);
+        }
+
         T::AfterPostUpdated::after_post_updated(editor.clone(), &post, old_data);
 
+        if is_content_updated {
+          Self::bump_content_version(&post);
+          Self::record_content_proof(&post);
+        }
+
         Self::deposit_event(RawEvent::PostUpdated(editor, post_id));
       }
       Ok(())
     }
 
-    #[weight = T::DbWeight::get().reads(1) + 50_000]
+    /// Hide or unhide root posts of `author` in a space. Requires `HideAnyPost` permission.
+    /// At most `MaxPostsPerHideByAuthor` posts of a space are checked per call, so this call
+    /// should be repeated until a `PostsByAuthorHidden` event reports that it is finished.
+    ///
+    /// `space_post_ids_count` should be at least the number of posts in a space,
+    /// because all of their ids are read to find the next chunk.
+    #[weight = 100_000
+      + T::DbWeight::get().reads_writes(
+        5 + T::MaxPostsPerHideByAuthor::get().saturating_mul(2) as Weight,
+        2 + T::MaxPostsPerHideByAuthor::get().saturating_mul(4) as Weight
+      )
+      + (*space_post_ids_count as Weight).saturating_mul(WEIGHT_PER_SPACE_POST_ID)]
+    pub fn hide_posts_by_author(
+      origin,
+      space_id: SpaceId,
+      author: T::AccountId,
+      hide: bool,
+      space_post_ids_count: u32
+    ) -> DispatchResult {
+      let moderator = ensure_signed(origin)?;
+
+      ensure!(
+        PostIdsBySpaceId::decode_len(space_id).unwrap_or_default() <= space_post_ids_count as usize,
+        Error::<T>::SpacePostIdsCountTooLow
+      );
+
+      T::IsAccountActive::ensure_account_is_active(&moderator)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      ensure!(T::IsAccountBlocked::is_allowed_account(moderator.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+      Spaces::ensure_account_has_space_permission(
+        moderator.clone(),
+        &space,
+        SpacePermission::HideAnyPost,
+        Error::<T>::NoPermissionToHideAnyPost.into()
+      )?;
+
+      let (changed_count, finished) = Self::hide_next_posts_by_author(&moderator, space_id, &author, hide);
+
+      Self::deposit_event(RawEvent::PostsByAuthorHidden(moderator, space_id, author, hide, changed_count, finished));
+      Ok(())
+    }
+
+    #[weight = T::DbWeight::get().reads_writes(2, 2) + 50_000]
     pub fn move_post(origin, post_id: PostId, new_space_id: Option<SpaceId>) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
@@ -410,12 +819,231 @@ decl_module! {
         space_id: old_space_id,
         content: None,
         hidden: None,
+        content_kind: None,
       };
 
+      T::PostHooks::on_post_updated(&who, &post, &historical_data);
       T::AfterPostUpdated::after_post_updated(who.clone(), &post, historical_data);
 
       Self::deposit_event(RawEvent::PostMoved(who, post_id));
       Ok(())
     }
+
+    /// Set or remove (if `None`) a content kind hint of a post or comment.
+    /// The same permissions are required as for `update_post`.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(4, 1)]
+    pub fn update_post_content_kind(
+      origin,
+      post_id: PostId,
+      content_kind_opt: Option<ContentKind>
+    ) -> DispatchResult {
+      let editor = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+
+      if let Some(space) = post.try_get_space() {
+        ensure!(T::IsAccountBlocked::is_allowed_account(editor.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+        Self::ensure_account_can_update_post(&editor, &post, &space)?;
+      } else {
+        post.ensure_owner(&editor)?;
+      }
+
+      ensure!(
+        Self::content_kind_by_post_id(post_id) != content_kind_opt,
+        Error::<T>::NoUpdatesForPost
+      );
+
+      if let Some(content_kind) = content_kind_opt {
+        Utils::<T>::ensure_content_is_some(&post.content)?;
+        ContentKindByPostId::insert(post_id, content_kind);
+      } else {
+        ContentKindByPostId::remove(post_id);
+      }
+
+      Self::deposit_event(RawEvent::PostUpdated(editor, post_id));
+      Ok(())
+    }
+
+    /// Mute notifications about replies in a thread (root post) for the caller.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+    pub fn mute_thread(origin, root_post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(root_post_id)?;
+      ensure!(post.is_root_post(), Error::<T>::CannotMuteComment);
+
+      ensure!(!Self::thread_muted_by_account((who.clone(), root_post_id)), Error::<T>::AlreadyMutedThread);
+
+      let mut muted_thread_ids = Self::muted_thread_ids_by_account(&who);
+      ensure!(
+        muted_thread_ids.len() < T::MaxMutedThreadsPerAccount::get() as usize,
+        Error::<T>::TooManyMutedThreads
+      );
+
+      muted_thread_ids.push(root_post_id);
+      MutedThreadIdsByAccount::<T>::insert(&who, muted_thread_ids);
+      ThreadMutedByAccount::<T>::insert((who.clone(), root_post_id), true);
+
+      Self::deposit_event(RawEvent::ThreadMuted(who, root_post_id));
+      Ok(())
+    }
+
+    /// Unmute notifications about replies in a thread (root post) for the caller.
+    /// It's possible to unmute a thread even if its root post was removed.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+    pub fn unmute_thread(origin, root_post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      ensure!(Self::thread_muted_by_account((who.clone(), root_post_id)), Error::<T>::ThreadIsNotMuted);
+
+      MutedThreadIdsByAccount::<T>::mutate(&who, |ids| remove_from_vec(ids, root_post_id));
+      ThreadMutedByAccount::<T>::remove((who.clone(), root_post_id));
+
+      Self::deposit_event(RawEvent::ThreadUnmuted(who, root_post_id));
+      Ok(())
+    }
+
+    /// Set or remove (if `None`) a number of blocks of inactivity after which posts
+    /// in a space are locked for new comments. Accounts with `CommentOnInactivePosts`
+    /// permission can still comment on such posts.
+    #[weight = 25_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn update_comments_lock(
+      origin,
+      space_id: SpaceId,
+      lock_after_opt: Option<T::BlockNumber>
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      if let Some(lock_after) = lock_after_opt {
+        ensure!(!lock_after.is_zero(), Error::<T>::ZeroCommentsLockPeriod);
+      }
+
+      ensure!(
+        Self::comments_lock_after_by_space_id(space_id) != lock_after_opt,
+        Error::<T>::NoUpdatesForCommentsLock
+      );
+
+      let space = Spaces::require_space(space_id)?;
+
+      Spaces::ensure_account_has_space_permission(
+        who.clone(),
+        &space,
+        SpacePermission::UpdateSpaceSettings,
+        Error::<T>::NoPermissionToUpdateCommentsLock.into()
+      )?;
+
+      if let Some(lock_after) = lock_after_opt {
+        CommentsLockAfterBySpaceId::<T>::insert(space_id, lock_after);
+      } else {
+        CommentsLockAfterBySpaceId::<T>::remove(space_id);
+      }
+
+      Self::deposit_event(RawEvent::CommentsLockUpdated(who, space_id, lock_after_opt));
+      Ok(())
+    }
+
+    /// Reserve the next `count` post ids, so that a space owner can import existing posts
+    /// into this space with `import_post` keeping their order.
+    /// `PostIdReservationDeposit` is reserved for every post id.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 3)]
+    pub fn reserve_post_ids(origin, space_id: SpaceId, count: u32) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(count > 0, Error::<T>::ZeroPostIdsToReserve);
+      ensure!(count <= T::MaxPostIdsPerReservation::get(), Error::<T>::TooManyPostIdsToReserve);
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      space.ensure_space_owner(owner.clone())?;
+
+      ensure!(Self::reserved_post_ids_by_space_id(space_id).is_none(), Error::<T>::PostIdsAlreadyReserved);
+
+      let deposit = T::PostIdReservationDeposit::get().saturating_mul(count.into());
+      <T as pallet_spaces::Config>::Currency::reserve(&owner, deposit)?;
+
+      let first_id = Self::next_post_id();
+      let last_id = first_id.saturating_add(count as u64 - 1);
+      let expires_at = <system::Pallet<T>>::block_number().saturating_add(T::PostIdReservationPeriod::get());
+
+      ReservedPostIdsBySpaceId::<T>::insert(space_id, PostIdsReservation {
+        owner: owner.clone(),
+        next_id: first_id,
+        last_id,
+        expires_at,
+      });
+      NextPostId::put(last_id.saturating_add(1));
+
+      Self::deposit_event(RawEvent::PostIdsReserved(owner, space_id, first_id, last_id));
+      Ok(())
+    }
+
+    /// Create a post with the next post id reserved in a space by `reserve_post_ids`.
+    /// An original creation time of a post is recorded in its `provenance`.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(20, 18)]
+    pub fn import_post(
+      origin,
+      space_id: SpaceId,
+      extension: PostExtension,
+      content: Content,
+      provenance: PostProvenance
+    ) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      space.ensure_space_owner(owner.clone())?;
+
+      let mut reservation = Self::reserved_post_ids_by_space_id(space_id).ok_or(Error::<T>::NoReservedPostIds)?;
+      ensure!(
+        <system::Pallet<T>>::block_number() <= reservation.expires_at,
+        Error::<T>::PostIdsReservationExpired
+      );
+      let post_id = reservation.next_id;
+
+      let space_id_opt = match extension {
+        PostExtension::Comment(comment_ext) => {
+          let root_post = Self::require_post(comment_ext.root_post_id)?;
+          ensure!(root_post.space_id == Some(space_id), Error::<T>::CannotImportIntoAnotherSpace);
+          None
+        },
+        _ => Some(space_id),
+      };
+
+      Self::do_create_post_with_id(owner.clone(), post_id, space_id_opt, extension, content, false)?;
+
+      <T as pallet_spaces::Config>::Currency::unreserve(&reservation.owner, T::PostIdReservationDeposit::get());
+
+      if post_id < reservation.last_id {
+        reservation.next_id = post_id + 1;
+        ReservedPostIdsBySpaceId::<T>::insert(space_id, reservation);
+      } else {
+        ReservedPostIdsBySpaceId::<T>::remove(space_id);
+      }
+      ProvenanceByPostId::insert(post_id, provenance);
+
+      Self::deposit_event(RawEvent::PostImported(owner, post_id));
+      Ok(())
+    }
+
+    /// Release post ids reserved in a space that were not imported yet,
+    /// and return their deposit to an account that reserved them.
+    /// Before a reservation expires, only this account can release it; after that, anyone can.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+    pub fn release_post_ids(origin, space_id: SpaceId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let reservation = Self::reserved_post_ids_by_space_id(space_id).ok_or(Error::<T>::NoReservedPostIds)?;
+      ensure!(
+        who == reservation.owner || <system::Pallet<T>>::block_number() > reservation.expires_at,
+        Error::<T>::PostIdsReservationNotExpired
+      );
+
+      let remaining_ids = reservation.last_id.saturating_sub(reservation.next_id).saturating_add(1);
+      let remaining_deposit = T::PostIdReservationDeposit::get().saturating_mul((remaining_ids as u32).into());
+      <T as pallet_spaces::Config>::Currency::unreserve(&reservation.owner, remaining_deposit);
+
+      ReservedPostIdsBySpaceId::<T>::remove(space_id);
+
+      Self::deposit_event(RawEvent::PostIdsReleased(who, space_id));
+      Ok(())
+    }
   }
 }
@@ -4,11 +4,16 @@ use serde::{Deserialize, Serialize};
 use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use sp_std::{vec, prelude::*};
 
+use frame_support::{dispatch::DispatchError, storage::StorageMap};
+use sp_runtime::SaturatedConversion;
+
+use df_traits::IsAccountActive;
 use pallet_space_follows::Module as SpaceFollows;
-use pallet_spaces::Module as Spaces;
+use pallet_permissions::SpacePermission;
+use pallet_spaces::{Module as Spaces, rpc::{FlatSpace, SpaceIdOrHandle}};
 use pallet_utils::{bool_to_option, PostId, rpc::{FlatContent, FlatWhoAndWhen, ShouldSkip}, SpaceId};
 
-use crate::{Module, Post, PostExtension, FIRST_POST_ID, Config};
+use crate::{ContentProof, ContentProofsByPostId, Module, Post, PostById, PostExtension, FIRST_POST_ID, Config};
 pub type RepliesByPostId<AccountId, BlockNumber> = BTreeMap<PostId, Vec<FlatPost<AccountId, BlockNumber>>>;
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
@@ -73,6 +78,12 @@ pub struct FlatPost<AccountId, BlockNumber> {
     #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
     pub is_hidden: Option<bool>,
 
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub is_author_deactivated: Option<bool>,
+
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub is_original_changed: Option<bool>,
+
     #[cfg_attr(feature = "std", serde(flatten))]
     pub extension: FlatPostExtension,
 
@@ -85,6 +96,47 @@ pub struct FlatPost<AccountId, BlockNumber> {
     pub downvotes_count: u16,
 }
 
+/// A space with everything from other pallets that is required to render it for a viewer.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatSpaceView<AccountId, BlockNumber> {
+    pub space: FlatSpace<AccountId, BlockNumber>,
+
+    /// A number of blocks of inactivity after which posts of this space are locked for comments.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub comments_lock_after: Option<BlockNumber>,
+
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub pinned_post: Option<FlatPost<AccountId, BlockNumber>>,
+
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub is_viewer_owner: Option<bool>,
+
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub is_viewer_follower: Option<bool>,
+
+    /// Permissions that a viewer has in this space, either via built-in roles or via custom ones.
+    /// Empty if there is no viewer.
+    pub viewer_permissions: Vec<SpacePermission>,
+}
+
+/// A proof that a post had a given content at a given block.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatContentProof<BlockNumber> {
+    pub post_id: PostId,
+    pub content: FlatContent,
+    /// A block at which the post got this content.
+    pub block: BlockNumber,
+    pub parent_hash: Vec<u8>,
+    pub time: u64,
+    /// A storage key of the post content proofs, so that a light client
+    /// can request a storage proof of them at `block` and verify it.
+    pub storage_key: Vec<u8>,
+}
+
 #[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum FlatPostKind {
@@ -111,6 +163,9 @@ impl<T: Config> From<Post<T>> for FlatPost<T::AccountId, T::BlockNumber> {
             hidden_replies_count, shares_count, upvotes_count, downvotes_count, ..
         } = from;
 
+        let is_author_deactivated = !T::IsAccountActive::is_active_account(&owner);
+        let is_original_changed = Module::<T>::is_original_changed_since_shared(id, &extension);
+
         Self {
             id,
             who_and_when: (created, updated).into(),
@@ -118,6 +173,8 @@ impl<T: Config> From<Post<T>> for FlatPost<T::AccountId, T::BlockNumber> {
             space_id,
             content: content.into(),
             is_hidden: bool_to_option(hidden),
+            is_author_deactivated: bool_to_option(is_author_deactivated),
+            is_original_changed: bool_to_option(is_original_changed),
             extension: extension.into(),
             replies_count,
             hidden_replies_count,
@@ -299,6 +356,39 @@ impl<T: Config> Module<T> {
         Self::next_post_id()
     }
 
+    /// Get the ids of posts created by an account between `from_block` and `to_block` (inclusive),
+    /// in ascending order. Only eras in which the account created posts are read.
+    pub fn get_post_ids_by_account_in_block_range(
+        account: T::AccountId,
+        from_block: T::BlockNumber,
+        to_block: T::BlockNumber,
+        limit: u16,
+    ) -> Vec<PostId> {
+        let mut post_ids = Vec::new();
+        let first_era = Self::posts_era_start(from_block);
+
+        let eras = Self::post_eras_by_account(&account)
+            .into_iter()
+            .filter(|era_start| *era_start >= first_era && *era_start <= to_block);
+
+        for era_start in eras {
+            for post_id in Self::post_ids_by_account_in_era(&account, era_start) {
+                if post_ids.len() >= limit as usize {
+                    return post_ids;
+                }
+
+                let is_in_range = Self::post_by_id(post_id)
+                    .map_or(false, |post| post.created.block >= from_block && post.created.block <= to_block);
+
+                if is_in_range {
+                    post_ids.push(post_id);
+                }
+            }
+        }
+
+        post_ids
+    }
+
     pub fn get_feed(account: T::AccountId, offset: u64, limit: u16) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
         let mut post_ids: Vec<PostId> = SpaceFollows::<T>::spaces_followed_by_account(account)
             .iter()
@@ -310,4 +400,67 @@ impl<T: Config> Module<T> {
 
         Self::get_posts_by_ids_with_filter(post_ids, offset, limit, |post| post.is_public() && !post.is_comment())
     }
+
+    pub fn get_space_view(
+        space_id_or_handle: SpaceIdOrHandle,
+        viewer: Option<T::AccountId>,
+    ) -> Option<FlatSpaceView<T::AccountId, T::BlockNumber>> {
+        let space_id = Spaces::<T>::get_space_id_by_id_or_handle(space_id_or_handle)?;
+        let space = Spaces::<T>::space_by_id(space_id)?;
+
+        let pinned_post = Self::pinned_post_id_by_space_id(space_id)
+            .and_then(Self::post_by_id)
+            .map(|post| post.into());
+
+        let mut view = FlatSpaceView {
+            space: Default::default(),
+            comments_lock_after: Self::comments_lock_after_by_space_id(space_id),
+            pinned_post,
+            is_viewer_owner: None,
+            is_viewer_follower: None,
+            viewer_permissions: Vec::new(),
+        };
+
+        if let Some(viewer) = viewer {
+            view.is_viewer_owner = bool_to_option(space.is_owner(&viewer));
+            view.is_viewer_follower = bool_to_option(space.is_follower(&viewer));
+            view.viewer_permissions = SpacePermission::all()
+                .into_iter()
+                .filter(|permission| Spaces::<T>::ensure_account_has_space_permission(
+                    viewer.clone(),
+                    &space,
+                    permission.clone(),
+                    DispatchError::Other("NoSpacePermission"),
+                ).is_ok())
+                .collect();
+        }
+
+        view.space = space.into();
+        Some(view)
+    }
+
+    /// Get a proof of the content that a given post had at a given block.
+    /// Returns `None` if the post did not exist at that block,
+    /// or a proof for that block is older than the latest `MaxContentProofsPerPost` proofs.
+    pub fn get_content_proof(post_id: PostId, at_block: T::BlockNumber) -> Option<FlatContentProof<T::BlockNumber>> {
+        let ContentProof { content, block, parent_hash, time } = Self::content_proofs_by_post_id(post_id)
+            .into_iter()
+            .rev()
+            .find(|proof| proof.block <= at_block)?;
+
+        Some(FlatContentProof {
+            post_id,
+            content: content.into(),
+            block,
+            parent_hash: parent_hash.as_ref().to_vec(),
+            time: time.saturated_into::<u64>(),
+            storage_key: ContentProofsByPostId::<T>::hashed_key_for(post_id),
+        })
+    }
+
+    /// Get a storage key of a post by its id, so that a light client
+    /// can request a storage proof of this post and verify it.
+    pub fn get_post_storage_key(post_id: PostId) -> Vec<u8> {
+        PostById::<T>::hashed_key_for(post_id)
+    }
 }
@@ -0,0 +1,166 @@
+use frame_support::traits::IsSubType;
+use sp_runtime::{
+    traits::{DispatchInfoOf, SignedExtension, Saturating},
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+};
+
+use super::*;
+
+/// A spam score added for every post (or comment) an account created within a recent period.
+pub const SPAM_SCORE_PER_RECENT_POST: u32 = 1;
+/// A spam score added for every moderation flag (upheld report) against an account.
+pub const SPAM_SCORE_PER_MODERATION_FLAG: u32 = 10;
+/// A spam score added if an account is younger than a recent period or has no profile.
+pub const SPAM_SCORE_FOR_NEW_ACCOUNT: u32 = 20;
+
+impl<T: Config> Module<T> {
+
+    /// Get the number of posts created by an account within the current recent period.
+    pub fn recent_posts_count(account: &T::AccountId, now: T::BlockNumber) -> u16 {
+        let (period_start, count) = Self::recent_posts_by_account(account);
+        if now.saturating_sub(period_start) < T::RecentPostsPeriod::get() {
+            count
+        } else {
+            0
+        }
+    }
+
+    pub(crate) fn note_recent_post(account: &T::AccountId) {
+        let now = <system::Pallet<T>>::block_number();
+        let period = T::RecentPostsPeriod::get();
+
+        RecentPostsByAccount::<T>::mutate(account, |(period_start, count)| {
+            if now.saturating_sub(*period_start) < period {
+                *count = count.saturating_add(1);
+            } else {
+                *period_start = now;
+                *count = 1;
+            }
+        });
+    }
+
+    /// Compute a cheap spam score of an account based on the number of its recent posts,
+    /// moderation flags against it and its age.
+    pub fn spam_score(account: &T::AccountId) -> u32 {
+        let now = <system::Pallet<T>>::block_number();
+
+        let recent_posts = Self::recent_posts_count(account, now) as u32;
+        let moderation_flags = T::AccountModerationFlags::moderation_flags_count(account.clone());
+        let is_new_account = T::AccountCreatedAt::account_created_at(account.clone())
+            .map_or(true, |created_at| now.saturating_sub(created_at) < T::RecentPostsPeriod::get());
+
+        let mut score = recent_posts.saturating_mul(SPAM_SCORE_PER_RECENT_POST)
+            .saturating_add(moderation_flags.saturating_mul(SPAM_SCORE_PER_MODERATION_FLAG));
+
+        if is_new_account {
+            score = score.saturating_add(SPAM_SCORE_FOR_NEW_ACCOUNT);
+        }
+
+        score
+    }
+
+    /// Ensure that a spam score of an account has not reached `MaxSpamScore`.
+    /// It is checked when a post is created or updated, whatever call led to it.
+    pub fn ensure_spam_score_below_max(account: &T::AccountId) -> DispatchResult {
+        ensure!(Self::spam_score(account) < T::MaxSpamScore::get(), Error::<T>::SpamScoreTooHigh);
+        Ok(())
+    }
+}
+
+/// Validate social-content calls (creating and updating posts) against a spam score of a signer.
+///
+/// Transactions of accounts that reached `MaxSpamScore` are rejected. Other transactions get
+/// a priority bonus that decreases as a spam score grows, so likely spammers are included last.
+///
+/// This only filters the transaction pool early: the same limit is enforced at dispatch time
+/// by `ensure_spam_score_below_max`, which also covers posts created by other calls or pallets.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckSpamScore<T: Config + Send + Sync>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckSpamScore<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckSpamScore")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> CheckSpamScore<T>
+    where
+        <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+    /// Create new `SignedExtension` to check a spam score of a signer.
+    pub fn new() -> Self {
+        Self(sp_std::marker::PhantomData)
+    }
+
+    fn is_social_content_call(call: &<T as frame_system::Config>::Call) -> bool {
+        matches!(
+            call.is_sub_type(),
+            Some(Call::create_post(..)) |
+            Some(Call::create_post_with_options(..)) |
+            Some(Call::create_scheduled_post(..)) |
+            Some(Call::update_post(..)) |
+            Some(Call::import_post(..))
+        )
+    }
+}
+
+#[repr(u8)]
+enum SpamScoreValidityError {
+    /// The signer's spam score reached the max allowed one.
+    SpamScoreTooHigh = 0,
+}
+
+impl From<SpamScoreValidityError> for u8 {
+    fn from(err: SpamScoreValidityError) -> Self {
+        err as u8
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckSpamScore<T>
+    where
+        <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    const IDENTIFIER: &'static str = "CheckSpamScore";
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if !Self::is_social_content_call(call) {
+            return Ok(ValidTransaction::default());
+        }
+
+        let max_score = T::MaxSpamScore::get();
+        let score = Module::<T>::spam_score(who);
+
+        if score >= max_score {
+            return Err(InvalidTransaction::Custom(SpamScoreValidityError::SpamScoreTooHigh.into()).into());
+        }
+
+        Ok(ValidTransaction {
+            priority: max_score.saturating_sub(score) as TransactionPriority,
+            ..Default::default()
+        })
+    }
+}
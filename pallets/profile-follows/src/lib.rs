@@ -3,16 +3,23 @@
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
-    traits::Get
+    storage::{with_transaction, TransactionOutcome},
+    traits::Get,
+    weights::Weight,
 };
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use pallet_profiles::{Module as Profiles, SocialAccountById};
+use df_traits::IsAccountActive;
+use pallet_profiles::{Module as Profiles, SocialAccount, SocialAccountById};
 use pallet_utils::remove_from_vec;
 
 pub mod rpc;
 
+/// An extra weight of following or unfollowing every account in a batch
+/// in its own storage transaction.
+pub const WEIGHT_PER_BATCH_TRANSACTION: Weight = 10_000;
+
 /// The pallet's configuration trait.
 pub trait Config: system::Config
     + pallet_utils::Config
@@ -23,7 +30,15 @@ pub trait Config: system::Config
 
     type BeforeAccountFollowed: BeforeAccountFollowed<Self>;
 
+    type AfterAccountFollowed: AfterAccountFollowed<Self>;
+
     type BeforeAccountUnfollowed: BeforeAccountUnfollowed<Self>;
+
+    /// Deactivated accounts are not allowed to follow others and to be followed.
+    type IsAccountActive: IsAccountActive<Self::AccountId>;
+
+    /// Max number of accounts that can be followed or unfollowed in one batch.
+    type MaxAccountsPerBatch: Get<u16>;
 }
 
 // This pallet's storage items.
@@ -46,6 +61,8 @@ decl_event!(
     {
         AccountFollowed(/* follower */ AccountId, /* following */ AccountId),
         AccountUnfollowed(/* follower */ AccountId, /* unfollowing */ AccountId),
+        AccountsFollowed(/* follower */ AccountId, /* followed */ Vec<AccountId>, /* failed */ Vec<AccountId>),
+        AccountsUnfollowed(/* follower */ AccountId, /* unfollowed */ Vec<AccountId>, /* failed */ Vec<AccountId>),
     }
 );
 
@@ -65,72 +82,211 @@ decl_error! {
         AlreadyAccountFollower,
         /// Account (Alice) is not a follower of another account (Bob).
         NotAccountFollower,
+        /// Not allowed to follow a deactivated account.
+        CannotFollowDeactivatedAccount,
+
+        /// No accounts provided in a batch.
+        NoAccountsInBatch,
+        /// Too many accounts provided in a batch.
+        TooManyAccountsInBatch,
     }
 }
 
 decl_module! {
   pub struct Module<T: Config> for enum Call where origin: T::Origin {
 
+    const MaxAccountsPerBatch: u16 = T::MaxAccountsPerBatch::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + Module::<T>::follow_account_weight()]
     pub fn follow_account(origin, account: T::AccountId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
-      ensure!(follower != account, Error::<T>::AccountCannotFollowItself);
-      ensure!(!<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())),
-        Error::<T>::AlreadyAccountFollower);
-
       let mut follower_account = Profiles::get_or_new_social_account(follower.clone());
-      let mut followed_account = Profiles::get_or_new_social_account(account.clone());
-
-      follower_account.inc_following_accounts();
-      followed_account.inc_followers();
-
-      T::BeforeAccountFollowed::before_account_followed(
-        follower.clone(), follower_account.reputation, account.clone())?;
 
+      Self::try_follow_account(&follower, &mut follower_account, &account)?;
       <SocialAccountById<T>>::insert(follower.clone(), follower_account);
-      <SocialAccountById<T>>::insert(account.clone(), followed_account);
-      <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(account.clone()));
-      <AccountFollowers<T>>::mutate(account.clone(), |ids| ids.push(follower.clone()));
-      <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
 
       Self::deposit_event(RawEvent::AccountFollowed(follower, account));
       Ok(())
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + Module::<T>::unfollow_account_weight()]
     pub fn unfollow_account(origin, account: T::AccountId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
-      ensure!(follower != account, Error::<T>::AccountCannotUnfollowItself);
-      ensure!(<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())), Error::<T>::NotAccountFollower);
-
       let mut follower_account = Profiles::social_account_by_id(follower.clone()).ok_or(Error::<T>::FollowerAccountNotFound)?;
-      let mut followed_account = Profiles::social_account_by_id(account.clone()).ok_or(Error::<T>::FollowedAccountNotFound)?;
-
-      follower_account.dec_following_accounts();
-      followed_account.dec_followers();
-
-      T::BeforeAccountUnfollowed::before_account_unfollowed(follower.clone(), account.clone())?;
 
+      Self::try_unfollow_account(&follower, &mut follower_account, &account)?;
       <SocialAccountById<T>>::insert(follower.clone(), follower_account);
-      <SocialAccountById<T>>::insert(account.clone(), followed_account);
-      <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |account_ids| remove_from_vec(account_ids, account.clone()));
-      <AccountFollowers<T>>::mutate(account.clone(), |account_ids| remove_from_vec(account_ids, follower.clone()));
-      <AccountFollowedByAccount<T>>::remove((follower.clone(), account.clone()));
 
       Self::deposit_event(RawEvent::AccountUnfollowed(follower, account));
       Ok(())
     }
+
+    /// Follow a few accounts at once, e.g. recommended accounts during onboarding.
+    /// Accounts that cannot be followed are skipped and reported in `AccountsFollowed` event.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + (accounts.len() as Weight).saturating_mul(
+      WEIGHT_PER_BATCH_TRANSACTION + Module::<T>::follow_account_weight()
+    )]
+    pub fn follow_accounts(origin, accounts: Vec<T::AccountId>) -> DispatchResult {
+      let follower = ensure_signed(origin)?;
+
+      Self::ensure_valid_batch(&accounts)?;
+
+      let mut follower_account = Profiles::get_or_new_social_account(follower.clone());
+      let mut followed = Vec::new();
+      let mut failed = Vec::new();
+
+      for account in accounts {
+        let mut account_after_follow = follower_account.clone();
+
+        let result = with_transaction(|| {
+          match Self::try_follow_account(&follower, &mut account_after_follow, &account) {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(err) => TransactionOutcome::Rollback(Err(err)),
+          }
+        });
+
+        if result.is_ok() {
+          follower_account = account_after_follow;
+          followed.push(account);
+        } else {
+          failed.push(account);
+        }
+      }
+
+      if !followed.is_empty() {
+        <SocialAccountById<T>>::insert(follower.clone(), follower_account);
+      }
+
+      Self::deposit_event(RawEvent::AccountsFollowed(follower, followed, failed));
+      Ok(())
+    }
+
+    /// Unfollow a few accounts at once.
+    /// Accounts that cannot be unfollowed are skipped and reported in `AccountsUnfollowed` event.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + (accounts.len() as Weight).saturating_mul(
+      WEIGHT_PER_BATCH_TRANSACTION + Module::<T>::unfollow_account_weight()
+    )]
+    pub fn unfollow_accounts(origin, accounts: Vec<T::AccountId>) -> DispatchResult {
+      let follower = ensure_signed(origin)?;
+
+      Self::ensure_valid_batch(&accounts)?;
+
+      let mut follower_account = Profiles::social_account_by_id(follower.clone()).ok_or(Error::<T>::FollowerAccountNotFound)?;
+      let mut unfollowed = Vec::new();
+      let mut failed = Vec::new();
+
+      for account in accounts {
+        let mut account_after_unfollow = follower_account.clone();
+
+        let result = with_transaction(|| {
+          match Self::try_unfollow_account(&follower, &mut account_after_unfollow, &account) {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(err) => TransactionOutcome::Rollback(Err(err)),
+          }
+        });
+
+        if result.is_ok() {
+          follower_account = account_after_unfollow;
+          unfollowed.push(account);
+        } else {
+          failed.push(account);
+        }
+      }
+
+      if !unfollowed.is_empty() {
+        <SocialAccountById<T>>::insert(follower.clone(), follower_account);
+      }
+
+      Self::deposit_event(RawEvent::AccountsUnfollowed(follower, unfollowed, failed));
+      Ok(())
+    }
   }
 }
 
+impl<T: Config> Module<T> {
+    /// A weight of `try_follow_account`: checks that both accounts are active,
+    /// follow storages and counters, and badges awarded by `AfterAccountFollowed` hook.
+    pub fn follow_account_weight() -> Weight {
+        T::DbWeight::get().reads_writes(10, 6)
+    }
+
+    /// A weight of `try_unfollow_account`.
+    pub fn unfollow_account_weight() -> Weight {
+        T::DbWeight::get().reads_writes(4, 4)
+    }
+
+    fn ensure_valid_batch(accounts: &[T::AccountId]) -> DispatchResult {
+        ensure!(!accounts.is_empty(), Error::<T>::NoAccountsInBatch);
+        ensure!(accounts.len() <= T::MaxAccountsPerBatch::get() as usize, Error::<T>::TooManyAccountsInBatch);
+        Ok(())
+    }
+
+    /// Update follow storages and counters, except of the follower's social account,
+    /// that should be saved by a caller.
+    fn try_follow_account(
+        follower: &T::AccountId,
+        follower_account: &mut SocialAccount<T>,
+        account: &T::AccountId,
+    ) -> DispatchResult {
+        ensure!(follower != account, Error::<T>::AccountCannotFollowItself);
+        ensure!(!<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())),
+            Error::<T>::AlreadyAccountFollower);
+
+        T::IsAccountActive::ensure_account_is_active(follower)?;
+        ensure!(T::IsAccountActive::is_active_account(account), Error::<T>::CannotFollowDeactivatedAccount);
+
+        let mut followed_account = Profiles::get_or_new_social_account(account.clone());
+
+        follower_account.inc_following_accounts();
+        followed_account.inc_followers();
+
+        T::BeforeAccountFollowed::before_account_followed(
+            follower.clone(), follower_account.reputation, account.clone())?;
+
+        <SocialAccountById<T>>::insert(account.clone(), followed_account);
+        <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(account.clone()));
+        <AccountFollowers<T>>::mutate(account.clone(), |ids| ids.push(follower.clone()));
+        <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
+
+        T::AfterAccountFollowed::after_account_followed(follower, account);
+
+        Ok(())
+    }
+
+    /// Update follow storages and counters, except of the follower's social account,
+    /// that should be saved by a caller.
+    fn try_unfollow_account(
+        follower: &T::AccountId,
+        follower_account: &mut SocialAccount<T>,
+        account: &T::AccountId,
+    ) -> DispatchResult {
+        ensure!(follower != account, Error::<T>::AccountCannotUnfollowItself);
+        ensure!(<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())), Error::<T>::NotAccountFollower);
+
+        let mut followed_account = Profiles::social_account_by_id(account.clone()).ok_or(Error::<T>::FollowedAccountNotFound)?;
+
+        follower_account.dec_following_accounts();
+        followed_account.dec_followers();
+
+        T::BeforeAccountUnfollowed::before_account_unfollowed(follower.clone(), account.clone())?;
+
+        <SocialAccountById<T>>::insert(account.clone(), followed_account);
+        <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |account_ids| remove_from_vec(account_ids, account.clone()));
+        <AccountFollowers<T>>::mutate(account.clone(), |account_ids| remove_from_vec(account_ids, follower.clone()));
+        <AccountFollowedByAccount<T>>::remove((follower.clone(), account.clone()));
+
+        Ok(())
+    }
+}
+
 /// Handler that will be called right before the account is followed.
 pub trait BeforeAccountFollowed<T: Config> {
     fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult;
@@ -142,6 +298,15 @@ impl<T: Config> BeforeAccountFollowed<T> for () {
     }
 }
 
+/// Handler that will be called right after the account is followed.
+pub trait AfterAccountFollowed<T: Config> {
+    fn after_account_followed(follower: &T::AccountId, following: &T::AccountId);
+}
+
+impl<T: Config> AfterAccountFollowed<T> for () {
+    fn after_account_followed(_follower: &T::AccountId, _following: &T::AccountId) {}
+}
+
 /// Handler that will be called right before the account is unfollowed.
 pub trait BeforeAccountUnfollowed<T: Config> {
     fn before_account_unfollowed(follower: T::AccountId, following: T::AccountId) -> DispatchResult;
@@ -13,5 +13,7 @@ sp_api::decl_runtime_apis! {
         fn get_social_accounts_by_ids(
             account_ids: Vec<AccountId>
         ) -> Vec<FlatSocialAccount<AccountId, BlockNumber>>;
+
+        fn get_social_account_storage_key(account: AccountId) -> Vec<u8>;
     }
 }
@@ -5,6 +5,7 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
+use sp_core::Bytes;
 
 use pallet_profiles::rpc::FlatSocialAccount;
 use pallet_utils::rpc::map_rpc_error;
@@ -18,6 +19,13 @@ pub trait ProfilesApi<BlockHash, AccountId, BlockNumber> {
         at: Option<BlockHash>,
         account_ids: Vec<AccountId>,
     ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "profiles_getSocialAccountStorageKey")]
+    fn get_social_account_storage_key(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+    ) -> Result<Bytes>;
 }
 
 pub struct Profiles<C, M> {
@@ -50,4 +58,12 @@ where
         let runtime_api_result = api.get_social_accounts_by_ids(&at, account_ids);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_social_account_storage_key(&self, at: Option<<Block as BlockT>::Hash>, account: AccountId) -> Result<Bytes> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_social_account_storage_key(&at, account);
+        runtime_api_result.map(Bytes).map_err(map_rpc_error)
+    }
 }
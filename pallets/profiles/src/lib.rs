@@ -5,13 +5,15 @@ use scale_info::TypeInfo;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
-    traits::Get
+    traits::Get,
+    Parameter,
 };
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, traits::{Hash, IdentifyAccount, Saturating, Verify}};
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use pallet_utils::{Module as Utils, WhoAndWhen, Content};
+use df_traits::{AccountCreatedAt, ContentDefaultsProvider, IsAccountActive, MutedThreadsProvider};
+use pallet_utils::{Module as Utils, Error as UtilsError, WhoAndWhen, Content, ContentDefaults, ContentKind, PostId};
 
 pub mod rpc;
 
@@ -38,6 +40,41 @@ pub struct ProfileUpdate {
     pub content: Option<Content>,
 }
 
+/// Profile preferences of an account that can be moved to another account.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ProfileSettings {
+    pub content_kind: Option<ContentKind>,
+    pub avatar: Option<Content>,
+    pub banner: Option<Content>,
+    pub content_defaults: Option<ContentDefaults>,
+    /// Threads (root posts) muted by an account.
+    pub muted_thread_ids: Vec<PostId>,
+}
+
+/// Profile settings exported by one account to be imported by another account.
+/// A bundle should be signed by `exported_by` to be imported.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ProfileSettingsBundle<AccountId, BlockNumber> {
+    pub exported_by: AccountId,
+    /// The only account that can import this bundle.
+    pub import_to: AccountId,
+    /// A block at which settings were exported. It makes a digest of every export unique,
+    /// so that the same settings can be exported and imported again later.
+    pub exported_at: BlockNumber,
+    pub settings: ProfileSettings,
+}
+
+/// A badge that is awarded to an account automatically when it reaches an on-chain milestone.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum Badge {
+    /// An account created its first post or comment.
+    FirstPost,
+    /// An account reached `FollowersBadgeThreshold` followers.
+    FollowersMilestone,
+    /// A profile of an account is at least `AccountAgeBadgeThreshold` blocks old.
+    AccountAnniversary,
+}
+
 /// The pallet's configuration trait.
 pub trait Config: system::Config
     + pallet_utils::Config
@@ -46,6 +83,20 @@ pub trait Config: system::Config
     type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
 
     type AfterProfileUpdated: AfterProfileUpdated<Self>;
+
+    /// The number of followers an account should have to get a `FollowersMilestone` badge.
+    type FollowersBadgeThreshold: Get<u32>;
+
+    /// The age of a profile (in blocks) after which an account gets an `AccountAnniversary` badge.
+    type AccountAgeBadgeThreshold: Get<Self::BlockNumber>;
+
+    /// A signature of a digest of an exported profile settings bundle.
+    type Signature: Verify<Signer = Self::Signer> + Parameter;
+
+    type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+    /// Provides muted threads of an account to be exported and imported with its settings.
+    type MutedThreads: MutedThreadsProvider<Self::AccountId>;
 }
 
 // This pallet's storage items.
@@ -53,15 +104,58 @@ decl_storage! {
     trait Store for Module<T: Config> as ProfilesModule {
         pub SocialAccountById get(fn social_account_by_id):
             map hasher(blake2_128_concat) T::AccountId => Option<SocialAccount<T>>;
+
+        /// A content kind hint of a profile of a given account.
+        pub ProfileContentKindByAccount get(fn profile_content_kind_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Option<ContentKind>;
+
+        /// A block number at which a given account was deactivated by its owner.
+        pub AccountDeactivatedAt get(fn account_deactivated_at):
+            map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+        /// Badges awarded to a given account. Every badge can be awarded only once,
+        /// so the number of badges per account is bounded by the number of `Badge` variants.
+        pub BadgesByAccount get(fn badges_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Vec<Badge>;
+
+        /// An avatar of a profile of a given account.
+        pub ProfileAvatarByAccount get(fn profile_avatar_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Option<Content>;
+
+        /// A banner of a profile of a given account.
+        pub ProfileBannerByAccount get(fn profile_banner_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Option<Content>;
+
+        /// Defaults (license, language, visibility) used when an account creates content
+        /// without specifying these fields.
+        pub ContentDefaultsByAccount get(fn content_defaults_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Option<ContentDefaults>;
+
+        /// Whether a profile settings bundle with a given digest has already been imported.
+        pub SettingsBundleImported get(fn is_settings_bundle_imported):
+            map hasher(blake2_128_concat) T::Hash => bool;
     }
 }
 
 decl_event!(
     pub enum Event<T> where
         <T as system::Config>::AccountId,
+        <T as system::Config>::Hash,
+        ProfileSettingsBundle = ProfileSettingsBundle<
+            <T as system::Config>::AccountId,
+            <T as system::Config>::BlockNumber,
+        >,
     {
         ProfileCreated(AccountId),
         ProfileUpdated(AccountId),
+        AccountDeactivated(AccountId),
+        AccountReactivated(AccountId),
+        BadgeAwarded(AccountId, Badge),
+        ContentDefaultsUpdated(AccountId),
+        /// A bundle of profile settings and its digest that should be signed by the exporter.
+        ProfileSettingsExported(AccountId, ProfileSettingsBundle, Hash),
+        /// An account (0) imported profile settings exported by another account (1).
+        ProfileSettingsImported(AccountId, AccountId, Hash),
     }
 );
 
@@ -75,12 +169,26 @@ decl_error! {
         NoUpdatesForProfile,
         /// Account has no profile yet.
         AccountHasNoProfile,
+        /// Account is already deactivated.
+        AccountAlreadyDeactivated,
+        /// Account is not deactivated.
+        AccountIsNotDeactivated,
+        /// Profile settings bundle is exported to another account.
+        NotSettingsBundleRecipient,
+        /// Profile settings bundle is not signed by the account that exported it.
+        InvalidSettingsBundleSignature,
+        /// Profile settings bundle has already been imported.
+        SettingsBundleAlreadyImported,
     }
 }
 
 decl_module! {
   pub struct Module<T: Config> for enum Call where origin: T::Origin {
 
+    const FollowersBadgeThreshold: u32 = T::FollowersBadgeThreshold::get();
+
+    const AccountAgeBadgeThreshold: T::BlockNumber = T::AccountAgeBadgeThreshold::get();
+
     // Initializing errors
     type Error = Error<T>;
 
@@ -90,23 +198,7 @@ decl_module! {
     #[weight = 100_000 + T::DbWeight::get().reads_writes(1, 2)]
     pub fn create_profile(origin, content: Content) -> DispatchResult {
       let owner = ensure_signed(origin)?;
-
-      Utils::<T>::is_valid_content(content.clone())?;
-
-      let mut social_account = Self::get_or_new_social_account(owner.clone());
-      ensure!(social_account.profile.is_none(), Error::<T>::ProfileAlreadyCreated);
-
-      social_account.profile = Some(
-        Profile {
-          created: WhoAndWhen::<T>::new(owner.clone()),
-          updated: None,
-          content
-        }
-      );
-      <SocialAccountById<T>>::insert(owner.clone(), social_account);
-
-      Self::deposit_event(RawEvent::ProfileCreated(owner));
-      Ok(())
+      Self::do_create_profile(owner, content)
     }
 
     #[weight = 100_000 + T::DbWeight::get().reads_writes(1, 2)]
@@ -117,6 +209,8 @@ decl_module! {
 
       ensure!(has_updates, Error::<T>::NoUpdatesForProfile);
 
+      Self::ensure_account_is_active(&owner)?;
+
       let mut social_account = Self::social_account_by_id(owner.clone()).ok_or(Error::<T>::SocialAccountNotFound)?;
       let mut profile = social_account.profile.ok_or(Error::<T>::AccountHasNoProfile)?;
       let mut is_update_applied = false;
@@ -137,11 +231,196 @@ decl_module! {
 
         <SocialAccountById<T>>::insert(owner.clone(), social_account);
         T::AfterProfileUpdated::after_profile_updated(owner.clone(), &profile, old_data);
+        Self::award_account_age_badge(&owner);
 
         Self::deposit_event(RawEvent::ProfileUpdated(owner));
       }
       Ok(())
     }
+
+    /// Set or remove (if `None`) a content kind hint of the caller's profile.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn update_profile_content_kind(origin, content_kind_opt: Option<ContentKind>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::ensure_account_is_active(&owner)?;
+
+      let social_account = Self::social_account_by_id(&owner).ok_or(Error::<T>::SocialAccountNotFound)?;
+      let profile = social_account.profile.ok_or(Error::<T>::AccountHasNoProfile)?;
+
+      ensure!(
+        Self::profile_content_kind_by_account(&owner) != content_kind_opt,
+        Error::<T>::NoUpdatesForProfile
+      );
+
+      if let Some(content_kind) = content_kind_opt {
+        Utils::<T>::ensure_content_is_some(&profile.content)?;
+        ProfileContentKindByAccount::<T>::insert(&owner, content_kind);
+      } else {
+        ProfileContentKindByAccount::<T>::remove(&owner);
+      }
+
+      Self::deposit_event(RawEvent::ProfileUpdated(owner));
+      Ok(())
+    }
+
+    /// Set or remove (if `None`) an avatar of the caller's profile.
+    #[weight = 25_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn update_profile_avatar(origin, avatar_opt: Option<Content>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::ensure_profile_image_can_be_updated(&owner, Self::profile_avatar_by_account(&owner), &avatar_opt)?;
+
+      if let Some(avatar) = avatar_opt {
+        ProfileAvatarByAccount::<T>::insert(&owner, avatar);
+      } else {
+        ProfileAvatarByAccount::<T>::remove(&owner);
+      }
+
+      Self::deposit_event(RawEvent::ProfileUpdated(owner));
+      Ok(())
+    }
+
+    /// Set or remove (if `None`) a banner of the caller's profile.
+    #[weight = 25_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn update_profile_banner(origin, banner_opt: Option<Content>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::ensure_profile_image_can_be_updated(&owner, Self::profile_banner_by_account(&owner), &banner_opt)?;
+
+      if let Some(banner) = banner_opt {
+        ProfileBannerByAccount::<T>::insert(&owner, banner);
+      } else {
+        ProfileBannerByAccount::<T>::remove(&owner);
+      }
+
+      Self::deposit_event(RawEvent::ProfileUpdated(owner));
+      Ok(())
+    }
+
+    /// Set or remove (if `None`) content defaults of the caller's account.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn update_content_defaults(origin, defaults_opt: Option<ContentDefaults>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::ensure_account_is_active(&owner)?;
+
+      ensure!(
+        Self::content_defaults_by_account(&owner) != defaults_opt,
+        Error::<T>::NoUpdatesForProfile
+      );
+
+      if let Some(defaults) = defaults_opt {
+        Utils::<T>::ensure_license_and_language_are_valid(&defaults.license, &defaults.language)?;
+        ContentDefaultsByAccount::<T>::insert(&owner, defaults);
+      } else {
+        ContentDefaultsByAccount::<T>::remove(&owner);
+      }
+
+      Self::deposit_event(RawEvent::ContentDefaultsUpdated(owner));
+      Ok(())
+    }
+
+    /// Export profile settings of the caller's account, so that they can be imported
+    /// by `import_to`. Only a `ProfileSettingsExported` event is emitted: the exported digest
+    /// should be signed by the caller off-chain and passed to `import_profile_settings`.
+    #[weight = 25_000 + T::DbWeight::get().reads(6)]
+    pub fn export_profile_settings(origin, import_to: T::AccountId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      let bundle = ProfileSettingsBundle {
+        exported_by: owner.clone(),
+        import_to,
+        exported_at: <system::Pallet<T>>::block_number(),
+        settings: Self::profile_settings(&owner),
+      };
+      let digest = T::Hashing::hash_of(&bundle);
+
+      Self::deposit_event(RawEvent::ProfileSettingsExported(owner, bundle, digest));
+      Ok(())
+    }
+
+    /// Import profile settings signed by an account that exported them.
+    /// Settings from a bundle replace all of the current settings of the caller's account.
+    #[weight = {
+      let muted_threads_count = bundle.settings.muted_thread_ids.len() as u64;
+      100_000 + T::DbWeight::get().reads_writes(5 + muted_threads_count, 6 + muted_threads_count.saturating_mul(2))
+    }]
+    pub fn import_profile_settings(
+      origin,
+      bundle: ProfileSettingsBundle<T::AccountId, T::BlockNumber>,
+      signature: T::Signature
+    ) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(bundle.import_to == owner, Error::<T>::NotSettingsBundleRecipient);
+
+      let digest = T::Hashing::hash_of(&bundle);
+      ensure!(
+        signature.verify(digest.as_ref(), &bundle.exported_by),
+        Error::<T>::InvalidSettingsBundleSignature
+      );
+      ensure!(!Self::is_settings_bundle_imported(digest), Error::<T>::SettingsBundleAlreadyImported);
+
+      Self::ensure_account_is_active(&owner)?;
+
+      let social_account = Self::social_account_by_id(&owner).ok_or(Error::<T>::SocialAccountNotFound)?;
+      let profile = social_account.profile.ok_or(Error::<T>::AccountHasNoProfile)?;
+
+      let ProfileSettings { content_kind, avatar, banner, content_defaults, muted_thread_ids } = bundle.settings;
+
+      if content_kind.is_some() {
+        Utils::<T>::ensure_content_is_some(&profile.content)?;
+      }
+
+      for image in avatar.iter().chain(banner.iter()) {
+        Utils::<T>::ensure_content_is_some(image)?;
+        Utils::<T>::is_valid_content(image.clone())?;
+      }
+
+      if let Some(defaults) = &content_defaults {
+        Utils::<T>::ensure_license_and_language_are_valid(&defaults.license, &defaults.language)?;
+      }
+
+      ProfileContentKindByAccount::<T>::mutate_exists(&owner, |value| *value = content_kind);
+      ProfileAvatarByAccount::<T>::mutate_exists(&owner, |value| *value = avatar);
+      ProfileBannerByAccount::<T>::mutate_exists(&owner, |value| *value = banner);
+      ContentDefaultsByAccount::<T>::mutate_exists(&owner, |value| *value = content_defaults);
+      T::MutedThreads::set_muted_thread_ids(&owner, muted_thread_ids)?;
+      SettingsBundleImported::<T>::insert(digest, true);
+
+      Self::deposit_event(RawEvent::ProfileSettingsImported(owner, bundle.exported_by, digest));
+      Ok(())
+    }
+
+    /// Deactivate the caller's account without deleting any of its data:
+    /// the profile is hidden, the account cannot be followed and cannot create new content
+    /// until it is reactivated.
+    #[weight = 25_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn deactivate_account(origin) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(Self::social_account_by_id(&owner).is_some(), Error::<T>::SocialAccountNotFound);
+      ensure!(!Self::is_deactivated(&owner), Error::<T>::AccountAlreadyDeactivated);
+
+      AccountDeactivatedAt::<T>::insert(&owner, <system::Pallet<T>>::block_number());
+
+      Self::deposit_event(RawEvent::AccountDeactivated(owner));
+      Ok(())
+    }
+
+    /// Reactivate the caller's account that was deactivated before.
+    #[weight = 25_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn reactivate_account(origin) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(Self::is_deactivated(&owner), Error::<T>::AccountIsNotDeactivated);
+
+      AccountDeactivatedAt::<T>::remove(&owner);
+
+      Self::deposit_event(RawEvent::AccountReactivated(owner));
+      Ok(())
+    }
   }
 }
 
@@ -202,6 +481,144 @@ impl<T: Config> Module<T> {
             }
         )
     }
+
+    pub fn is_deactivated(account: &T::AccountId) -> bool {
+        AccountDeactivatedAt::<T>::contains_key(account)
+    }
+
+    pub fn ensure_account_is_active(account: &T::AccountId) -> DispatchResult {
+        ensure!(!Self::is_deactivated(account), UtilsError::<T>::AccountIsDeactivated);
+        Ok(())
+    }
+
+    pub fn do_create_profile(owner: T::AccountId, content: Content) -> DispatchResult {
+        Self::ensure_account_is_active(&owner)?;
+        Utils::<T>::is_valid_content(content.clone())?;
+
+        let mut social_account = Self::get_or_new_social_account(owner.clone());
+        ensure!(social_account.profile.is_none(), Error::<T>::ProfileAlreadyCreated);
+
+        social_account.profile = Some(
+            Profile {
+                created: WhoAndWhen::<T>::new(owner.clone()),
+                updated: None,
+                content
+            }
+        );
+        <SocialAccountById<T>>::insert(owner.clone(), social_account);
+
+        Self::deposit_event(RawEvent::ProfileCreated(owner));
+        Ok(())
+    }
+
+    /// Ensure that an account is active, has a profile, and a new image (if any)
+    /// differs from the current one and is a valid non-empty content.
+    fn ensure_profile_image_can_be_updated(
+        owner: &T::AccountId,
+        current_image: Option<Content>,
+        new_image: &Option<Content>,
+    ) -> DispatchResult {
+        Self::ensure_account_is_active(owner)?;
+
+        let social_account = Self::social_account_by_id(owner).ok_or(Error::<T>::SocialAccountNotFound)?;
+        ensure!(social_account.profile.is_some(), Error::<T>::AccountHasNoProfile);
+        ensure!(current_image != *new_image, Error::<T>::NoUpdatesForProfile);
+
+        if let Some(image) = new_image {
+            Utils::<T>::ensure_content_is_some(image)?;
+            Utils::<T>::is_valid_content(image.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Get the profile settings of an account that can be moved to another account.
+    pub fn profile_settings(account: &T::AccountId) -> ProfileSettings {
+        ProfileSettings {
+            content_kind: Self::profile_content_kind_by_account(account),
+            avatar: Self::profile_avatar_by_account(account),
+            banner: Self::profile_banner_by_account(account),
+            content_defaults: Self::content_defaults_by_account(account),
+            muted_thread_ids: T::MutedThreads::muted_thread_ids(account),
+        }
+    }
+
+    pub fn has_badge(account: &T::AccountId, badge: Badge) -> bool {
+        Self::badges_by_account(account).contains(&badge)
+    }
+
+    /// Award an account with a badge, if it has not been awarded with this badge yet.
+    /// Returns `true` if a badge was awarded.
+    pub fn award_badge(account: &T::AccountId, badge: Badge) -> bool {
+        let mut badges = Self::badges_by_account(account);
+        if badges.contains(&badge) {
+            return false;
+        }
+
+        badges.push(badge);
+        BadgesByAccount::<T>::insert(account, badges);
+
+        Self::deposit_event(RawEvent::BadgeAwarded(account.clone(), badge));
+        true
+    }
+
+    /// Award an account with a `FollowersMilestone` badge if it has enough followers.
+    pub fn award_followers_badge(account: &T::AccountId) {
+        let followers_count = Self::social_account_by_id(account)
+            .map_or(0, |social_account| social_account.followers_count);
+
+        if followers_count >= T::FollowersBadgeThreshold::get() {
+            Self::award_badge(account, Badge::FollowersMilestone);
+        }
+    }
+
+    /// Award an account with an `AccountAnniversary` badge if its profile is old enough.
+    pub fn award_account_age_badge(account: &T::AccountId) {
+        let now = <system::Pallet<T>>::block_number();
+        let is_old_enough = Self::account_created_at(account.clone())
+            .map_or(false, |created_at| now.saturating_sub(created_at) >= T::AccountAgeBadgeThreshold::get());
+
+        if is_old_enough {
+            Self::award_badge(account, Badge::AccountAnniversary);
+        }
+    }
+
+    /// Award badges for a post (or comment) created by an account.
+    /// It is called by a post hook configured in a runtime.
+    pub fn on_post_created(account: &T::AccountId) {
+        Self::award_badge(account, Badge::FirstPost);
+        Self::award_account_age_badge(account);
+    }
+
+    /// Award badges after `follower` followed `following`.
+    /// It is called by a follow hook configured in a runtime.
+    pub fn on_account_followed(follower: &T::AccountId, following: &T::AccountId) {
+        Self::award_followers_badge(following);
+        Self::award_account_age_badge(follower);
+    }
+}
+
+impl<T: Config> IsAccountActive<T::AccountId> for Module<T> {
+    fn is_active_account(account: &T::AccountId) -> bool {
+        !Self::is_deactivated(account)
+    }
+
+    fn ensure_account_is_active(account: &T::AccountId) -> DispatchResult {
+        Self::ensure_account_is_active(account)
+    }
+}
+
+impl<T: Config> ContentDefaultsProvider<T::AccountId> for Module<T> {
+    fn content_defaults(account: &T::AccountId) -> Option<ContentDefaults> {
+        Self::content_defaults_by_account(account)
+    }
+}
+
+impl<T: Config> AccountCreatedAt<T::AccountId, T::BlockNumber> for Module<T> {
+    fn account_created_at(account: T::AccountId) -> Option<T::BlockNumber> {
+        Self::social_account_by_id(account)
+            .and_then(|social_account| social_account.profile)
+            .map(|profile| profile.created.block)
+    }
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(10)]
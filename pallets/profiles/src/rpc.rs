@@ -3,11 +3,13 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 use sp_std::prelude::*;
 
+use frame_support::storage::StorageMap;
+
 use pallet_utils::rpc::{FlatContent, FlatWhoAndWhen};
 
 use frame_system::Pallet as SystemPallet;
 
-use crate::{Module, Profile, SocialAccount, Config};
+use crate::{Module, Profile, SocialAccount, SocialAccountById, Config};
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -69,6 +71,9 @@ impl<T: Config> Module<T> {
                            .map(|social_account| social_account.into())
                            .map(|mut flat_social_account: FlatSocialAccount<T::AccountId, T::BlockNumber>| {
                                flat_social_account.id = account.clone();
+                               if Self::is_deactivated(account) {
+                                   flat_social_account.profile = None;
+                               }
                                flat_social_account
                            })
                    })
@@ -78,4 +83,10 @@ impl<T: Config> Module<T> {
     pub fn get_account_data(account: T::AccountId) -> T::AccountData {
         SystemPallet::<T>::account(&account).data
     }
+
+    /// Get a storage key of a social account (profile and follow counters), so that
+    /// a light client can request a storage proof of it and verify it.
+    pub fn get_social_account_storage_key(account: T::AccountId) -> Vec<u8> {
+        SocialAccountById::<T>::hashed_key_for(account)
+    }
 }
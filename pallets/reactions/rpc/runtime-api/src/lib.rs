@@ -7,7 +7,7 @@ use sp_std::collections::btree_map::BTreeMap;
 use pallet_reactions::{
     ReactionId,
     ReactionKind,
-    rpc::FlatReaction,
+    rpc::{FlatPostView, FlatReaction},
 };
 use pallet_utils::PostId;
 
@@ -28,5 +28,7 @@ sp_api::decl_runtime_apis! {
             post_ids: Vec<PostId>,
             reactor: AccountId,
         ) -> BTreeMap<PostId, ReactionKind>;
+
+        fn get_post_view(post_id: PostId, viewer: Option<AccountId>) -> Option<FlatPostView<AccountId, BlockNumber>>;
     }
 }
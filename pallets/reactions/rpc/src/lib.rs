@@ -6,7 +6,7 @@ use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
 
-use pallet_reactions::{ReactionId, ReactionKind, rpc::FlatReaction};
+use pallet_reactions::{ReactionId, ReactionKind, rpc::{FlatPostView, FlatReaction}};
 use pallet_utils::{PostId, rpc::map_rpc_error};
 pub use reactions_runtime_api::ReactionsApi as ReactionsRuntimeApi;
 
@@ -35,6 +35,14 @@ pub trait ReactionsApi<BlockHash, AccountId, BlockNumber> {
         post_ids: Vec<PostId>,
         reactor: AccountId,
     ) -> Result<BTreeMap<PostId, ReactionKind>>;
+
+    #[rpc(name = "reactions_getPostView")]
+    fn get_post_view(
+        &self,
+        at: Option<BlockHash>,
+        post_id: PostId,
+        viewer: Option<AccountId>,
+    ) -> Result<Option<FlatPostView<AccountId, BlockNumber>>>;
 }
 
 pub struct Reactions<C, M> {
@@ -98,4 +106,17 @@ where
         let runtime_api_result = api.get_reaction_kinds_by_post_ids_and_reactor(&at, post_ids, reactor);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_post_view(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        post_id: PostId,
+        viewer: Option<AccountId>,
+    ) -> Result<Option<FlatPostView<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_post_view(&at, post_id, viewer);
+        runtime_api_result.map_err(map_rpc_error)
+    }
 }
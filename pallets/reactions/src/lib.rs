@@ -14,7 +14,7 @@ use serde::Deserialize;
 use sp_runtime::{RuntimeDebug, DispatchError};
 use sp_std::prelude::*;
 
-use df_traits::moderation::IsAccountBlocked;
+use df_traits::{IsAccountActive, moderation::IsAccountBlocked};
 use pallet_permissions::SpacePermission;
 use pallet_posts::{Module as Posts, PostById};
 use pallet_spaces::Module as Spaces;
@@ -59,6 +59,28 @@ pub trait Config: system::Config
 {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    /// Hooks that let other pallets (reputation, trending, etc.) react to post reactions.
+    type ReactionHooks: ReactionHooks<Self>;
+}
+
+/// Hooks called by this pallet after a reaction on a post (or comment) has been saved.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait ReactionHooks<T: Config> {
+    /// Called after `reactor` reacted on a post with `kind`.
+    fn on_reaction_created(_reactor: &T::AccountId, _post_id: PostId, _reaction_id: ReactionId, _kind: ReactionKind) {}
+
+    /// Called after `reactor` changed a reaction on a post from `old_kind` to `new_kind`.
+    fn on_reaction_updated(
+        _reactor: &T::AccountId,
+        _post_id: PostId,
+        _reaction_id: ReactionId,
+        _old_kind: ReactionKind,
+        _new_kind: ReactionKind,
+    ) {}
+
+    /// Called after `reactor` deleted a reaction of `kind` on a post.
+    fn on_reaction_deleted(_reactor: &T::AccountId, _post_id: PostId, _reaction_id: ReactionId, _kind: ReactionKind) {}
 }
 
 pub const FIRST_REACTION_ID: u64 = 1;
@@ -125,122 +147,43 @@ decl_module! {
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(6, 5)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(7, 7)]
     pub fn create_post_reaction(origin, post_id: PostId, kind: ReactionKind) -> DispatchResult {
       let owner = ensure_signed(origin)?;
-
-      let post = &mut Posts::require_post(post_id)?;
-      ensure!(
-        !<PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::AccountAlreadyReacted
-      );
-
-      let space = post.get_space()?;
-      ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
-      ensure!(Posts::<T>::is_root_post_visible(post_id)?, Error::<T>::CannotReactWhenPostHidden);
-
-      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
-
-      match kind {
-        ReactionKind::Upvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Upvote,
-            Error::<T>::NoPermissionToUpvote.into()
-          )?;
-          post.inc_upvotes();
-        },
-        ReactionKind::Downvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Downvote,
-            Error::<T>::NoPermissionToDownvote.into()
-          )?;
-          post.inc_downvotes();
-        }
-      }
-
-      <PostById<T>>::insert(post_id, post.clone());
-      let reaction_id = Self::insert_new_reaction(owner.clone(), kind);
-      ReactionIdsByPostId::mutate(post.id, |ids| ids.push(reaction_id));
-      <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
-
-      Self::deposit_event(RawEvent::PostReactionCreated(owner, post_id, reaction_id, kind));
-      Ok(())
+      Self::do_create_post_reaction(owner, post_id, kind)
     }
 
     #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
     pub fn update_post_reaction(origin, post_id: PostId, reaction_id: ReactionId, new_kind: ReactionKind) -> DispatchResult {
       let owner = ensure_signed(origin)?;
-
-      ensure!(
-        <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::ReactionByAccountNotFound
-      );
-
-      let mut reaction = Self::require_reaction(reaction_id)?;
-      let post = &mut Posts::require_post(post_id)?;
-
-      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
-      ensure!(reaction.kind != new_kind, Error::<T>::SameReaction);
-
-      if let Some(space_id) = post.try_get_space_id() {
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
-      }
-
-      reaction.kind = new_kind;
-      reaction.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
-
-      match new_kind {
-        ReactionKind::Upvote => {
-          post.inc_upvotes();
-          post.dec_downvotes();
-        },
-        ReactionKind::Downvote => {
-          post.inc_downvotes();
-          post.dec_upvotes();
-        },
-      }
-
-      <ReactionById<T>>::insert(reaction_id, reaction);
-      <PostById<T>>::insert(post_id, post);
-
-      Self::deposit_event(RawEvent::PostReactionUpdated(owner, post_id, reaction_id, new_kind));
-      Ok(())
+      Self::do_update_post_reaction(owner, post_id, reaction_id, new_kind)
     }
 
     #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
     pub fn delete_post_reaction(origin, post_id: PostId, reaction_id: ReactionId) -> DispatchResult {
       let owner = ensure_signed(origin)?;
+      Self::do_delete_post_reaction(owner, post_id, reaction_id)
+    }
 
-      ensure!(
-        <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::ReactionByAccountNotFound
-      );
-
-      // TODO extract Self::require_reaction(reaction_id)?;
-      let reaction = Self::require_reaction(reaction_id)?;
-      let post = &mut Posts::require_post(post_id)?;
-
-      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
-      if let Some(space_id) = post.try_get_space_id() {
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
-      }
+    /// Create a reaction of a given `kind` if the caller has not reacted on a post yet,
+    /// update the reaction if its kind differs, or delete the reaction if its kind is the same.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(8, 7)]
+    pub fn toggle_post_reaction(origin, post_id: PostId, kind: ReactionKind) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
 
-      match reaction.kind {
-        ReactionKind::Upvote => post.dec_upvotes(),
-        ReactionKind::Downvote => post.dec_downvotes(),
+      let reaction_key = (owner.clone(), post_id);
+      if !<PostReactionIdByAccount<T>>::contains_key(&reaction_key) {
+        return Self::do_create_post_reaction(owner, post_id, kind);
       }
 
-      <PostById<T>>::insert(post_id, post.clone());
-      <ReactionById<T>>::remove(reaction_id);
-      ReactionIdsByPostId::mutate(post.id, |ids| remove_from_vec(ids, reaction_id));
-      <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
+      let reaction_id = Self::post_reaction_id_by_account(&reaction_key);
+      let reaction = Self::require_reaction(reaction_id)?;
 
-      Self::deposit_event(RawEvent::PostReactionDeleted(owner, post_id, reaction_id, reaction.kind));
-      Ok(())
+      if reaction.kind == kind {
+        Self::do_delete_post_reaction(owner, post_id, reaction_id)
+      } else {
+        Self::do_update_post_reaction(owner, post_id, reaction_id, kind)
+      }
     }
   }
 }
@@ -262,6 +205,131 @@ impl<T: Config> Module<T> {
         id
     }
 
+    pub fn do_create_post_reaction(owner: T::AccountId, post_id: PostId, kind: ReactionKind) -> DispatchResult {
+        T::IsAccountActive::ensure_account_is_active(&owner)?;
+
+        let post = &mut Posts::require_post(post_id)?;
+        ensure!(
+            !<PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
+            Error::<T>::AccountAlreadyReacted
+        );
+
+        let space = post.get_space()?;
+        ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+        ensure!(Posts::<T>::is_root_post_visible(post_id)?, Error::<T>::CannotReactWhenPostHidden);
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+        match kind {
+            ReactionKind::Upvote => {
+                Spaces::ensure_account_has_space_permission(
+                    owner.clone(),
+                    &post.get_space()?,
+                    SpacePermission::Upvote,
+                    Error::<T>::NoPermissionToUpvote.into()
+                )?;
+                post.inc_upvotes();
+            },
+            ReactionKind::Downvote => {
+                Spaces::ensure_account_has_space_permission(
+                    owner.clone(),
+                    &post.get_space()?,
+                    SpacePermission::Downvote,
+                    Error::<T>::NoPermissionToDownvote.into()
+                )?;
+                post.inc_downvotes();
+            }
+        }
+
+        <PostById<T>>::insert(post_id, post.clone());
+        let reaction_id = Self::insert_new_reaction(owner.clone(), kind);
+        ReactionIdsByPostId::mutate(post.id, |ids| ids.push(reaction_id));
+        <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
+
+        T::ReactionHooks::on_reaction_created(&owner, post_id, reaction_id, kind);
+
+        Self::deposit_event(RawEvent::PostReactionCreated(owner, post_id, reaction_id, kind));
+        Ok(())
+    }
+
+    pub fn do_update_post_reaction(
+        owner: T::AccountId,
+        post_id: PostId,
+        reaction_id: ReactionId,
+        new_kind: ReactionKind,
+    ) -> DispatchResult {
+        T::IsAccountActive::ensure_account_is_active(&owner)?;
+
+        ensure!(
+            <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
+            Error::<T>::ReactionByAccountNotFound
+        );
+
+        let mut reaction = Self::require_reaction(reaction_id)?;
+        let post = &mut Posts::require_post(post_id)?;
+
+        ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
+        ensure!(reaction.kind != new_kind, Error::<T>::SameReaction);
+
+        if let Some(space_id) = post.try_get_space_id() {
+            ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
+        }
+
+        let old_kind = reaction.kind;
+        reaction.kind = new_kind;
+        reaction.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+
+        match new_kind {
+            ReactionKind::Upvote => {
+                post.inc_upvotes();
+                post.dec_downvotes();
+            },
+            ReactionKind::Downvote => {
+                post.inc_downvotes();
+                post.dec_upvotes();
+            },
+        }
+
+        <ReactionById<T>>::insert(reaction_id, reaction);
+        <PostById<T>>::insert(post_id, post);
+
+        T::ReactionHooks::on_reaction_updated(&owner, post_id, reaction_id, old_kind, new_kind);
+
+        Self::deposit_event(RawEvent::PostReactionUpdated(owner, post_id, reaction_id, new_kind));
+        Ok(())
+    }
+
+    pub fn do_delete_post_reaction(owner: T::AccountId, post_id: PostId, reaction_id: ReactionId) -> DispatchResult {
+        ensure!(
+            <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
+            Error::<T>::ReactionByAccountNotFound
+        );
+
+        // TODO extract Self::require_reaction(reaction_id)?;
+        let reaction = Self::require_reaction(reaction_id)?;
+        let post = &mut Posts::require_post(post_id)?;
+
+        ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
+        if let Some(space_id) = post.try_get_space_id() {
+            ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
+        }
+
+        match reaction.kind {
+            ReactionKind::Upvote => post.dec_upvotes(),
+            ReactionKind::Downvote => post.dec_downvotes(),
+        }
+
+        <PostById<T>>::insert(post_id, post.clone());
+        <ReactionById<T>>::remove(reaction_id);
+        ReactionIdsByPostId::mutate(post.id, |ids| remove_from_vec(ids, reaction_id));
+        <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
+
+        T::ReactionHooks::on_reaction_deleted(&owner, post_id, reaction_id, reaction.kind);
+
+        Self::deposit_event(RawEvent::PostReactionDeleted(owner, post_id, reaction_id, reaction.kind));
+        Ok(())
+    }
+
     /// Get `Reaction` by id from the storage or return `ReactionNotFound` error.
     pub fn require_reaction(reaction_id: ReactionId) -> Result<Reaction<T>, DispatchError> {
         Ok(Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?)
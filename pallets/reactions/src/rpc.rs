@@ -5,7 +5,11 @@ use sp_runtime::traits::Zero;
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::prelude::*;
 
-use pallet_utils::{PostId, rpc::FlatWhoAndWhen};
+use pallet_posts::{Module as Posts, rpc::FlatPost};
+use pallet_profiles::{Module as Profiles, rpc::FlatProfile};
+#[cfg(feature = "std")]
+use pallet_spaces::rpc::bytes_to_string;
+use pallet_utils::{PostId, rpc::{FlatWhoAndWhen, ShouldSkip}};
 
 use crate::{Module, Reaction, ReactionId, ReactionKind, Config};
 
@@ -19,6 +23,25 @@ pub struct FlatReaction<AccountId, BlockNumber> {
     pub kind: ReactionKind,
 }
 
+/// A post with everything from other pallets that is required to render it.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatPostView<AccountId, BlockNumber> {
+    pub post: FlatPost<AccountId, BlockNumber>,
+
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip", serialize_with = "bytes_to_string"))]
+    pub space_handle: Option<Vec<u8>>,
+
+    /// `None` if a post author has no profile or their account is deactivated.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub author_profile: Option<FlatProfile<AccountId, BlockNumber>>,
+
+    /// A reaction of a viewer on this post, if any.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub viewer_reaction: Option<ReactionKind>,
+}
+
 #[cfg(feature = "std")]
 impl Serialize for ReactionKind {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
@@ -95,4 +118,32 @@ impl<T: Config> Module<T> {
 
         res.clone().collect()
     }
-}
\ No newline at end of file
+
+    pub fn get_post_view(
+        post_id: PostId,
+        viewer: Option<T::AccountId>,
+    ) -> Option<FlatPostView<T::AccountId, T::BlockNumber>> {
+        let post = Posts::<T>::post_by_id(post_id)?;
+
+        let space_handle = post.try_get_space().and_then(|space| space.handle);
+
+        let author_profile = Some(&post.owner)
+            .filter(|author| !Profiles::<T>::is_deactivated(author))
+            .and_then(|author| Profiles::<T>::social_account_by_id(author))
+            .and_then(|social_account| social_account.profile)
+            .map(|profile| profile.into());
+
+        let viewer_reaction = viewer
+            .map(|viewer| Self::post_reaction_id_by_account((viewer, post_id)))
+            .filter(|reaction_id| *reaction_id != 0)
+            .and_then(|reaction_id| Self::reaction_by_id(reaction_id))
+            .map(|reaction| reaction.kind);
+
+        Some(FlatPostView {
+            post: post.into(),
+            space_handle,
+            author_profile,
+            viewer_reaction,
+        })
+    }
+}
@@ -4,6 +4,7 @@ use codec::Codec;
 use sp_std::vec::Vec;
 use pallet_utils::SpaceId;
 use pallet_permissions::SpacePermission;
+use pallet_roles::RoleId;
 
 sp_api::decl_runtime_apis! {
     pub trait RolesApi<AccountId> where
@@ -14,5 +15,7 @@ sp_api::decl_runtime_apis! {
         fn get_accounts_with_any_role_in_space(space_id: SpaceId) -> Vec<AccountId>;
 
         fn get_space_ids_for_account_with_any_role(account_id: AccountId) -> Vec<SpaceId>;
+
+        fn get_role_ids_by_space_permission(space_id: SpaceId, permission: SpacePermission) -> Vec<RoleId>;
     }
 }
@@ -7,6 +7,7 @@ use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
 use pallet_utils::{SpaceId, rpc::map_rpc_error};
 use pallet_permissions::SpacePermission;
+use pallet_roles::RoleId;
 
 pub use roles_runtime_api::RolesApi as RolesRuntimeApi;
 
@@ -33,6 +34,14 @@ pub trait RolesApi<BlockHash, AccountId> {
         at: Option<BlockHash>,
         account_id: AccountId
     ) -> Result<Vec<SpaceId>>;
+
+    #[rpc(name = "roles_getRoleIdsBySpacePermission")]
+    fn get_role_ids_by_space_permission(
+        &self,
+        at: Option<BlockHash>,
+        space_id: SpaceId,
+        permission: SpacePermission
+    ) -> Result<Vec<RoleId>>;
 }
 
 pub struct Roles<C, M> {
@@ -93,4 +102,17 @@ where
         let runtime_api_result = api.get_space_ids_for_account_with_any_role(&at, account_id);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_role_ids_by_space_permission(
+        &self, at:
+        Option<<Block as BlockT>::Hash>,
+        space_id: SpaceId,
+        permission: SpacePermission
+    ) -> Result<Vec<RoleId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_role_ids_by_space_permission(&at, space_id, permission);
+        runtime_api_result.map_err(map_rpc_error)
+    }
 }
@@ -1,7 +1,8 @@
 use super::*;
 
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, weights::Weight};
 use pallet_permissions::SpacePermissionsContext;
+use pallet_utils::{DeferredJob, DeferredJobHandler, DeferredJobProgress, remove_from_vec};
 
 impl<T: Config> Module<T> {
 
@@ -17,12 +18,45 @@ impl<T: Config> Module<T> {
       Ok(Self::role_by_id(role_id).ok_or(Error::<T>::RoleNotFound)?)
   }
 
-  /// Ensure that this account is not blocked and has 'ManageRoles' permission in a given space
+  /// Ensure that the list of users to grant or revoke a role is not empty
+  /// and does not exceed `MaxUsersPerRoleChange`.
+  pub(crate) fn ensure_valid_users_batch(users: &[User<T::AccountId>]) -> DispatchResult {
+    ensure!(!users.is_empty(), Error::<T>::NoUsersProvided);
+    ensure!(
+      users.len() <= T::MaxUsersPerRoleChange::get() as usize,
+      Error::<T>::TooManyUsersToChangeRole
+    );
+    Ok(())
+  }
+
+  /// Ensure that the sets of roles and users to grant or revoke are not empty,
+  /// and that the number of role changes does not exceed `MaxUsersPerRoleChange`.
+  /// Sets are checked, so that a duplicated role or user counts only once.
+  pub(crate) fn ensure_valid_roles_batch(
+    role_ids: &BTreeSet<RoleId>,
+    users: &BTreeSet<User<T::AccountId>>,
+  ) -> DispatchResult {
+    ensure!(!role_ids.is_empty(), Error::<T>::NoRolesProvided);
+    ensure!(!users.is_empty(), Error::<T>::NoUsersProvided);
+    ensure!(
+      users.len() <= T::MaxUsersPerRoleChange::get() as usize,
+      Error::<T>::TooManyUsersToChangeRole
+    );
+    ensure!(
+      role_ids.len().saturating_mul(users.len()) <= T::MaxUsersPerRoleChange::get() as usize,
+      Error::<T>::TooManyRoleChanges
+    );
+    Ok(())
+  }
+
+  /// Ensure that this account is not blocked and has 'ManageRoles' permission in a given space,
+  /// and that the space is not frozen for a pending ownership transfer.
   pub fn ensure_role_manager(account: T::AccountId, space_id: SpaceId) -> DispatchResult {
     ensure!(
       T::IsAccountBlocked::is_allowed_account(account.clone(), space_id),
       UtilsError::<T>::AccountIsBlocked
     );
+    ensure!(!T::Spaces::get_space(space_id)?.frozen, Error::<T>::SpaceIsFrozen);
     Self::ensure_user_has_space_permission_with_load_space(
       User::Account(account),
       space_id,
@@ -165,6 +199,39 @@ impl<T: Config> Role<T> {
     Ok(())
   }
 
+  /// Add this role to `RoleIdsBySpacePermission` index for each of the given permissions.
+  pub fn index_permissions<'a>(&self, permissions: impl Iterator<Item = &'a SpacePermission>) {
+    for permission in permissions {
+      RoleIdsBySpacePermission::mutate(self.space_id, permission, |role_ids| {
+        if !role_ids.contains(&self.id) {
+          role_ids.push(self.id);
+        }
+      });
+    }
+  }
+
+  /// Remove this role from `RoleIdsBySpacePermission` index for each of the given permissions.
+  pub fn unindex_permissions<'a>(&self, permissions: impl Iterator<Item = &'a SpacePermission>) {
+    for permission in permissions {
+      RoleIdsBySpacePermission::mutate(self.space_id, permission, |role_ids| {
+        if let Some(role_idx) = role_ids.iter().position(|x| *x == self.id) {
+          role_ids.swap_remove(role_idx);
+        }
+      });
+    }
+  }
+
+  pub fn grant_to_users(&self, users: &BTreeSet<User<T::AccountId>>) {
+    for user in users.iter() {
+      if !Module::<T>::users_by_role_id(self.id).contains(user) {
+        <UsersByRoleId<T>>::mutate(self.id, |users| { users.push(user.clone()); });
+      }
+      if !Module::<T>::role_ids_by_user_in_space(user.clone(), self.space_id).contains(&self.id) {
+        <RoleIdsByUserInSpace<T>>::mutate(user.clone(), self.space_id, |roles| { roles.push(self.id); })
+      }
+    }
+  }
+
   pub fn revoke_from_users(&self, users: Vec<User<T::AccountId>>) {
     let mut users_by_role = <UsersByRoleId<T>>::take(self.id);
 
@@ -203,4 +270,51 @@ impl<T: Config> PermissionChecker for Module<T> {
       error
     )
   }
+
+  fn has_space_roles(space_id: SpaceId) -> bool {
+    RoleIdsBySpaceId::decode_len(space_id).unwrap_or_default() > 0
+  }
+}
+
+impl<T: Config> DeferredJobHandler for Module<T> {
+  /// Revoke a deleted role from the next chunk of its remaining users.
+  fn process_job(job: &DeferredJob, weight_limit: Weight) -> Option<DeferredJobProgress> {
+    if job.owner != DELETED_ROLE_JOB_OWNER {
+      return None;
+    }
+
+    let (role_id, space_id) = match <(RoleId, SpaceId)>::decode(&mut &job.data[..]) {
+      Ok(data) => data,
+      Err(_) => return Some(DeferredJobProgress { consumed_weight: 0, remaining_data: None }),
+    };
+
+    let db_weight = T::DbWeight::get();
+    let user_weight = db_weight.reads_writes(1, 1);
+    let mut consumed_weight = db_weight.reads_writes(1, 1);
+
+    let mut users = Self::users_by_role_id(role_id);
+    let mut revoked_count = 0;
+
+    for user in users.iter() {
+      if consumed_weight.saturating_add(user_weight) > weight_limit {
+        break;
+      }
+      consumed_weight = consumed_weight.saturating_add(user_weight);
+      revoked_count += 1;
+
+      <RoleIdsByUserInSpace<T>>::mutate(user, space_id, |role_ids| remove_from_vec(role_ids, role_id));
+    }
+
+    users.drain(..revoked_count);
+
+    let remaining_data = if users.is_empty() {
+      <UsersByRoleId<T>>::remove(role_id);
+      None
+    } else {
+      <UsersByRoleId<T>>::insert(role_id, users);
+      Some(job.data.clone())
+    };
+
+    Some(DeferredJobProgress { consumed_weight, remaining_data })
+  }
 }
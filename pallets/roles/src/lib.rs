@@ -14,7 +14,7 @@ use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    ensure,
+    ensure, transactional,
     traits::Get,
     dispatch::DispatchResult
 };
@@ -27,9 +27,10 @@ use df_traits::{
     moderation::{IsAccountBlocked, IsContentBlocked},
 };
 use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissionSet};
-use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, User, WhoAndWhen, Content};
+use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, User, WhoAndWhen, Content, DeferredJobOwner};
 
 pub mod functions;
+pub mod migrations;
 pub mod rpc;
 
 #[cfg(test)]
@@ -88,10 +89,18 @@ pub trait Config: system::Config
     type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
 
     /// When deleting a role via `delete_role()` dispatch, this parameter is checked.
-    /// If the number of users that own a given role is greater or equal to this number,
-    /// then `TooManyUsersToDeleteRole` error will be returned and the dispatch will fail.
+    /// If the number of users that own a given role is greater than this number,
+    /// then the role is revoked from them later, in a deferred job processed in `on_idle`.
     type MaxUsersToProcessPerDeleteRole: Get<u16>;
 
+    /// Max number of users that a role can be granted to or revoked from
+    /// in a single `grant_role()` or `revoke_role()` dispatch.
+    type MaxUsersPerRoleChange: Get<u16>;
+
+    /// Max number of roles that can be created in a space.
+    /// It also bounds the number of roles indexed by a space permission.
+    type MaxRolesPerSpace: Get<u16>;
+
     type Spaces: SpaceForRolesProvider<AccountId=Self::AccountId>;
 
     type SpaceFollows: SpaceFollowsProvider<AccountId=Self::AccountId>;
@@ -135,20 +144,37 @@ decl_error! {
         /// A role must be granted/revoked to/from at least one user.
         NoUsersProvided,
 
-        /// Canot remove a role from this many users in a single transaction.
-        /// See `MaxUsersToProcessPerDeleteRole` parameter of this trait.
-        TooManyUsersToDeleteRole,
+
+        /// Cannot grant or revoke a role to/from this many users in a single transaction.
+        /// See `MaxUsersPerRoleChange` parameter of this trait.
+        TooManyUsersToChangeRole,
+
+        /// No roles provided when trying to grant or revoke them in a batch.
+        NoRolesProvided,
+
+        /// Cannot grant or revoke this many roles in a single transaction: the number of roles
+        /// multiplied by the number of users should not exceed `MaxUsersPerRoleChange`.
+        TooManyRoleChanges,
 
         /// Cannot disable a role that is already disabled.
         RoleAlreadyDisabled,
 
         /// Cannot enable a role that is already enabled.
         RoleAlreadyEnabled,
+
+        /// Space has reached the max number of roles.
+        TooManyRolesInSpace,
+
+        /// Roles cannot be changed while a space is frozen for a pending ownership transfer.
+        SpaceIsFrozen,
     }
 }
 
 pub const FIRST_ROLE_ID: u64 = 1;
 
+/// An owner of deferred jobs that revoke deleted roles from their remaining users.
+pub const DELETED_ROLE_JOB_OWNER: DeferredJobOwner = *b"roles___";
+
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Config> as PermissionsModule {
@@ -174,6 +200,18 @@ decl_storage! {
             hasher(blake2_128_concat) User<T::AccountId>,
             hasher(twox_64_concat) SpaceId
             => Vec<RoleId>;
+
+        /// Get a list of role ids in a given space that contain a given permission.
+        pub RoleIdsBySpacePermission get(fn role_ids_by_space_permission): double_map
+            hasher(twox_64_concat) SpaceId,
+            hasher(blake2_128_concat) SpacePermission
+            => Vec<RoleId>;
+
+        /// Whether `RoleIdsBySpacePermission` index is built for roles created before it was added.
+        RoleIdsBySpacePermissionIndexed: bool;
+
+        /// The next role id to add to `RoleIdsBySpacePermission` index, while it's being built.
+        NextRoleIdToIndex: RoleId = FIRST_ROLE_ID;
     }
 }
 
@@ -183,12 +221,25 @@ decl_module! {
 
     const MaxUsersToProcessPerDeleteRole: u16 = T::MaxUsersToProcessPerDeleteRole::get();
 
+    const MaxUsersPerRoleChange: u16 = T::MaxUsersPerRoleChange::get();
+
+    const MaxRolesPerSpace: u16 = T::MaxRolesPerSpace::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
+    // The index is built in chunks, so that it never exceeds the block weight.
+    fn on_idle(_now: T::BlockNumber, remaining_weight: frame_support::weights::Weight) -> frame_support::weights::Weight {
+      if RoleIdsBySpacePermissionIndexed::get() {
+        return T::DbWeight::get().reads(1);
+      }
+
+      migrations::build_role_ids_by_space_permission_index::<T>(remaining_weight)
+    }
+
     /// Create a new role, with a list of permissions, within a given space.
     ///
     /// `content` can optionally contain additional information associated with a role,
@@ -212,6 +263,9 @@ decl_module! {
 
       Self::ensure_role_manager(who.clone(), space_id)?;
 
+      let mut role_ids = Self::role_ids_by_space_id(space_id);
+      ensure!(role_ids.len() < T::MaxRolesPerSpace::get() as usize, Error::<T>::TooManyRolesInSpace);
+
       let permissions_set = permissions.into_iter().collect();
       let new_role = Role::<T>::new(who.clone(), space_id, time_to_live, content, permissions_set)?;
 
@@ -219,8 +273,10 @@ decl_module! {
       let next_role_id = new_role.id.checked_add(1).ok_or(Error::<T>::RoleIdOverflow)?;
       NextRoleId::put(next_role_id);
 
+      new_role.index_permissions(new_role.permissions.iter());
       <RoleById<T>>::insert(new_role.id, new_role.clone());
-      RoleIdsBySpaceId::mutate(space_id, |role_ids| { role_ids.push(new_role.id) });
+      role_ids.push(new_role.id);
+      RoleIdsBySpaceId::insert(space_id, role_ids);
 
       Self::deposit_event(RawEvent::RoleCreated(who, space_id, new_role.id));
       Ok(())
@@ -267,6 +323,9 @@ decl_module! {
           let permissions_diff: Vec<_> = permissions.symmetric_difference(&role.permissions).cloned().collect();
 
           if !permissions_diff.is_empty() {
+            role.unindex_permissions(role.permissions.difference(&permissions));
+            role.index_permissions(permissions.difference(&role.permissions));
+
             role.permissions = permissions;
             is_update_applied = true;
           }
@@ -284,7 +343,7 @@ decl_module! {
 
     /// Delete a given role and clean all associated storage items.
     /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
-    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(6, 5)]
+    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(7, 7)]
     pub fn delete_role(origin, role_id: RoleId) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
@@ -293,10 +352,6 @@ decl_module! {
       Self::ensure_role_manager(who.clone(), role.space_id)?;
 
       let users = Self::users_by_role_id(role_id);
-      ensure!(
-        users.len() <= T::MaxUsersToProcessPerDeleteRole::get() as usize,
-        Error::<T>::TooManyUsersToDeleteRole
-      );
 
       let role_idx_by_space_opt = Self::role_ids_by_space_id(role.space_id).iter()
         .position(|x| { *x == role_id });
@@ -305,10 +360,16 @@ decl_module! {
         RoleIdsBySpaceId::mutate(role.space_id, |n| { n.swap_remove(role_idx) });
       }
 
-      role.revoke_from_users(users);
+      if users.len() <= T::MaxUsersToProcessPerDeleteRole::get() as usize {
+        role.revoke_from_users(users);
+        <UsersByRoleId<T>>::remove(role_id);
+      } else {
+        // A deleted role is ignored in permission checks, so it can be revoked later.
+        Utils::<T>::push_deferred_job(DELETED_ROLE_JOB_OWNER, (role_id, role.space_id).encode());
+      }
 
+      role.unindex_permissions(role.permissions.iter());
       <RoleById<T>>::remove(role_id);
-      <UsersByRoleId<T>>::remove(role_id);
 
       Self::deposit_event(RawEvent::RoleDeleted(who, role_id));
       Ok(())
@@ -316,25 +377,21 @@ decl_module! {
 
     /// Grant a given role to a list of users.
     /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
-    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(4, 2)]
+    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(
+      2 + 2 * users.len() as u64,
+      2 * users.len() as u64
+    )]
     pub fn grant_role(origin, role_id: RoleId, users: Vec<User<T::AccountId>>) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
-      ensure!(!users.is_empty(), Error::<T>::NoUsersProvided);
+      Self::ensure_valid_users_batch(&users)?;
       let users_set: BTreeSet<User<T::AccountId>> = Utils::<T>::convert_users_vec_to_btree_set(users)?;
 
       let role = Self::require_role(role_id)?;
 
       Self::ensure_role_manager(who.clone(), role.space_id)?;
 
-      for user in users_set.iter() {
-        if !Self::users_by_role_id(role_id).contains(&user) {
-          <UsersByRoleId<T>>::mutate(role_id, |users| { users.push(user.clone()); });
-        }
-        if !Self::role_ids_by_user_in_space(user.clone(), role.space_id).contains(&role_id) {
-          <RoleIdsByUserInSpace<T>>::mutate(user.clone(), role.space_id, |roles| { roles.push(role_id); })
-        }
-      }
+      role.grant_to_users(&users_set);
 
       Self::deposit_event(RawEvent::RoleGranted(who, role_id, users_set.iter().cloned().collect()));
       Ok(())
@@ -342,11 +399,14 @@ decl_module! {
 
     /// Revoke a given role from a list of users.
     /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
-    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(4, 2)]
+    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(
+      2 + 2 * users.len() as u64,
+      2 * users.len() as u64
+    )]
     pub fn revoke_role(origin, role_id: RoleId, users: Vec<User<T::AccountId>>) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
-      ensure!(!users.is_empty(), Error::<T>::NoUsersProvided);
+      Self::ensure_valid_users_batch(&users)?;
 
       let role = Self::require_role(role_id)?;
 
@@ -357,5 +417,58 @@ decl_module! {
       Self::deposit_event(RawEvent::RoleRevoked(who, role_id, users));
       Ok(())
     }
+
+    /// Grant each of the given roles to a list of users. Either all roles are granted or none.
+    /// The caller should be able to manage roles in the spaces of all given roles.
+    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(
+      (role_ids.len() as u64).saturating_mul(2 + 2 * users.len() as u64),
+      (role_ids.len() as u64).saturating_mul(2 * users.len() as u64)
+    )]
+    #[transactional]
+    pub fn grant_roles(origin, role_ids: Vec<RoleId>, users: Vec<User<T::AccountId>>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let role_ids_set: BTreeSet<RoleId> = role_ids.into_iter().collect();
+      let users_set: BTreeSet<User<T::AccountId>> = Utils::<T>::convert_users_vec_to_btree_set(users)?;
+      Self::ensure_valid_roles_batch(&role_ids_set, &users_set)?;
+
+      for role_id in role_ids_set {
+        let role = Self::require_role(role_id)?;
+
+        Self::ensure_role_manager(who.clone(), role.space_id)?;
+
+        role.grant_to_users(&users_set);
+
+        Self::deposit_event(RawEvent::RoleGranted(who.clone(), role_id, users_set.iter().cloned().collect()));
+      }
+      Ok(())
+    }
+
+    /// Revoke each of the given roles from a list of users. Either all roles are revoked or none.
+    /// The caller should be able to manage roles in the spaces of all given roles.
+    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(
+      (role_ids.len() as u64).saturating_mul(2 + 2 * users.len() as u64),
+      (role_ids.len() as u64).saturating_mul(2 * users.len() as u64)
+    )]
+    #[transactional]
+    pub fn revoke_roles(origin, role_ids: Vec<RoleId>, users: Vec<User<T::AccountId>>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let role_ids_set: BTreeSet<RoleId> = role_ids.into_iter().collect();
+      let users_set: BTreeSet<User<T::AccountId>> = Utils::<T>::convert_users_vec_to_btree_set(users)?;
+      Self::ensure_valid_roles_batch(&role_ids_set, &users_set)?;
+      let users: Vec<User<T::AccountId>> = users_set.into_iter().collect();
+
+      for role_id in role_ids_set {
+        let role = Self::require_role(role_id)?;
+
+        Self::ensure_role_manager(who.clone(), role.space_id)?;
+
+        role.revoke_from_users(users.clone());
+
+        Self::deposit_event(RawEvent::RoleRevoked(who.clone(), role_id, users.clone()));
+      }
+      Ok(())
+    }
   }
 }
@@ -0,0 +1,46 @@
+use frame_support::weights::Weight;
+
+use super::*;
+
+/// Index the next roles created before `RoleIdsBySpacePermission` was added,
+/// until `remaining_weight` is exhausted. The index is marked as built after the last role.
+pub fn build_role_ids_by_space_permission_index<T: Config>(remaining_weight: Weight) -> Weight {
+    let db_weight = T::DbWeight::get();
+    // Reading the flag and both role id counters, then saving the cursor.
+    let mut consumed_weight = db_weight.reads_writes(3, 1);
+
+    if consumed_weight > remaining_weight {
+        return 0;
+    }
+
+    let next_role_id = <Module<T>>::next_role_id();
+    let mut role_id = NextRoleIdToIndex::get();
+
+    while role_id < next_role_id {
+        let role_weight = match <Module<T>>::role_by_id(role_id) {
+            Some(role) => {
+                let permissions_count = role.permissions.len() as Weight;
+                let role_weight = db_weight.reads_writes(1 + permissions_count, permissions_count);
+                if consumed_weight.saturating_add(role_weight) > remaining_weight {
+                    break;
+                }
+
+                role.index_permissions(role.permissions.iter());
+                role_weight
+            }
+            None => db_weight.reads(1),
+        };
+
+        consumed_weight = consumed_weight.saturating_add(role_weight);
+        role_id = role_id.saturating_add(1);
+    }
+
+    NextRoleIdToIndex::put(role_id);
+
+    if role_id >= next_role_id {
+        RoleIdsBySpacePermissionIndexed::put(true);
+        consumed_weight = consumed_weight.saturating_add(db_weight.writes(1));
+    }
+
+    consumed_weight
+}
@@ -14,6 +14,7 @@ use frame_support::{
     parameter_types, assert_ok,
     dispatch::{DispatchResult, DispatchError},
     traits::Everything,
+    weights::constants::RocksDbWeight,
 };
 use frame_system as system;
 
@@ -63,7 +64,7 @@ impl system::Config for Test {
     type Header = Header;
     type Event = Event;
     type BlockHashCount = BlockHashCount;
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Version = ();
     type PalletInfo = PalletInfo;
     type AccountData = pallet_balances::AccountData<u64>;
@@ -111,6 +112,7 @@ impl pallet_utils::Config for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type DeferredJobHandler = Roles;
 }
 
 use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -121,11 +123,15 @@ impl pallet_permissions::Config for Test {
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 20;
+  pub const MaxUsersPerRoleChange: u16 = 30;
+  pub const MaxRolesPerSpace: u16 = 5;
 }
 
 impl Config for Test {
     type Event = Event;
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxUsersPerRoleChange = MaxUsersPerRoleChange;
+    type MaxRolesPerSpace = MaxRolesPerSpace;
     type Spaces = Roles;
     type SpaceFollows = Roles;
     type IsAccountBlocked = ();
@@ -142,7 +148,7 @@ impl<T: Config> SpaceForRolesProvider for Module<T> {
     // Currently, we have a list of valid space id's to check
     fn get_space(id: SpaceId) -> Result<SpaceForRoles<Self::AccountId>, DispatchError> {
         if self::valid_space_ids().contains(&id) {
-            return Ok(SpaceForRoles { owner: ACCOUNT1, permissions: None })
+            return Ok(SpaceForRoles { owner: ACCOUNT1, permissions: None, frozen: false })
         }
 
         Err("SpaceNotFound".into())
@@ -331,6 +337,30 @@ pub(crate) fn _revoke_role(
     )
 }
 
+pub(crate) fn _grant_roles(
+    origin: Option<Origin>,
+    role_ids: Option<Vec<RoleId>>,
+    users: Option<Vec<User<AccountId>>>
+) -> DispatchResult {
+    Roles::grant_roles(
+        origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+        role_ids.unwrap_or_else(|| vec![ROLE1, ROLE2]),
+        users.unwrap_or_else(|| vec![User::Account(ACCOUNT2)])
+    )
+}
+
+pub(crate) fn _revoke_roles(
+    origin: Option<Origin>,
+    role_ids: Option<Vec<RoleId>>,
+    users: Option<Vec<User<AccountId>>>
+) -> DispatchResult {
+    Roles::revoke_roles(
+        origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+        role_ids.unwrap_or_else(|| vec![ROLE1, ROLE2]),
+        users.unwrap_or_else(|| vec![User::Account(ACCOUNT2)])
+    )
+}
+
 pub(crate) fn _delete_default_role() -> DispatchResult {
     _delete_role(None, None)
 }
@@ -1,4 +1,4 @@
-use crate::{Module, Config, Role, RoleIdsByUserInSpace};
+use crate::{Module, Config, Role, RoleId, RoleIdsByUserInSpace};
 
 use frame_support::storage::IterableStorageDoubleMap;
 use sp_std::prelude::*;
@@ -44,4 +44,8 @@ impl<T: Config> Module<T> {
 
         space_ids
     }
+
+    pub fn get_role_ids_by_space_permission(space_id: SpaceId, permission: SpacePermission) -> Vec<RoleId> {
+        Self::role_ids_by_space_permission(space_id, permission)
+    }
 }
\ No newline at end of file
@@ -3,6 +3,8 @@ use crate::*;
 
 use frame_support::{
     assert_ok, assert_noop,
+    traits::{Get, OnIdle},
+    weights::Weight,
 };
 use pallet_utils::{Error as UtilsError};
 
@@ -106,6 +108,17 @@ fn create_role_should_fail_with_no_permissions_provided() {
     });
 }
 
+#[test]
+fn create_role_should_fail_with_too_many_roles_in_space() {
+    ExtBuilder::build().execute_with(|| {
+        for _ in 0..MaxRolesPerSpace::get() {
+            assert_ok!(_create_default_role());
+        }
+
+        assert_noop!(_create_default_role(), Error::<Test>::TooManyRolesInSpace);
+    });
+}
+
 #[test]
 fn create_role_should_fail_with_ipfs_is_incorrect() {
     ExtBuilder::build().execute_with(|| {
@@ -416,6 +429,19 @@ fn grant_role_should_fail_with_no_users_provided() {
     });
 }
 
+#[test]
+fn grant_role_should_fail_with_too_many_users_for_role_change() {
+    ExtBuilder::build().execute_with(|| {
+        let users: Vec<User<AccountId>> = (2..33).map(User::Account).collect();
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_noop!(
+            _grant_role(None, None, Some(users)), // Grant RoleId 1 to ACCOUNT2-ACCOUNT32
+            Error::<Test>::TooManyUsersToChangeRole
+        );
+    });
+}
+
 #[test]
 fn grant_role_should_fail_with_a_few_roles_no_permission() {
     ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
@@ -478,6 +504,19 @@ fn revoke_role_should_fail_with_no_users_provided() {
     });
 }
 
+#[test]
+fn revoke_role_should_fail_with_too_many_users_for_role_change() {
+    ExtBuilder::build().execute_with(|| {
+        let users: Vec<User<AccountId>> = (2..33).map(User::Account).collect();
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_noop!(
+            _revoke_role(None, None, Some(users)),
+            Error::<Test>::TooManyUsersToChangeRole
+        );
+    });
+}
+
 #[test]
 fn revoke_role_should_fail_with_no_permission() {
     ExtBuilder::build().execute_with(|| {
@@ -507,6 +546,88 @@ fn revoke_role_should_fail_with_a_few_roles_no_permission() {
     });
 }
 
+#[test]
+fn grant_roles_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_create_default_role()); // RoleId 2
+        assert_ok!(_grant_roles(None, None, None)); // Grant RoleId 1 and 2 to ACCOUNT2
+
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![user.clone()]);
+        assert_eq!(Roles::users_by_role_id(ROLE2), vec![user.clone()]);
+        assert_eq!(Roles::role_ids_by_user_in_space(user, SPACE1), vec![ROLE1, ROLE2]);
+    });
+}
+
+#[test]
+fn grant_roles_should_fail_and_grant_nothing_when_a_role_not_found() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        // RoleId 1 is granted first, but it should be rolled back
+        assert_noop!(_grant_roles(None, Some(vec![ROLE1, 3]), None), Error::<Test>::RoleNotFound);
+    });
+}
+
+#[test]
+fn grant_roles_should_fail_with_no_roles_provided() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(_grant_roles(None, Some(vec![]), None), Error::<Test>::NoRolesProvided);
+    });
+}
+
+#[test]
+fn grant_roles_should_fail_with_too_many_role_changes() {
+    ExtBuilder::build().execute_with(|| {
+        let users: Vec<User<AccountId>> = (2..18).map(User::Account).collect();
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_create_default_role()); // RoleId 2
+
+        // 2 roles * 16 users is greater than `MaxUsersPerRoleChange`
+        assert_noop!(_grant_roles(None, None, Some(users)), Error::<Test>::TooManyRoleChanges);
+    });
+}
+
+#[test]
+fn revoke_roles_should_work() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+
+        assert_ok!(_revoke_roles(None, None, None)); // Revoke RoleId 1 and 2 from ACCOUNT2
+
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::users_by_role_id(ROLE2).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(user, SPACE1).is_empty());
+    });
+}
+
+#[test]
+fn revoke_roles_should_count_duplicated_users_once() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+
+        // 2 roles * 16 users is greater than `MaxUsersPerRoleChange`, but they are all the same user
+        assert_ok!(_revoke_roles(None, None, Some(vec![user.clone(); 16])));
+
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::users_by_role_id(ROLE2).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(user, SPACE1).is_empty());
+    });
+}
+
+#[test]
+fn revoke_roles_should_fail_with_no_permission() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_noop!(
+            _revoke_roles(Some(Origin::signed(ACCOUNT3)), None, None),
+            Error::<Test>::NoPermissionToManageRoles
+        );
+    });
+}
+
 #[test]
 fn delete_role_should_work() {
     ExtBuilder::build().execute_with(|| {
@@ -563,7 +684,7 @@ fn delete_role_should_fail_with_no_permission() {
 }
 
 #[test]
-fn delete_role_should_fail_with_too_many_users_for_delete_role() {
+fn delete_role_should_revoke_role_in_on_idle_when_too_many_users() {
     ExtBuilder::build().execute_with(|| {
         let mut users: Vec<User<AccountId>> = Vec::new();
         for account in 2..23 {
@@ -571,8 +692,16 @@ fn delete_role_should_fail_with_too_many_users_for_delete_role() {
         }
 
         assert_ok!(_create_default_role()); // RoleId 1
-        assert_ok!(_grant_role(None, None, Some(users))); // Grant RoleId 1 to ACCOUNT2-ACCOUNT20
-        assert_noop!(_delete_default_role(), Error::<Test>::TooManyUsersToDeleteRole);
+        assert_ok!(_grant_role(None, None, Some(users))); // Grant RoleId 1 to ACCOUNT2-ACCOUNT22
+        assert_ok!(_delete_default_role());
+
+        assert!(Roles::role_by_id(ROLE1).is_none());
+        assert_eq!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1), vec![ROLE1]);
+
+        Utils::on_idle(1, Weight::max_value());
+
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).is_empty());
     });
 }
 
@@ -588,3 +717,75 @@ fn delete_role_should_fail_with_a_few_roles_no_permission() {
         );
     });
 }
+
+#[test]
+fn create_role_should_index_role_by_its_permissions() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        assert_eq!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::ManageRoles), vec![ROLE1]);
+        assert!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::CreatePosts).is_empty());
+    });
+}
+
+#[test]
+fn update_role_should_reindex_role_by_its_permissions() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_update_role(
+            None,
+            None,
+            Some(self::role_update(None, None, Some(vec![SpacePermission::CreatePosts].into_iter().collect())))
+        ));
+
+        assert!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::ManageRoles).is_empty());
+        assert_eq!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::CreatePosts), vec![ROLE1]);
+    });
+}
+
+#[test]
+fn delete_role_should_remove_role_from_permissions_index() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_delete_default_role());
+
+        assert!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::ManageRoles).is_empty());
+    });
+}
+
+#[test]
+fn build_role_ids_by_space_permission_index_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        // Emulate a role created before the index was introduced.
+        RoleIdsBySpacePermission::remove_all(None);
+
+        migrations::build_role_ids_by_space_permission_index::<Test>(Weight::max_value());
+
+        assert_eq!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::ManageRoles), vec![ROLE1]);
+        assert!(RoleIdsBySpacePermissionIndexed::get());
+    });
+}
+
+#[test]
+fn build_role_ids_by_space_permission_index_should_continue_when_weight_is_exhausted() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_create_default_role()); // RoleId 2
+
+        RoleIdsBySpacePermission::remove_all(None);
+
+        // Enough weight to index only the first role with one permission.
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        migrations::build_role_ids_by_space_permission_index::<Test>(db_weight.reads_writes(5, 2));
+
+        assert_eq!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::ManageRoles), vec![ROLE1]);
+        assert!(!RoleIdsBySpacePermissionIndexed::get());
+
+        migrations::build_role_ids_by_space_permission_index::<Test>(Weight::max_value());
+
+        assert_eq!(Roles::role_ids_by_space_permission(SPACE1, SpacePermission::ManageRoles), vec![ROLE1, ROLE2]);
+        assert!(RoleIdsBySpacePermissionIndexed::get());
+    });
+}
@@ -0,0 +1,263 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    dispatch::{DispatchError, DispatchResult},
+    traits::Get
+};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use frame_system::{self as system, ensure_root, ensure_signed};
+
+use pallet_spaces::Module as Spaces;
+use pallet_utils::{Content, Module as Utils, SpaceId, remove_from_vec};
+
+pub type CategoryId = u64;
+
+pub const FIRST_CATEGORY_ID: u64 = 1;
+
+/// A node of the governance-curated tree of space categories.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct Category {
+    pub id: CategoryId,
+
+    /// `None` if this is a top-level category.
+    pub parent_id: Option<CategoryId>,
+
+    /// Depth of this category in the tree. Top-level categories have depth 0.
+    pub depth: u32,
+
+    pub content: Content,
+}
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_spaces::Config
+{
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    /// Max number of categories that can exist at the same time.
+    type MaxCategories: Get<u32>;
+
+    /// Max number of levels in the category tree.
+    type MaxCategoryDepth: Get<u32>;
+
+    /// Max number of spaces that can be placed in a single category.
+    type MaxSpacesPerCategory: Get<u32>;
+}
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Category was not found by id.
+        CategoryNotFound,
+        /// Parent category was not found by id.
+        ParentCategoryNotFound,
+        /// Cannot create more categories than allowed by `MaxCategories`.
+        TooManyCategories,
+        /// Cannot nest a category deeper than allowed by `MaxCategoryDepth`.
+        CategoryTooDeep,
+        /// Cannot delete a category that has subcategories.
+        CategoryHasSubcategories,
+        /// Cannot delete a category that has spaces placed in it.
+        CategoryHasSpaces,
+        /// Cannot place more spaces in this category than allowed by `MaxSpacesPerCategory`.
+        TooManySpacesInCategory,
+        /// Space is already placed in this category.
+        SpaceAlreadyInCategory,
+        /// Space is not placed in any category.
+        SpaceNotInCategory,
+        /// There is no pending placement request for this space.
+        NoPendingPlacementRequest,
+    }
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Config> as SpaceCategoriesModule {
+        pub NextCategoryId get(fn next_category_id): CategoryId = FIRST_CATEGORY_ID;
+
+        pub CategoriesCount get(fn categories_count): u32;
+
+        pub CategoryById get(fn category_by_id):
+            map hasher(twox_64_concat) CategoryId => Option<Category>;
+
+        pub SubcategoryIdsByCategory get(fn subcategory_ids_by_category):
+            map hasher(twox_64_concat) CategoryId => Vec<CategoryId>;
+
+        /// A category requested by a space owner that waits for approval by governance.
+        pub PendingCategoryBySpace get(fn pending_category_by_space):
+            map hasher(twox_64_concat) SpaceId => Option<CategoryId>;
+
+        pub CategoryIdBySpace get(fn category_id_by_space):
+            map hasher(twox_64_concat) SpaceId => Option<CategoryId>;
+
+        pub SpacesByCategory get(fn spaces_by_category):
+            map hasher(twox_64_concat) CategoryId => Vec<SpaceId>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Config>::AccountId,
+    {
+        CategoryCreated(CategoryId),
+        CategoryDeleted(CategoryId),
+        SpacePlacementRequested(AccountId, SpaceId, CategoryId),
+        SpacePlacementApproved(SpaceId, CategoryId),
+        SpacePlacementRejected(SpaceId, CategoryId),
+        SpaceRemovedFromCategory(SpaceId, CategoryId),
+    }
+);
+
+// The pallet's dispatchable functions.
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    // Initializing errors
+    type Error = Error<T>;
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 4)]
+    pub fn create_category(origin, parent_id: Option<CategoryId>, content: Content) -> DispatchResult {
+      ensure_root(origin)?;
+
+      Utils::<T>::is_valid_content(content.clone())?;
+      ensure!(Self::categories_count() < T::MaxCategories::get(), Error::<T>::TooManyCategories);
+
+      let mut depth = 0;
+      if let Some(parent_id) = parent_id {
+        let parent = Self::category_by_id(parent_id).ok_or(Error::<T>::ParentCategoryNotFound)?;
+        depth = parent.depth.saturating_add(1);
+      }
+      ensure!(depth < T::MaxCategoryDepth::get(), Error::<T>::CategoryTooDeep);
+
+      let category_id = Self::next_category_id();
+      let new_category = Category { id: category_id, parent_id, depth, content };
+
+      if let Some(parent_id) = parent_id {
+        SubcategoryIdsByCategory::mutate(parent_id, |ids| ids.push(category_id));
+      }
+
+      CategoryById::insert(category_id, new_category);
+      CategoriesCount::mutate(|n| { *n += 1; });
+      NextCategoryId::mutate(|n| { *n += 1; });
+
+      Self::deposit_event(RawEvent::CategoryCreated(category_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 3)]
+    pub fn delete_category(origin, category_id: CategoryId) -> DispatchResult {
+      ensure_root(origin)?;
+
+      let category = Self::require_category(category_id)?;
+      ensure!(Self::subcategory_ids_by_category(category_id).is_empty(), Error::<T>::CategoryHasSubcategories);
+      ensure!(Self::spaces_by_category(category_id).is_empty(), Error::<T>::CategoryHasSpaces);
+
+      if let Some(parent_id) = category.parent_id {
+        SubcategoryIdsByCategory::mutate(parent_id, |ids| remove_from_vec(ids, category_id));
+      }
+
+      CategoryById::remove(category_id);
+      CategoriesCount::mutate(|n| { *n = n.saturating_sub(1); });
+
+      Self::deposit_event(RawEvent::CategoryDeleted(category_id));
+      Ok(())
+    }
+
+    /// Request to place a space in a category. The request has to be approved by governance.
+    /// A new request replaces the previous pending request of the same space, if any.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn request_space_placement(origin, space_id: SpaceId, category_id: CategoryId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      space.ensure_space_owner(who.clone())?;
+
+      Self::require_category(category_id)?;
+      ensure!(Self::category_id_by_space(space_id) != Some(category_id), Error::<T>::SpaceAlreadyInCategory);
+
+      PendingCategoryBySpace::insert(space_id, category_id);
+
+      Self::deposit_event(RawEvent::SpacePlacementRequested(who, space_id, category_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 4)]
+    pub fn approve_space_placement(origin, space_id: SpaceId) -> DispatchResult {
+      ensure_root(origin)?;
+
+      let category_id = Self::pending_category_by_space(space_id).ok_or(Error::<T>::NoPendingPlacementRequest)?;
+      Spaces::<T>::ensure_space_exists(space_id)?;
+      Self::require_category(category_id)?;
+
+      let spaces_count = Self::spaces_by_category(category_id).len() as u32;
+      ensure!(spaces_count < T::MaxSpacesPerCategory::get(), Error::<T>::TooManySpacesInCategory);
+
+      if let Some(old_category_id) = Self::category_id_by_space(space_id) {
+        SpacesByCategory::mutate(old_category_id, |ids| remove_from_vec(ids, space_id));
+      }
+
+      PendingCategoryBySpace::remove(space_id);
+      CategoryIdBySpace::insert(space_id, category_id);
+      SpacesByCategory::mutate(category_id, |ids| ids.push(space_id));
+
+      Self::deposit_event(RawEvent::SpacePlacementApproved(space_id, category_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn reject_space_placement(origin, space_id: SpaceId) -> DispatchResult {
+      ensure_root(origin)?;
+
+      let category_id = Self::pending_category_by_space(space_id).ok_or(Error::<T>::NoPendingPlacementRequest)?;
+      PendingCategoryBySpace::remove(space_id);
+
+      Self::deposit_event(RawEvent::SpacePlacementRejected(space_id, category_id));
+      Ok(())
+    }
+
+    /// Remove a space from its category. Can be called by the space owner.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+    pub fn remove_space_from_category(origin, space_id: SpaceId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      space.ensure_space_owner(who)?;
+
+      Self::do_remove_space_from_category(space_id)
+    }
+
+    /// Remove a space from its category by governance, e.g. if a space no longer fits it.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+    pub fn force_remove_space_from_category(origin, space_id: SpaceId) -> DispatchResult {
+      ensure_root(origin)?;
+
+      Self::do_remove_space_from_category(space_id)
+    }
+  }
+}
+
+impl<T: Config> Module<T> {
+
+    /// Get `Category` by id from the storage or return `CategoryNotFound` error.
+    pub fn require_category(category_id: CategoryId) -> Result<Category, DispatchError> {
+        Ok(Self::category_by_id(category_id).ok_or(Error::<T>::CategoryNotFound)?)
+    }
+
+    fn do_remove_space_from_category(space_id: SpaceId) -> DispatchResult {
+        let category_id = Self::category_id_by_space(space_id).ok_or(Error::<T>::SpaceNotInCategory)?;
+
+        CategoryIdBySpace::remove(space_id);
+        SpacesByCategory::mutate(category_id, |ids| remove_from_vec(ids, space_id));
+
+        Self::deposit_event(RawEvent::SpaceRemovedFromCategory(space_id, category_id));
+        Ok(())
+    }
+}
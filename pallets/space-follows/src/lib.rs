@@ -1,23 +1,41 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
-    dispatch::DispatchResult,
-    traits::Get
+    dispatch::{DispatchError, DispatchResult},
+    storage::{with_transaction, TransactionOutcome},
+    traits::Get,
+    weights::Weight,
 };
+use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::{
-    SpaceFollowsProvider,
+    SpaceFollowsProvider, IsAccountActive,
     moderation::IsAccountBlocked,
 };
-use pallet_profiles::{Module as Profiles, SocialAccountById};
-use pallet_spaces::{BeforeSpaceCreated, Module as Spaces, Space, SpaceById};
+use pallet_profiles::{Module as Profiles, SocialAccount, SocialAccountById};
+use pallet_spaces::{BeforeSpaceCreated, Module as Spaces, Space};
 use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec};
 
 pub mod rpc;
 
+/// An extra weight of following or unfollowing every space in a batch
+/// in its own storage transaction.
+pub const WEIGHT_PER_BATCH_TRANSACTION: Weight = 10_000;
+
+/// Counters of space-to-space follows of a given space.
+#[derive(Encode, Decode, Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct SpaceFollowsCounts {
+    /// The number of spaces a given space follows.
+    pub following_spaces_count: u32,
+    /// The number of spaces that follow a given space.
+    pub follower_spaces_count: u32,
+}
+
 /// The pallet's configuration trait.
 pub trait Config: system::Config
     + pallet_utils::Config
@@ -30,6 +48,9 @@ pub trait Config: system::Config
     type BeforeSpaceFollowed: BeforeSpaceFollowed<Self>;
 
     type BeforeSpaceUnfollowed: BeforeSpaceUnfollowed<Self>;
+
+    /// Max number of spaces that can be followed or unfollowed in one batch.
+    type MaxSpacesPerBatch: Get<u16>;
 }
 
 decl_error! {
@@ -42,6 +63,16 @@ decl_error! {
         NotSpaceFollower,
         /// Not allowed to follow a hidden space.
         CannotFollowHiddenSpace,
+        /// No space ids provided in a batch.
+        NoSpacesInBatch,
+        /// Too many space ids provided in a batch.
+        TooManySpacesInBatch,
+        /// A space cannot follow itself.
+        SpaceCannotFollowItself,
+        /// A space already follows this space.
+        SpaceAlreadyFollowsSpace,
+        /// A space does not follow this space.
+        SpaceDoesNotFollowSpace,
     }
 }
 
@@ -56,6 +87,24 @@ decl_storage! {
 
         pub SpacesFollowedByAccount get(fn spaces_followed_by_account):
             map hasher(blake2_128_concat) T::AccountId => Vec<SpaceId>;
+
+        /// Ids of spaces followed by a given space (key).
+        pub SpacesFollowedBySpace get(fn spaces_followed_by_space):
+            map hasher(twox_64_concat) SpaceId => Vec<SpaceId>;
+
+        /// Ids of spaces that follow a given space (key).
+        pub FollowerSpacesBySpace get(fn follower_spaces_by_space):
+            map hasher(twox_64_concat) SpaceId => Vec<SpaceId>;
+
+        /// Whether a space (key 1) follows another space (key 2).
+        pub SpaceFollowedBySpace get(fn space_followed_by_space): double_map
+            hasher(twox_64_concat) SpaceId,
+            hasher(twox_64_concat) SpaceId
+            => bool;
+
+        /// Counters of space-to-space follows of a given space (key).
+        pub SpaceFollowsCountsBySpace get(fn space_follows_counts_by_space):
+            map hasher(twox_64_concat) SpaceId => SpaceFollowsCounts;
     }
 }
 
@@ -65,85 +114,311 @@ decl_event!(
     {
         SpaceFollowed(/* follower */ AccountId, /* following */ SpaceId),
         SpaceUnfollowed(/* follower */ AccountId, /* unfollowing */ SpaceId),
+        SpacesFollowed(/* follower */ AccountId, /* followed */ Vec<SpaceId>, /* failed */ Vec<SpaceId>),
+        SpacesUnfollowed(/* follower */ AccountId, /* unfollowed */ Vec<SpaceId>, /* failed */ Vec<SpaceId>),
+        SpaceFollowedBySpace(AccountId, /* follower */ SpaceId, /* following */ SpaceId),
+        SpaceUnfollowedBySpace(AccountId, /* follower */ SpaceId, /* unfollowing */ SpaceId),
     }
 );
 
 // The pallet's dispatchable functions.
 decl_module! {
   pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    const MaxSpacesPerBatch: u16 = T::MaxSpacesPerBatch::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + Module::<T>::follow_space_weight()]
     pub fn follow_space(origin, space_id: SpaceId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
-      ensure!(!Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::AlreadySpaceFollower);
+      let space = &Self::ensure_can_follow_space(&follower, space_id)?;
 
-      let space = &mut Spaces::require_space(space_id)?;
-      ensure!(!space.hidden, Error::<T>::CannotFollowHiddenSpace);
+      Self::add_space_follower(follower, space)
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + Module::<T>::unfollow_space_weight()]
+    pub fn unfollow_space(origin, space_id: SpaceId) -> DispatchResult {
+      let follower = ensure_signed(origin)?;
+
+      ensure!(Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::NotSpaceFollower);
 
-      ensure!(T::IsAccountBlocked::is_allowed_account(follower.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+      Self::unfollow_space_by_account(follower, space_id)
+    }
+
+    /// Follow a few spaces at once, e.g. recommended spaces during onboarding.
+    /// Spaces that cannot be followed are skipped and reported in `SpacesFollowed` event.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + (space_ids.len() as Weight).saturating_mul(
+      WEIGHT_PER_BATCH_TRANSACTION + Module::<T>::follow_space_weight()
+    )]
+    pub fn follow_spaces(origin, space_ids: Vec<SpaceId>) -> DispatchResult {
+      let follower = ensure_signed(origin)?;
 
-      Self::add_space_follower(follower, space)?;
-      <SpaceById<T>>::insert(space_id, space);
+      Self::ensure_valid_batch(&space_ids)?;
+
+      let mut social_account = Profiles::get_or_new_social_account(follower.clone());
+      let mut followed = Vec::new();
+      let mut failed = Vec::new();
+
+      for space_id in space_ids {
+        let mut account_after_follow = social_account.clone();
+
+        let result = with_transaction(|| {
+          match Self::try_follow_space(&follower, &mut account_after_follow, space_id) {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(err) => TransactionOutcome::Rollback(Err(err)),
+          }
+        });
+
+        if result.is_ok() {
+          social_account = account_after_follow;
+          followed.push(space_id);
+        } else {
+          failed.push(space_id);
+        }
+      }
+
+      if !followed.is_empty() {
+        <SocialAccountById<T>>::insert(follower.clone(), social_account);
+      }
 
+      Self::deposit_event(RawEvent::SpacesFollowed(follower, followed, failed));
       Ok(())
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
-    pub fn unfollow_space(origin, space_id: SpaceId) -> DispatchResult {
+    /// Unfollow a few spaces at once.
+    /// Spaces that cannot be unfollowed are skipped and reported in `SpacesUnfollowed` event.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1) + (space_ids.len() as Weight).saturating_mul(
+      WEIGHT_PER_BATCH_TRANSACTION + Module::<T>::unfollow_space_weight()
+    )]
+    pub fn unfollow_spaces(origin, space_ids: Vec<SpaceId>) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
-      ensure!(Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::NotSpaceFollower);
+      Self::ensure_valid_batch(&space_ids)?;
 
-      Self::unfollow_space_by_account(follower, space_id)
+      let mut social_account = Profiles::social_account_by_id(follower.clone())
+        .ok_or(Error::<T>::SocialAccountNotFound)?;
+      let mut unfollowed = Vec::new();
+      let mut failed = Vec::new();
+
+      for space_id in space_ids {
+        let mut account_after_unfollow = social_account.clone();
+
+        let result = with_transaction(|| {
+          match Self::try_unfollow_space(&follower, &mut account_after_unfollow, space_id) {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(err) => TransactionOutcome::Rollback(Err(err)),
+          }
+        });
+
+        if result.is_ok() {
+          social_account = account_after_unfollow;
+          unfollowed.push(space_id);
+        } else {
+          failed.push(space_id);
+        }
+      }
+
+      if !unfollowed.is_empty() {
+        <SocialAccountById<T>>::insert(follower.clone(), social_account);
+      }
+
+      Self::deposit_event(RawEvent::SpacesUnfollowed(follower, unfollowed, failed));
+      Ok(())
+    }
+
+    /// Make a space (`follower_space_id`) follow another space (`space_id`),
+    /// e.g. to list related communities. Only an owner of a follower space can do this.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 5)]
+    pub fn follow_space_by_space(origin, follower_space_id: SpaceId, space_id: SpaceId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(follower_space_id != space_id, Error::<T>::SpaceCannotFollowItself);
+
+      let follower_space = Spaces::<T>::require_space(follower_space_id)?;
+      follower_space.ensure_space_owner(owner.clone())?;
+
+      ensure!(
+        !Self::space_followed_by_space(follower_space_id, space_id),
+        Error::<T>::SpaceAlreadyFollowsSpace
+      );
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      ensure!(!space.hidden, Error::<T>::CannotFollowHiddenSpace);
+
+      SpacesFollowedBySpace::mutate(follower_space_id, |space_ids| space_ids.push(space_id));
+      FollowerSpacesBySpace::mutate(space_id, |space_ids| space_ids.push(follower_space_id));
+      SpaceFollowedBySpace::insert(follower_space_id, space_id, true);
+
+      SpaceFollowsCountsBySpace::mutate(follower_space_id, |counts| counts.inc_following_spaces());
+      SpaceFollowsCountsBySpace::mutate(space_id, |counts| counts.inc_follower_spaces());
+
+      Self::deposit_event(RawEvent::SpaceFollowedBySpace(owner, follower_space_id, space_id));
+      Ok(())
+    }
+
+    /// Make a space (`follower_space_id`) unfollow another space (`space_id`).
+    /// Only an owner of a follower space can do this.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 5)]
+    pub fn unfollow_space_by_space(origin, follower_space_id: SpaceId, space_id: SpaceId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      let follower_space = Spaces::<T>::require_space(follower_space_id)?;
+      follower_space.ensure_space_owner(owner.clone())?;
+
+      ensure!(
+        Self::space_followed_by_space(follower_space_id, space_id),
+        Error::<T>::SpaceDoesNotFollowSpace
+      );
+
+      SpacesFollowedBySpace::mutate(follower_space_id, |space_ids| remove_from_vec(space_ids, space_id));
+      FollowerSpacesBySpace::mutate(space_id, |space_ids| remove_from_vec(space_ids, follower_space_id));
+      SpaceFollowedBySpace::remove(follower_space_id, space_id);
+
+      SpaceFollowsCountsBySpace::mutate(follower_space_id, |counts| counts.dec_following_spaces());
+      SpaceFollowsCountsBySpace::mutate(space_id, |counts| counts.dec_follower_spaces());
+
+      Self::deposit_event(RawEvent::SpaceUnfollowedBySpace(owner, follower_space_id, space_id));
+      Ok(())
     }
   }
 }
 
+impl SpaceFollowsCounts {
+    pub fn inc_following_spaces(&mut self) {
+        self.following_spaces_count = self.following_spaces_count.saturating_add(1);
+    }
+
+    pub fn dec_following_spaces(&mut self) {
+        self.following_spaces_count = self.following_spaces_count.saturating_sub(1);
+    }
+
+    pub fn inc_follower_spaces(&mut self) {
+        self.follower_spaces_count = self.follower_spaces_count.saturating_add(1);
+    }
+
+    pub fn dec_follower_spaces(&mut self) {
+        self.follower_spaces_count = self.follower_spaces_count.saturating_sub(1);
+    }
+}
+
 impl<T: Config> Module<T> {
-    fn add_space_follower(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult {
-        space.inc_followers();
+    /// A weight of `try_follow_space`: checks that a follower is active and not blocked,
+    /// follow storages and space stats.
+    pub fn follow_space_weight() -> Weight {
+        T::DbWeight::get().reads_writes(8, 4)
+    }
+
+    /// A weight of `try_unfollow_space`.
+    pub fn unfollow_space_weight() -> Weight {
+        T::DbWeight::get().reads_writes(5, 4)
+    }
+
+    fn ensure_can_follow_space(follower: &T::AccountId, space_id: SpaceId) -> Result<Space<T>, DispatchError> {
+        T::IsAccountActive::ensure_account_is_active(follower)?;
+        ensure!(!Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::AlreadySpaceFollower);
+
+        let space = Spaces::require_space(space_id)?;
+        ensure!(!space.hidden, Error::<T>::CannotFollowHiddenSpace);
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(follower.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
 
+        Ok(space)
+    }
+
+    fn ensure_valid_batch(space_ids: &[SpaceId]) -> DispatchResult {
+        ensure!(!space_ids.is_empty(), Error::<T>::NoSpacesInBatch);
+        ensure!(space_ids.len() <= T::MaxSpacesPerBatch::get() as usize, Error::<T>::TooManySpacesInBatch);
+        Ok(())
+    }
+
+    fn add_space_follower(follower: T::AccountId, space: &Space<T>) -> DispatchResult {
         let mut social_account = Profiles::get_or_new_social_account(follower.clone());
+
+        Self::add_space_follower_to_account(&follower, &mut social_account, space)?;
+        <SocialAccountById<T>>::insert(follower.clone(), social_account);
+
+        Self::deposit_event(RawEvent::SpaceFollowed(follower, space.id));
+
+        Ok(())
+    }
+
+    /// Update follow storages and counters, except of the follower's social account,
+    /// that should be saved by a caller.
+    fn add_space_follower_to_account(
+        follower: &T::AccountId,
+        social_account: &mut SocialAccount<T>,
+        space: &Space<T>,
+    ) -> DispatchResult {
         social_account.inc_following_spaces();
 
         T::BeforeSpaceFollowed::before_space_followed(
             follower.clone(), social_account.reputation, space)?;
 
         let space_id = space.id;
+        Spaces::<T>::mutate_space_stats(space_id, |stats| stats.inc_followers());
         <SpaceFollowers<T>>::mutate(space_id, |followers| followers.push(follower.clone()));
         <SpaceFollowedByAccount<T>>::insert((follower.clone(), space_id), true);
         <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| space_ids.push(space_id));
-        <SocialAccountById<T>>::insert(follower.clone(), social_account);
-
-        Self::deposit_event(RawEvent::SpaceFollowed(follower, space_id));
 
         Ok(())
     }
 
-    pub fn unfollow_space_by_account(follower: T::AccountId, space_id: SpaceId) -> DispatchResult {
-        let space = &mut Spaces::require_space(space_id)?;
-        space.dec_followers();
+    fn try_follow_space(
+        follower: &T::AccountId,
+        social_account: &mut SocialAccount<T>,
+        space_id: SpaceId,
+    ) -> DispatchResult {
+        let space = &Self::ensure_can_follow_space(follower, space_id)?;
+
+        Self::add_space_follower_to_account(follower, social_account, space)
+    }
 
+    pub fn unfollow_space_by_account(follower: T::AccountId, space_id: SpaceId) -> DispatchResult {
         let mut social_account = Profiles::social_account_by_id(follower.clone()).ok_or(Error::<T>::SocialAccountNotFound)?;
+
+        Self::remove_space_follower_from_account(&follower, &mut social_account, space_id)?;
+        <SocialAccountById<T>>::insert(follower.clone(), social_account);
+
+        Self::deposit_event(RawEvent::SpaceUnfollowed(follower, space_id));
+        Ok(())
+    }
+
+    /// Update follow storages and counters, except of the follower's social account,
+    /// that should be saved by a caller.
+    fn remove_space_follower_from_account(
+        follower: &T::AccountId,
+        social_account: &mut SocialAccount<T>,
+        space_id: SpaceId,
+    ) -> DispatchResult {
+        let space = &Spaces::require_space(space_id)?;
+
         social_account.dec_following_spaces();
 
         T::BeforeSpaceUnfollowed::before_space_unfollowed(follower.clone(), space)?;
 
+        Spaces::<T>::mutate_space_stats(space_id, |stats| stats.dec_followers());
+
         <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| remove_from_vec(space_ids, space_id));
         <SpaceFollowers<T>>::mutate(space_id, |account_ids| remove_from_vec(account_ids, follower.clone()));
         <SpaceFollowedByAccount<T>>::remove((follower.clone(), space_id));
-        <SocialAccountById<T>>::insert(follower.clone(), social_account);
-        <SpaceById<T>>::insert(space_id, space);
 
-        Self::deposit_event(RawEvent::SpaceUnfollowed(follower, space_id));
         Ok(())
     }
+
+    fn try_unfollow_space(
+        follower: &T::AccountId,
+        social_account: &mut SocialAccount<T>,
+        space_id: SpaceId,
+    ) -> DispatchResult {
+        ensure!(Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::NotSpaceFollower);
+        Self::remove_space_follower_from_account(follower, social_account, space_id)
+    }
 }
 
 impl<T: Config> SpaceFollowsProvider for Module<T> {
@@ -163,22 +438,22 @@ impl<T: Config> BeforeSpaceCreated<T> for Module<T> {
 
 /// Handler that will be called right before the space is followed.
 pub trait BeforeSpaceFollowed<T: Config> {
-    fn before_space_followed(follower: T::AccountId, follower_reputation: u32, space: &mut Space<T>) -> DispatchResult;
+    fn before_space_followed(follower: T::AccountId, follower_reputation: u32, space: &Space<T>) -> DispatchResult;
 }
 
 impl<T: Config> BeforeSpaceFollowed<T> for () {
-    fn before_space_followed(_follower: T::AccountId, _follower_reputation: u32, _space: &mut Space<T>) -> DispatchResult {
+    fn before_space_followed(_follower: T::AccountId, _follower_reputation: u32, _space: &Space<T>) -> DispatchResult {
         Ok(())
     }
 }
 
 /// Handler that will be called right before the space is unfollowed.
 pub trait BeforeSpaceUnfollowed<T: Config> {
-    fn before_space_unfollowed(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult;
+    fn before_space_unfollowed(follower: T::AccountId, space: &Space<T>) -> DispatchResult;
 }
 
 impl<T: Config> BeforeSpaceUnfollowed<T> for () {
-    fn before_space_unfollowed(_follower: T::AccountId, _space: &mut Space<T>) -> DispatchResult {
+    fn before_space_unfollowed(_follower: T::AccountId, _space: &Space<T>) -> DispatchResult {
         Ok(())
     }
 }
@@ -9,6 +9,10 @@ impl<T: Config> Module<T> {
         Self::spaces_followed_by_account(account)
     }
 
+    pub fn get_space_ids_followed_by_space(space_id: SpaceId) -> Vec<SpaceId> {
+        Self::spaces_followed_by_space(space_id)
+    }
+
     pub fn filter_followed_space_ids(account: T::AccountId, space_ids: Vec<SpaceId>) -> Vec<SpaceId> {
         space_ids.iter()
             .filter(|space_id| Self::space_followed_by_account((&account, space_id)))
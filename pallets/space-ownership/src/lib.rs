@@ -10,7 +10,7 @@ use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::moderation::IsAccountBlocked;
-use pallet_spaces::{Module as Spaces, SpaceById, SpaceIdsByOwner};
+use pallet_spaces::{FrozenSpaceIds, Module as Spaces, SpaceById, SpaceIdsByOwner};
 use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec};
 
 /// The pallet's configuration trait.
@@ -34,6 +34,10 @@ decl_error! {
     NotAllowedToAcceptOwnershipTransfer,
     /// Account is not allowed to reject ownership transfer.
     NotAllowedToRejectOwnershipTransfer,
+    /// Only a recipient of a frozen ownership transfer can reject it.
+    OnlyRecipientCanRejectFrozenTransfer,
+    /// A frozen ownership transfer cannot be redirected to another account.
+    CannotRedirectFrozenTransfer,
   }
 }
 
@@ -65,20 +69,20 @@ decl_module! {
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
     pub fn transfer_space_ownership(origin, space_id: SpaceId, transfer_to: T::AccountId) -> DispatchResult {
       let who = ensure_signed(origin)?;
+      Self::do_transfer_space_ownership(who, space_id, transfer_to, false)
+    }
 
-      let space = Spaces::<T>::require_space(space_id)?;
-      space.ensure_space_owner(who.clone())?;
-
-      ensure!(who != transfer_to, Error::<T>::CannotTranferToCurrentOwner);
-      ensure!(T::IsAccountBlocked::is_allowed_account(transfer_to.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
-
-      <PendingSpaceOwner<T>>::insert(space_id, transfer_to.clone());
-
-      Self::deposit_event(RawEvent::SpaceOwnershipTransferCreated(who, space_id, transfer_to));
-      Ok(())
+    /// Same as `transfer_space_ownership`, but also freezes handle, permission overrides
+    /// and roles of a space until the transfer is accepted or rejected,
+    /// so that the current owner cannot change them after agreeing to transfer a space.
+    /// Only the recipient can lift a freeze, by accepting or rejecting the transfer.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+    pub fn transfer_space_ownership_and_freeze(origin, space_id: SpaceId, transfer_to: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      Self::do_transfer_space_ownership(who, space_id, transfer_to, true)
     }
 
     #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
@@ -93,26 +97,29 @@ decl_module! {
 
       // Here we know that the origin is eligible to become a new owner of this space.
       <PendingSpaceOwner<T>>::remove(space_id);
+      FrozenSpaceIds::remove(space_id);
 
       Spaces::maybe_transfer_handle_deposit_to_new_space_owner(&space, &new_owner)?;
 
-      let old_owner = space.owner;
+      let old_owner = space.owner.clone();
       space.owner = new_owner.clone();
-      <SpaceById<T>>::insert(space_id, space);
+      <SpaceById<T>>::insert(space_id, space.clone());
 
       // Remove space id from the list of spaces by old owner
-      <SpaceIdsByOwner<T>>::mutate(old_owner, |space_ids| remove_from_vec(space_ids, space_id));
+      <SpaceIdsByOwner<T>>::mutate(&old_owner, |space_ids| remove_from_vec(space_ids, space_id));
 
       // Add space id to the list of spaces by new owner
       <SpaceIdsByOwner<T>>::mutate(new_owner.clone(), |ids| ids.push(space_id));
 
       // TODO add a new owner as a space follower? See T::BeforeSpaceCreated::before_space_created(new_owner.clone(), space)?;
 
+      T::SpaceHooks::on_space_ownership_changed(&old_owner, &space);
+
       Self::deposit_event(RawEvent::SpaceOwnershipTransferAccepted(new_owner, space_id));
       Ok(())
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
     pub fn reject_pending_ownership(origin, space_id: SpaceId) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
@@ -120,10 +127,49 @@ decl_module! {
       let transfer_to = Self::pending_space_owner(space_id).ok_or(Error::<T>::NoPendingTransferOnSpace)?;
       ensure!(who == transfer_to || who == space.owner, Error::<T>::NotAllowedToRejectOwnershipTransfer);
 
+      // Otherwise the current owner could unfreeze a space right after agreeing to transfer it.
+      ensure!(
+        who == transfer_to || !Spaces::<T>::is_space_frozen(space_id),
+        Error::<T>::OnlyRecipientCanRejectFrozenTransfer
+      );
+
       <PendingSpaceOwner<T>>::remove(space_id);
+      FrozenSpaceIds::remove(space_id);
 
       Self::deposit_event(RawEvent::SpaceOwnershipTransferRejected(who, space_id));
       Ok(())
     }
   }
 }
+
+impl<T: Config> Module<T> {
+
+    fn do_transfer_space_ownership(
+        who: T::AccountId,
+        space_id: SpaceId,
+        transfer_to: T::AccountId,
+        freeze: bool,
+    ) -> DispatchResult {
+        let space = Spaces::<T>::require_space(space_id)?;
+        space.ensure_space_owner(who.clone())?;
+
+        ensure!(who != transfer_to, Error::<T>::CannotTranferToCurrentOwner);
+        ensure!(T::IsAccountBlocked::is_allowed_account(transfer_to.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
+
+        // A frozen transfer can only be re-issued to the same recipient, and it stays frozen.
+        let is_same_recipient = Self::pending_space_owner(space_id).as_ref() == Some(&transfer_to);
+        ensure!(
+            is_same_recipient || !Spaces::<T>::is_space_frozen(space_id),
+            Error::<T>::CannotRedirectFrozenTransfer
+        );
+
+        <PendingSpaceOwner<T>>::insert(space_id, transfer_to.clone());
+
+        if freeze {
+            FrozenSpaceIds::insert(space_id, true);
+        }
+
+        Self::deposit_event(RawEvent::SpaceOwnershipTransferCreated(who, space_id, transfer_to));
+        Ok(())
+    }
+}
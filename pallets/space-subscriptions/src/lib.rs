@@ -0,0 +1,441 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    dispatch::{DispatchError, DispatchResult},
+    traits::{Currency, ExistenceRequirement, Get},
+    weights::Weight,
+};
+use sp_runtime::{RuntimeDebug, traits::{One, Saturating, Zero}};
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+use frame_system::{self as system, ensure_signed};
+
+use pallet_roles::{Module as Roles, RoleId};
+use pallet_space_follows::Module as SpaceFollows;
+use pallet_spaces::Module as Spaces;
+use pallet_utils::{BalanceOf, Content, Module as Utils, SpaceId, User, WhoAndWhen};
+
+pub type SubscriptionTierId = u64;
+
+pub const FIRST_SUBSCRIPTION_TIER_ID: u64 = 1;
+
+/// A paid tier of a space that its followers can subscribe to.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct SubscriptionTier<T: Config> {
+    pub id: SubscriptionTierId,
+    pub created: WhoAndWhen<T>,
+    pub space_id: SpaceId,
+
+    /// An amount of native tokens that is charged from a subscriber every `period`.
+    pub price: BalanceOf<T>,
+    pub period: T::BlockNumber,
+
+    /// A role of the same space that is granted to every active subscriber of this tier.
+    pub role_id: Option<RoleId>,
+
+    pub content: Content,
+
+    /// Disabled tiers accept no new subscribers,
+    /// and their active subscriptions expire at the end of a paid period.
+    pub disabled: bool,
+}
+
+/// A subscription of an account to a tier.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Subscription<T: Config> {
+    pub subscribed_at: T::BlockNumber,
+
+    /// A block at which the next period is charged or, if cancelled, the subscription expires.
+    pub renews_at: T::BlockNumber,
+
+    pub cancelled: bool,
+
+    /// A tier role that was granted to a subscriber by this subscription,
+    /// and should be revoked when it expires. `None` if a subscriber already had this role.
+    pub granted_role_id: Option<RoleId>,
+}
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_spaces::Config
+    + pallet_space_follows::Config
+    + pallet_roles::Config
+{
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    /// Max number of subscription tiers that a single space can have.
+    type MaxTiersPerSpace: Get<u32>;
+
+    /// Min number of blocks in a subscription period.
+    type MinSubscriptionPeriod: Get<Self::BlockNumber>;
+
+    /// Max number of subscriptions that can be renewed at the same block.
+    /// Renewals that do not fit into a block are moved to the following blocks.
+    type MaxRenewalsPerBlock: Get<u32>;
+
+    /// Max number of blocks by which a renewal can be moved when its block is full.
+    type MaxRenewalDelay: Get<u32>;
+}
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Subscription tier was not found by id.
+        TierNotFound,
+        /// Cannot create more tiers in this space than allowed by `MaxTiersPerSpace`.
+        TooManyTiersInSpace,
+        /// Subscription period is shorter than `MinSubscriptionPeriod`.
+        SubscriptionPeriodTooShort,
+        /// Subscription tier price should be greater than zero.
+        ZeroTierPrice,
+        /// Role of a tier should belong to the same space as the tier.
+        RoleNotInSpace,
+        /// Nothing to update in a subscription tier.
+        NoUpdatesForTier,
+        /// Cannot subscribe to a disabled tier.
+        TierIsDisabled,
+        /// Only followers of a space can subscribe to its tiers.
+        NotSpaceFollower,
+        /// Account is already subscribed to this tier.
+        AlreadySubscribed,
+        /// Account is not subscribed to this tier.
+        NotSubscribed,
+        /// Subscription is already cancelled.
+        SubscriptionAlreadyCancelled,
+        /// All blocks within `MaxRenewalDelay` after the end of a period have no room for a renewal.
+        RenewalQueueIsFull,
+    }
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Config> as SpaceSubscriptionsModule {
+        pub NextTierId get(fn next_tier_id): SubscriptionTierId = FIRST_SUBSCRIPTION_TIER_ID;
+
+        pub TierById get(fn tier_by_id):
+            map hasher(twox_64_concat) SubscriptionTierId => Option<SubscriptionTier<T>>;
+
+        pub TierIdsBySpaceId get(fn tier_ids_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Vec<SubscriptionTierId>;
+
+        pub SubscriptionByAccount get(fn subscription_by_account):
+            double_map
+                hasher(blake2_128_concat) T::AccountId,
+                hasher(twox_64_concat) SubscriptionTierId
+            => Option<Subscription<T>>;
+
+        /// Get the subscriptions that should be renewed or expired at a given block.
+        pub RenewalsAt get(fn renewals_at):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(T::AccountId, SubscriptionTierId)>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Config>::AccountId,
+    {
+        TierCreated(AccountId, SpaceId, SubscriptionTierId),
+        TierUpdated(AccountId, SubscriptionTierId),
+        Subscribed(AccountId, SubscriptionTierId),
+        SubscriptionRenewed(AccountId, SubscriptionTierId),
+        SubscriptionCancelled(AccountId, SubscriptionTierId),
+        SubscriptionExpired(AccountId, SubscriptionTierId),
+    }
+);
+
+// The pallet's dispatchable functions.
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    const MaxTiersPerSpace: u32 = T::MaxTiersPerSpace::get();
+
+    const MinSubscriptionPeriod: T::BlockNumber = T::MinSubscriptionPeriod::get();
+
+    const MaxRenewalsPerBlock: u32 = T::MaxRenewalsPerBlock::get();
+
+    const MaxRenewalDelay: u32 = T::MaxRenewalDelay::get();
+
+    // Initializing errors
+    type Error = Error<T>;
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    fn on_initialize(now: T::BlockNumber) -> Weight {
+      Self::renew_subscriptions(now)
+    }
+
+    /// Create a subscription tier in a space. Only the space owner can call this dispatch.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(4, 3)]
+    pub fn create_tier(
+      origin,
+      space_id: SpaceId,
+      price: BalanceOf<T>,
+      period: T::BlockNumber,
+      role_id: Option<RoleId>,
+      content: Content
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      space.ensure_space_owner(who.clone())?;
+
+      Utils::<T>::is_valid_content(content.clone())?;
+      ensure!(!price.is_zero(), Error::<T>::ZeroTierPrice);
+      ensure!(period >= T::MinSubscriptionPeriod::get(), Error::<T>::SubscriptionPeriodTooShort);
+
+      let tiers_count = Self::tier_ids_by_space_id(space_id).len() as u32;
+      ensure!(tiers_count < T::MaxTiersPerSpace::get(), Error::<T>::TooManyTiersInSpace);
+
+      if let Some(role_id) = role_id {
+        Self::ensure_role_in_space(role_id, space_id)?;
+      }
+
+      let tier_id = Self::next_tier_id();
+      let new_tier = SubscriptionTier::<T> {
+        id: tier_id,
+        created: WhoAndWhen::<T>::new(who.clone()),
+        space_id,
+        price,
+        period,
+        role_id,
+        content,
+        disabled: false,
+      };
+
+      TierById::<T>::insert(tier_id, new_tier);
+      TierIdsBySpaceId::mutate(space_id, |ids| ids.push(tier_id));
+      NextTierId::mutate(|n| { *n += 1; });
+
+      Self::deposit_event(RawEvent::TierCreated(who, space_id, tier_id));
+      Ok(())
+    }
+
+    /// Disable or enable a subscription tier. Only the space owner can call this dispatch.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn set_tier_disabled(origin, tier_id: SubscriptionTierId, disabled: bool) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let mut tier = Self::require_tier(tier_id)?;
+      let space = Spaces::<T>::require_space(tier.space_id)?;
+      space.ensure_space_owner(who.clone())?;
+
+      ensure!(tier.disabled != disabled, Error::<T>::NoUpdatesForTier);
+
+      tier.disabled = disabled;
+      TierById::<T>::insert(tier_id, tier);
+
+      Self::deposit_event(RawEvent::TierUpdated(who, tier_id));
+      Ok(())
+    }
+
+    /// Subscribe to a tier of a followed space. The first period is charged immediately.
+    /// Subscribing again to a cancelled subscription before it expires resumes it for free.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(
+      10 + T::MaxRenewalDelay::get() as u64,
+      7
+    )]
+    pub fn subscribe(origin, tier_id: SubscriptionTierId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let tier = Self::require_tier(tier_id)?;
+      ensure!(!tier.disabled, Error::<T>::TierIsDisabled);
+      ensure!(
+        SpaceFollows::<T>::space_followed_by_account((who.clone(), tier.space_id)),
+        Error::<T>::NotSpaceFollower
+      );
+
+      if let Some(mut subscription) = Self::subscription_by_account(&who, tier_id) {
+        ensure!(subscription.cancelled, Error::<T>::AlreadySubscribed);
+
+        subscription.cancelled = false;
+        SubscriptionByAccount::<T>::insert(&who, tier_id, subscription);
+
+        Self::deposit_event(RawEvent::Subscribed(who, tier_id));
+        return Ok(());
+      }
+
+      let now = <system::Pallet<T>>::block_number();
+      let renews_at = Self::find_renewal_block(now.saturating_add(tier.period))?;
+
+      Self::charge_subscriber(&who, &tier)?;
+
+      RenewalsAt::<T>::append(renews_at, (who.clone(), tier_id));
+      SubscriptionByAccount::<T>::insert(&who, tier_id, Subscription::<T> {
+        subscribed_at: now,
+        renews_at,
+        cancelled: false,
+        granted_role_id: Self::grant_tier_role(&who, &tier),
+      });
+
+      Self::deposit_event(RawEvent::Subscribed(who, tier_id));
+      Ok(())
+    }
+
+    /// Cancel a subscription. It stays active until the end of the paid period.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn cancel_subscription(origin, tier_id: SubscriptionTierId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let mut subscription = Self::subscription_by_account(&who, tier_id).ok_or(Error::<T>::NotSubscribed)?;
+      ensure!(!subscription.cancelled, Error::<T>::SubscriptionAlreadyCancelled);
+
+      subscription.cancelled = true;
+      SubscriptionByAccount::<T>::insert(&who, tier_id, subscription);
+
+      Self::deposit_event(RawEvent::SubscriptionCancelled(who, tier_id));
+      Ok(())
+    }
+  }
+}
+
+impl<T: Config> Module<T> {
+
+    /// Get `SubscriptionTier` by id from the storage or return `TierNotFound` error.
+    pub fn require_tier(tier_id: SubscriptionTierId) -> Result<SubscriptionTier<T>, DispatchError> {
+        Ok(Self::tier_by_id(tier_id).ok_or(Error::<T>::TierNotFound)?)
+    }
+
+    fn ensure_role_in_space(role_id: RoleId, space_id: SpaceId) -> DispatchResult {
+        let role = Roles::<T>::require_role(role_id)?;
+        ensure!(role.space_id == space_id, Error::<T>::RoleNotInSpace);
+        Ok(())
+    }
+
+    /// Find the first block, starting from `at` and within `MaxRenewalDelay` blocks after it,
+    /// that has room for one more renewal.
+    fn find_renewal_block(mut at: T::BlockNumber) -> Result<T::BlockNumber, DispatchError> {
+        let max_renewals = T::MaxRenewalsPerBlock::get() as usize;
+
+        for _ in 0..=T::MaxRenewalDelay::get() {
+            if RenewalsAt::<T>::decode_len(at).unwrap_or_default() < max_renewals {
+                return Ok(at);
+            }
+            at = at.saturating_add(One::one());
+        }
+
+        Err(Error::<T>::RenewalQueueIsFull.into())
+    }
+
+    /// The upper bound of the weight of renewing or expiring a single subscription:
+    /// a search for a renewal block, a currency transfer, and a role revocation
+    /// that looks for other subscriptions of a subscriber in the same space.
+    fn renewal_weight() -> Weight {
+        let renewal_block_probes = T::MaxRenewalDelay::get() as Weight + 1;
+        let space_tiers = T::MaxTiersPerSpace::get() as Weight;
+
+        50_000 + T::DbWeight::get().reads_writes(
+            8 + renewal_block_probes + space_tiers.saturating_mul(2),
+            7,
+        )
+    }
+
+    /// Charge the next period of every subscription that is due at `now`,
+    /// or expire it, if it was cancelled, its tier was disabled, a subscriber cannot pay,
+    /// or there is no room for its next renewal.
+    pub(crate) fn renew_subscriptions(now: T::BlockNumber) -> Weight {
+        let renewals = RenewalsAt::<T>::take(now);
+        let renewals_count = renewals.len() as Weight;
+
+        for (subscriber, tier_id) in renewals {
+            let subscription = match Self::subscription_by_account(&subscriber, tier_id) {
+                Some(subscription) if subscription.renews_at == now => subscription,
+                _ => continue,
+            };
+
+            let tier = match Self::tier_by_id(tier_id) {
+                Some(tier) if !subscription.cancelled && !tier.disabled => tier,
+                tier_opt => {
+                    Self::expire_subscription(subscriber, tier_id, subscription, tier_opt.as_ref());
+                    continue;
+                }
+            };
+
+            let renewed_at = Self::find_renewal_block(now.saturating_add(tier.period))
+                .and_then(|renews_at| Self::charge_subscriber(&subscriber, &tier).map(|_| renews_at));
+
+            if let Ok(renews_at) = renewed_at {
+                RenewalsAt::<T>::append(renews_at, (subscriber.clone(), tier_id));
+                SubscriptionByAccount::<T>::insert(&subscriber, tier_id, Subscription::<T> {
+                    renews_at,
+                    ..subscription
+                });
+
+                Self::deposit_event(RawEvent::SubscriptionRenewed(subscriber, tier_id));
+            } else {
+                Self::expire_subscription(subscriber, tier_id, subscription, Some(&tier));
+            }
+        }
+
+        T::DbWeight::get().reads_writes(1, 1)
+            .saturating_add(renewals_count.saturating_mul(Self::renewal_weight()))
+    }
+
+    fn charge_subscriber(subscriber: &T::AccountId, tier: &SubscriptionTier<T>) -> DispatchResult {
+        let space = Spaces::<T>::require_space(tier.space_id)?;
+        <T as pallet_utils::Config>::Currency::transfer(
+            subscriber,
+            &space.owner,
+            tier.price,
+            ExistenceRequirement::KeepAlive,
+        )
+    }
+
+    fn expire_subscription(
+        subscriber: T::AccountId,
+        tier_id: SubscriptionTierId,
+        subscription: Subscription<T>,
+        tier_opt: Option<&SubscriptionTier<T>>,
+    ) {
+        SubscriptionByAccount::<T>::remove(&subscriber, tier_id);
+
+        if let (Some(role_id), Some(tier)) = (subscription.granted_role_id, tier_opt) {
+            Self::revoke_granted_role(&subscriber, role_id, tier.space_id);
+        }
+
+        Self::deposit_event(RawEvent::SubscriptionExpired(subscriber, tier_id));
+    }
+
+    /// Grant the role of a tier to a subscriber, unless they already have it.
+    /// Return the id of the granted role, if any.
+    fn grant_tier_role(subscriber: &T::AccountId, tier: &SubscriptionTier<T>) -> Option<RoleId> {
+        let role = tier.role_id.and_then(Roles::<T>::role_by_id)?;
+
+        let user = User::Account(subscriber.clone());
+        if Roles::<T>::role_ids_by_user_in_space(&user, tier.space_id).contains(&role.id) {
+            return None;
+        }
+
+        let mut users = BTreeSet::new();
+        users.insert(user);
+        role.grant_to_users(&users);
+
+        Some(role.id)
+    }
+
+    /// Revoke a role granted by an expired subscription. If a subscriber has another subscription
+    /// in the same space with the same role, that subscription takes over the granted role instead.
+    fn revoke_granted_role(subscriber: &T::AccountId, role_id: RoleId, space_id: SpaceId) {
+        let other_subscription = Self::tier_ids_by_space_id(space_id)
+            .into_iter()
+            .filter(|tier_id| Self::tier_by_id(tier_id).and_then(|tier| tier.role_id) == Some(role_id))
+            .find_map(|tier_id| {
+                Self::subscription_by_account(subscriber, tier_id).map(|subscription| (tier_id, subscription))
+            });
+
+        if let Some((tier_id, mut subscription)) = other_subscription {
+            subscription.granted_role_id = Some(role_id);
+            SubscriptionByAccount::<T>::insert(subscriber, tier_id, subscription);
+            return;
+        }
+
+        if let Some(role) = Roles::<T>::role_by_id(role_id) {
+            role.revoke_from_users(vec![User::Account(subscriber.clone())]);
+        }
+    }
+}
@@ -4,7 +4,7 @@ use codec::Codec;
 use sp_std::vec::Vec;
 
 use pallet_spaces::rpc::FlatSpace;
-use pallet_utils::SpaceId;
+use pallet_utils::{HandleValidationError, SpaceId};
 
 sp_api::decl_runtime_apis! {
     pub trait SpacesApi<AccountId, BlockNumber> where
@@ -28,5 +28,9 @@ sp_api::decl_runtime_apis! {
         fn get_space_by_handle(handle: Vec<u8>) -> Option<FlatSpace<AccountId, BlockNumber>>;
 
         fn get_space_id_by_handle(handle: Vec<u8>) -> Option<SpaceId>;
+
+        fn get_handle_validation_error(handle: Vec<u8>) -> Option<HandleValidationError>;
+
+        fn get_space_storage_keys_by_handle(handle: Vec<u8>) -> Vec<Vec<u8>>;
     }
 }
@@ -5,9 +5,10 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
+use sp_core::Bytes;
 
 use pallet_spaces::rpc::FlatSpace;
-use pallet_utils::{SpaceId, rpc::map_rpc_error};
+use pallet_utils::{HandleValidationError, SpaceId, rpc::map_rpc_error};
 pub use spaces_runtime_api::SpacesApi as SpacesRuntimeApi;
 
 #[rpc]
@@ -57,6 +58,20 @@ pub trait SpacesApi<BlockHash, AccountId, BlockNumber> {
         handle: Vec<u8>,
     ) -> Result<Option<FlatSpace<AccountId, BlockNumber>>>;
 
+    #[rpc(name = "spaces_getHandleValidationError")]
+    fn get_handle_validation_error(
+        &self,
+        at: Option<BlockHash>,
+        handle: Vec<u8>,
+    ) -> Result<Option<HandleValidationError>>;
+
+    #[rpc(name = "spaces_getSpaceStorageKeysByHandle")]
+    fn get_space_storage_keys_by_handle(
+        &self,
+        at: Option<BlockHash>,
+        handle: Vec<u8>,
+    ) -> Result<Vec<Bytes>>;
+
     #[rpc(name = "spaces_getPublicSpaceIdsByOwner")]
     fn get_public_space_ids_by_owner(
         &self,
@@ -169,6 +184,32 @@ where
         runtime_api_result.map_err(map_rpc_error)
     }
 
+    fn get_handle_validation_error(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        handle: Vec<u8>,
+    ) -> Result<Option<HandleValidationError>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_handle_validation_error(&at, handle);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_space_storage_keys_by_handle(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        handle: Vec<u8>,
+    ) -> Result<Vec<Bytes>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_space_storage_keys_by_handle(&at, handle);
+        runtime_api_result
+            .map(|keys| keys.into_iter().map(Bytes).collect())
+            .map_err(map_rpc_error)
+    }
+
     fn get_public_space_ids_by_owner(&self, at: Option<<Block as BlockT>::Hash>, owner: AccountId) -> Result<Vec<u64>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
@@ -24,7 +24,7 @@
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, ensure,
+    decl_error, decl_event, decl_module, decl_storage, ensure, transactional,
     dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
     traits::{Get, Currency, ExistenceRequirement, ReservableCurrency},
     weights::Pays,
@@ -34,7 +34,7 @@ use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed, ensure_root};
 
 use df_traits::{
-    SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider,
+    SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider, IsAccountActive,
     moderation::{IsAccountBlocked, IsContentBlocked},
 };
 use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissions, SpacePermissionsContext};
@@ -71,6 +71,20 @@ pub struct Space<T: Config> {
     /// space and its' posts should not be shown.
     pub hidden: bool,
 
+    /// This allows you to override Subsocial's default permissions by enabling or disabling role
+    /// permissions.
+    pub permissions: Option<SpacePermissions>,
+
+    /// Max number of nested comment levels in this space, e.g. `1` for flat comments.
+    /// If `None` or greater than `MaxCommentDepth` of the posts pallet, that limit applies.
+    pub max_comment_depth: Option<u32>,
+}
+
+/// Frequently updated counters of a space, stored apart from `Space`,
+/// so that posting or following does not rewrite the whole space.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct SpaceStats {
+
     /// The total number of posts in a given space.
     pub posts_count: u32,
 
@@ -81,10 +95,6 @@ pub struct Space<T: Config> {
     pub followers_count: u32,
 
     pub score: i32,
-
-    /// This allows you to override Subsocial's default permissions by enabling or disabling role
-    /// permissions.
-    pub permissions: Option<SpacePermissions>,
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
@@ -131,10 +141,19 @@ pub trait Config: system::Config
 
     type AfterSpaceUpdated: AfterSpaceUpdated<Self>;
 
+    /// Hooks that let other pallets react to space lifecycle changes.
+    type SpaceHooks: SpaceHooks<Self>;
+
+    /// Creates a welcome post in a new space, see `create_space_with_welcome_post`.
+    type WelcomePost: CreateWelcomePost<Self>;
+
     type IsAccountBlocked: IsAccountBlocked<Self::AccountId>;
 
     type IsContentBlocked: IsContentBlocked;
 
+    /// Deactivated accounts are not allowed to create new content and to follow others.
+    type IsAccountActive: IsAccountActive<Self::AccountId>;
+
     type HandleDeposit: Get<BalanceOf<Self>>;
 }
 
@@ -158,6 +177,21 @@ decl_error! {
     SpaceIsAtRoot,
     /// New spaces' settings don't differ from the old ones.
     NoUpdatesForSpacesSettings,
+    /// Welcome posts are not supported by this runtime.
+    WelcomePostsNotSupported,
+    /// Space id is out of the range reserved for system spaces.
+    NotASystemSpaceId,
+    /// System space with this id is already created.
+    SystemSpaceAlreadyCreated,
+    /// Reserved space was already used by its genesis owner and cannot become a system space.
+    ReservedSpaceIsInUse,
+    /// Handle of a system space cannot be changed.
+    CannotChangeSystemSpaceHandle,
+    /// Handle and permissions of a space cannot be changed while it is frozen
+    /// for a pending ownership transfer.
+    SpaceIsFrozen,
+    /// Max comment depth of a space should be greater than zero.
+    ZeroMaxCommentDepth,
   }
 }
 
@@ -196,11 +230,30 @@ decl_storage! {
         /// True if `SpaceIdByHandle` storage is already fixed.
         // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
         pub SpaceIdByHandleStorageFixed: bool = false;
+
+        /// Get the counters (posts, followers, etc.) of a space by its' id.
+        pub SpaceStatsById get(fn space_stats_by_id):
+            map hasher(twox_64_concat) SpaceId => SpaceStats;
+
+        /// True if space counters are already moved from `SpaceById` to `SpaceStatsById`.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub SpaceStatsStorageSplit: bool = false;
+
+        /// Whether a given space is a system space created by governance
+        /// within the reserved range of space ids.
+        pub IsSystemSpace get(fn is_system_space):
+            map hasher(twox_64_concat) SpaceId => bool;
+
+        /// Whether permission-sensitive changes (handle, permission overrides, roles)
+        /// are frozen in a given space until its pending ownership transfer is resolved.
+        pub FrozenSpaceIds get(fn is_space_frozen):
+            map hasher(twox_64_concat) SpaceId => bool;
     }
     add_extra_genesis {
       config(endowed_account): T::AccountId;
       build(|_: &Self| {
         SpaceIdByHandleStorageFixed::put(true);
+        SpaceStatsStorageSplit::put(true);
       })
     }
 }
@@ -212,6 +265,12 @@ decl_event!(
         SpaceCreated(AccountId, SpaceId),
         SpaceUpdated(AccountId, SpaceId),
         SpaceDeleted(AccountId, SpaceId),
+        SystemSpaceCreated(AccountId, SpaceId),
+        /// Pallet settings were updated by root.
+        SpacesSettingsUpdated(SpacesSettings),
+        /// A handle was unreserved by root. A space id is `None` if the handle
+        /// did not point to an existing space.
+        HandleForceUnreserved(/* handle */ Vec<u8>, Option<SpaceId>),
     }
 );
 
@@ -234,10 +293,14 @@ decl_module! {
         final_weight = migrations::fix_corrupted_handles_storage::<T>();
       }
 
+      if !SpaceStatsStorageSplit::get() {
+        final_weight = final_weight.saturating_add(migrations::split_space_stats::<T>());
+      }
+
       final_weight
     }
 
-    #[weight = 500_000 + T::DbWeight::get().reads_writes(5, 4)]
+    #[weight = 500_000 + T::DbWeight::get().reads_writes(6, 6)]
     pub fn create_space(
       origin,
       parent_id_opt: Option<SpaceId>,
@@ -246,51 +309,28 @@ decl_module! {
       permissions_opt: Option<SpacePermissions>
     ) -> DispatchResult {
       let owner = ensure_signed(origin)?;
+      Self::do_create_space(owner, parent_id_opt, handle_opt, content, permissions_opt).map(|_| ())
+    }
 
-      Utils::<T>::is_valid_content(content.clone())?;
-
-      if handle_opt.is_some() {
-        Self::ensure_handles_enabled()?;
-      }
-
-      // TODO: add tests for this case
-      if let Some(parent_id) = parent_id_opt {
-        let parent_space = Self::require_space(parent_id)?;
-
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), parent_id), UtilsError::<T>::AccountIsBlocked);
-        ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), parent_id), UtilsError::<T>::ContentIsBlocked);
-
-        Self::ensure_account_has_space_permission(
-          owner.clone(),
-          &parent_space,
-          SpacePermission::CreateSubspaces,
-          Error::<T>::NoPermissionToCreateSubspaces.into()
-        )?;
-      }
-
-      let permissions = permissions_opt.map(|perms| {
-        Permissions::<T>::override_permissions(perms)
-      });
-
-      let space_id = Self::next_space_id();
-      let new_space = &mut Space::new(space_id, parent_id_opt, owner.clone(), content, handle_opt.clone(), permissions);
-
-      if let Some(handle) = handle_opt {
-        new_space.reserve_handle(handle)?;
-      }
-
-      // FIXME: What's about handle reservation if this fails?
-      T::BeforeSpaceCreated::before_space_created(owner.clone(), new_space)?;
-
-      <SpaceById<T>>::insert(space_id, new_space);
-      <SpaceIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(space_id));
-      NextSpaceId::mutate(|n| { *n += 1; });
+    /// Create a space together with a pinned welcome post with `welcome_post_content`,
+    /// so the space never appears empty. Either both are created or none of them.
+    #[weight = 600_000 + T::DbWeight::get().reads_writes(17, 16)]
+    #[transactional]
+    pub fn create_space_with_welcome_post(
+      origin,
+      parent_id_opt: Option<SpaceId>,
+      handle_opt: Option<Vec<u8>>,
+      content: Content,
+      permissions_opt: Option<SpacePermissions>,
+      welcome_post_content: Content
+    ) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
 
-      Self::deposit_event(RawEvent::SpaceCreated(owner, space_id));
-      Ok(())
+      let space_id = Self::do_create_space(owner.clone(), parent_id_opt, handle_opt, content, permissions_opt)?;
+      T::WelcomePost::create_welcome_post(owner, space_id, welcome_post_content)
     }
 
-    #[weight = 500_000 + T::DbWeight::get().reads_writes(3, 3)]
+    #[weight = 500_000 + T::DbWeight::get().reads_writes(4, 5)]
     pub fn update_space(origin, space_id: SpaceId, update: SpaceUpdate) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
@@ -305,6 +345,16 @@ decl_module! {
 
       let mut space = Self::require_space(space_id)?;
 
+      if let Some(handle_opt) = &update.handle {
+        if Self::is_system_space(space_id) {
+          ensure!(*handle_opt == space.handle, Error::<T>::CannotChangeSystemSpaceHandle);
+        }
+      }
+
+      if update.handle.is_some() || update.permissions.is_some() {
+        Self::ensure_space_not_frozen(space_id)?;
+      }
+
       ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
 
       Self::ensure_account_has_space_permission(
@@ -387,6 +437,7 @@ decl_module! {
         space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
 
         <SpaceById<T>>::insert(space_id, space.clone());
+        T::SpaceHooks::on_space_updated(&owner, &space, &old_data);
         T::AfterSpaceUpdated::after_space_updated(owner.clone(), &space, old_data);
 
         Self::deposit_event(RawEvent::SpaceUpdated(owner, space_id));
@@ -394,6 +445,37 @@ decl_module! {
       Ok(())
     }
 
+    /// Set the max number of nested comment levels in a space,
+    /// or `None` to apply `MaxCommentDepth` of the posts pallet.
+    /// A depth is not a part of `SpaceUpdate`, so this change is not recorded in a space history.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(4, 4)]
+    pub fn set_max_comment_depth(origin, space_id: SpaceId, max_comment_depth: Option<u32>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(max_comment_depth != Some(0), Error::<T>::ZeroMaxCommentDepth);
+
+      let mut space = Self::require_space(space_id)?;
+      ensure!(space.max_comment_depth != max_comment_depth, Error::<T>::NoUpdatesForSpace);
+
+      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+      Self::ensure_account_has_space_permission(
+        owner.clone(),
+        &space,
+        SpacePermission::UpdateSpace,
+        Error::<T>::NoPermissionToUpdateSpace.into()
+      )?;
+
+      space.max_comment_depth = max_comment_depth;
+      space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+      <SpaceById<T>>::insert(space_id, space.clone());
+
+      T::SpaceHooks::on_space_updated(&owner, &space, &SpaceUpdate::default());
+
+      Self::deposit_event(RawEvent::SpaceUpdated(owner, space_id));
+      Ok(())
+    }
+
     #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
     pub fn update_settings(origin, new_settings: SpacesSettings) -> DispatchResult {
       ensure_root(origin)?;
@@ -401,8 +483,9 @@ decl_module! {
       let space_settings = Self::settings();
       ensure!(space_settings != new_settings, Error::<T>::NoUpdatesForSpacesSettings);
 
-      PalletSettings::mutate(|settings| *settings = new_settings);
+      PalletSettings::mutate(|settings| *settings = new_settings.clone());
 
+      Self::deposit_event(RawEvent::SpacesSettingsUpdated(new_settings));
       Ok(())
     }
 
@@ -413,21 +496,97 @@ decl_module! {
       let lowercased_handle = handle.to_ascii_lowercase();
 
       if let Some(space_id) = Self::space_id_by_handle(&lowercased_handle) {
+        let mut unreserved_from = None;
+
         if let Ok(mut space) = Self::require_space(space_id) {
-          space.unreserve_handle(lowercased_handle)?;
+          space.unreserve_handle(lowercased_handle.clone())?;
 
           space.handle = None;
           SpaceById::<T>::insert(space_id, space);
+          unreserved_from = Some(space_id);
         } else {
           SpaceIdByHandle::remove(&lowercased_handle);
         }
+
+        Self::deposit_event(RawEvent::HandleForceUnreserved(lowercased_handle, unreserved_from));
+      }
+
+      Ok(Pays::No.into())
+    }
+
+    /// Create a system space (e.g. announcements or governance discussions) with a given id
+    /// from the reserved range and a handle that cannot be changed later by the space owner.
+    /// A handle of a system space is reserved without a deposit even if handles are disabled.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(7, 6)]
+    pub fn force_create_system_space(
+      origin,
+      space_id: SpaceId,
+      owner: T::AccountId,
+      handle: Vec<u8>,
+      content: Content,
+      permissions_opt: Option<SpacePermissions>
+    ) -> DispatchResultWithPostInfo {
+      ensure_root(origin)?;
+
+      ensure!(
+        (FIRST_SPACE_ID..=RESERVED_SPACE_COUNT).contains(&space_id),
+        Error::<T>::NotASystemSpaceId
+      );
+      ensure!(!Self::is_system_space(space_id), Error::<T>::SystemSpaceAlreadyCreated);
+
+      Utils::<T>::is_valid_content(content.clone())?;
+      let handle_in_lowercase = Self::lowercase_and_ensure_unique_handle(handle.clone())?;
+
+      // Only an untouched placeholder, created for a reserved id at genesis, can be replaced,
+      // as a used space may be indexed by other pallets (posts, follows, roles, etc).
+      if let Some(old_space) = Self::space_by_id(space_id) {
+        ensure!(Self::is_reserved_space_placeholder(&old_space), Error::<T>::ReservedSpaceIsInUse);
       }
 
+      let permissions = permissions_opt.map(Permissions::<T>::override_permissions);
+      let new_space = &mut Space::new(space_id, None, owner.clone(), content, Some(handle), permissions);
+
+      T::BeforeSpaceCreated::before_space_created(owner.clone(), new_space)?;
+
+      SpaceIdByHandle::insert(handle_in_lowercase, space_id);
+      <SpaceById<T>>::insert(space_id, &*new_space);
+      <SpaceIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(space_id));
+      IsSystemSpace::insert(space_id, true);
+
+      T::SpaceHooks::on_space_created(&owner, new_space);
+
+      Self::deposit_event(RawEvent::SystemSpaceCreated(owner, space_id));
       Ok(Pays::No.into())
     }
   }
 }
 
+impl SpaceStats {
+    pub fn inc_posts(&mut self) {
+        self.posts_count = self.posts_count.saturating_add(1);
+    }
+
+    pub fn dec_posts(&mut self) {
+        self.posts_count = self.posts_count.saturating_sub(1);
+    }
+
+    pub fn inc_hidden_posts(&mut self) {
+        self.hidden_posts_count = self.hidden_posts_count.saturating_add(1);
+    }
+
+    pub fn dec_hidden_posts(&mut self) {
+        self.hidden_posts_count = self.hidden_posts_count.saturating_sub(1);
+    }
+
+    pub fn inc_followers(&mut self) {
+        self.followers_count = self.followers_count.saturating_add(1);
+    }
+
+    pub fn dec_followers(&mut self) {
+        self.followers_count = self.followers_count.saturating_sub(1);
+    }
+}
+
 impl<T: Config> Space<T> {
     pub fn new(
         id: SpaceId,
@@ -446,11 +605,8 @@ impl<T: Config> Space<T> {
             handle,
             content,
             hidden: false,
-            posts_count: 0,
-            hidden_posts_count: 0,
-            followers_count: 0,
-            score: 0,
             permissions,
+            max_comment_depth: None,
         }
     }
 
@@ -467,30 +623,6 @@ impl<T: Config> Space<T> {
         Ok(())
     }
 
-    pub fn inc_posts(&mut self) {
-        self.posts_count = self.posts_count.saturating_add(1);
-    }
-
-    pub fn dec_posts(&mut self) {
-        self.posts_count = self.posts_count.saturating_sub(1);
-    }
-
-    pub fn inc_hidden_posts(&mut self) {
-        self.hidden_posts_count = self.hidden_posts_count.saturating_add(1);
-    }
-
-    pub fn dec_hidden_posts(&mut self) {
-        self.hidden_posts_count = self.hidden_posts_count.saturating_sub(1);
-    }
-
-    pub fn inc_followers(&mut self) {
-        self.followers_count = self.followers_count.saturating_add(1);
-    }
-
-    pub fn dec_followers(&mut self) {
-        self.followers_count = self.followers_count.saturating_sub(1);
-    }
-
     pub fn try_get_parent(&self) -> Result<SpaceId, DispatchError> {
         self.parent_id.ok_or_else(|| Error::<T>::SpaceIsAtRoot.into())
     }
@@ -538,6 +670,69 @@ impl<T: Config> Module<T> {
         Ok(Self::space_by_id(space_id).ok_or(Error::<T>::SpaceNotFound)?)
     }
 
+    /// Whether a space is a placeholder created for a reserved id at genesis,
+    /// that was never updated, posted to, followed or given roles since then.
+    pub fn is_reserved_space_placeholder(space: &Space<T>) -> bool {
+        space.created.block.is_zero()
+            && space.updated.is_none()
+            && Self::space_stats_by_id(space.id) == SpaceStats::default()
+            && !T::Roles::has_space_roles(space.id)
+    }
+
+    /// Create a new space on behalf of `owner`. Returns an id of the created space.
+    pub fn do_create_space(
+        owner: T::AccountId,
+        parent_id_opt: Option<SpaceId>,
+        handle_opt: Option<Vec<u8>>,
+        content: Content,
+        permissions_opt: Option<SpacePermissions>,
+    ) -> Result<SpaceId, DispatchError> {
+        T::IsAccountActive::ensure_account_is_active(&owner)?;
+        Utils::<T>::is_valid_content(content.clone())?;
+
+        if handle_opt.is_some() {
+            Self::ensure_handles_enabled()?;
+        }
+
+        // TODO: add tests for this case
+        if let Some(parent_id) = parent_id_opt {
+            let parent_space = Self::require_space(parent_id)?;
+
+            ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), parent_id), UtilsError::<T>::AccountIsBlocked);
+            ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), parent_id), UtilsError::<T>::ContentIsBlocked);
+
+            Self::ensure_account_has_space_permission(
+                owner.clone(),
+                &parent_space,
+                SpacePermission::CreateSubspaces,
+                Error::<T>::NoPermissionToCreateSubspaces.into()
+            )?;
+        }
+
+        let permissions = permissions_opt.map(|perms| {
+            Permissions::<T>::override_permissions(perms)
+        });
+
+        let space_id = Self::next_space_id();
+        let new_space = &mut Space::new(space_id, parent_id_opt, owner.clone(), content, handle_opt.clone(), permissions);
+
+        if let Some(handle) = handle_opt {
+            new_space.reserve_handle(handle)?;
+        }
+
+        // FIXME: What's about handle reservation if this fails?
+        T::BeforeSpaceCreated::before_space_created(owner.clone(), new_space)?;
+
+        <SpaceById<T>>::insert(space_id, &*new_space);
+        <SpaceIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(space_id));
+        NextSpaceId::mutate(|n| { *n += 1; });
+
+        T::SpaceHooks::on_space_created(&owner, new_space);
+
+        Self::deposit_event(RawEvent::SpaceCreated(owner, space_id));
+        Ok(space_id)
+    }
+
     pub fn ensure_account_has_space_permission(
         account: T::AccountId,
         space: &Space<T>,
@@ -562,6 +757,11 @@ impl<T: Config> Module<T> {
         )
     }
 
+    pub fn ensure_space_not_frozen(space_id: SpaceId) -> DispatchResult {
+        ensure!(!Self::is_space_frozen(space_id), Error::<T>::SpaceIsFrozen);
+        Ok(())
+    }
+
     pub fn ensure_handles_enabled() -> DispatchResult {
         ensure!(Self::settings().handles_enabled, Error::<T>::HandlesAreDisabled);
         Ok(())
@@ -591,6 +791,12 @@ impl<T: Config> Module<T> {
         })
     }
 
+    /// Update the counters of a space. Unlike `mutate_space_by_id`, it does not read
+    /// nor write the space itself, so a caller should ensure that the space exists.
+    pub fn mutate_space_stats<F: FnOnce(&mut SpaceStats)>(space_id: SpaceId, f: F) {
+        SpaceStatsById::mutate(space_id, f);
+    }
+
     /// Lowercase a handle and ensure that it's unique, i.e. no space reserved this handle yet.
     fn lowercase_and_ensure_unique_handle(handle: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
         let handle_in_lowercase = Utils::<T>::lowercase_and_validate_a_handle(handle)?;
@@ -679,6 +885,7 @@ impl<T: Config> SpaceForRolesProvider for Module<T> {
         Ok(SpaceForRoles {
             owner: space.owner,
             permissions: space.permissions,
+            frozen: Module::<T>::is_space_frozen(id),
         })
     }
 }
@@ -693,7 +900,31 @@ impl<T: Config> BeforeSpaceCreated<T> for () {
     }
 }
 
+pub trait CreateWelcomePost<T: Config> {
+    fn create_welcome_post(owner: T::AccountId, space_id: SpaceId, content: Content) -> DispatchResult;
+}
+
+impl<T: Config> CreateWelcomePost<T> for () {
+    fn create_welcome_post(_owner: T::AccountId, _space_id: SpaceId, _content: Content) -> DispatchResult {
+        Err(Error::<T>::WelcomePostsNotSupported.into())
+    }
+}
+
 #[impl_trait_for_tuples::impl_for_tuples(10)]
 pub trait AfterSpaceUpdated<T: Config> {
     fn after_space_updated(sender: T::AccountId, space: &Space<T>, old_data: SpaceUpdate);
 }
+
+/// Hooks called after a space has been saved, so that other pallets
+/// (roles, treasury, notifications, etc.) can react to it.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait SpaceHooks<T: Config> {
+    /// Called after a space (including a system space) is created.
+    fn on_space_created(_owner: &T::AccountId, _space: &Space<T>) {}
+
+    /// Called after a space is updated. `old_data` holds the previous values of changed fields.
+    fn on_space_updated(_editor: &T::AccountId, _space: &Space<T>, _old_data: &SpaceUpdate) {}
+
+    /// Called after a new owner accepted a space. `space.owner` is already the new owner.
+    fn on_space_ownership_changed(_old_owner: &T::AccountId, _space: &Space<T>) {}
+}
@@ -37,3 +37,56 @@ pub fn fix_corrupted_handles_storage<T: Config>() -> frame_support::weights::Wei
         removed + 1,
     )
 }
+
+/// A space as it was stored before its counters were moved to `SpaceStatsById`.
+#[derive(Encode, Decode)]
+pub struct OldSpace<T: Config> {
+    pub id: SpaceId,
+    pub created: WhoAndWhen<T>,
+    pub updated: Option<WhoAndWhen<T>>,
+    pub owner: T::AccountId,
+    pub parent_id: Option<SpaceId>,
+    pub handle: Option<Vec<u8>>,
+    pub content: Content,
+    pub hidden: bool,
+    pub posts_count: u32,
+    pub hidden_posts_count: u32,
+    pub followers_count: u32,
+    pub score: i32,
+    pub permissions: Option<SpacePermissions>,
+}
+
+pub fn split_space_stats<T: Config>() -> frame_support::weights::Weight {
+    let mut spaces_migrated: u64 = 0;
+
+    SpaceById::<T>::translate::<OldSpace<T>, _>(|space_id, old_space| {
+        spaces_migrated += 1;
+
+        SpaceStatsById::insert(space_id, SpaceStats {
+            posts_count: old_space.posts_count,
+            hidden_posts_count: old_space.hidden_posts_count,
+            followers_count: old_space.followers_count,
+            score: old_space.score,
+        });
+
+        Some(Space {
+            id: old_space.id,
+            created: old_space.created,
+            updated: old_space.updated,
+            owner: old_space.owner,
+            parent_id: old_space.parent_id,
+            handle: old_space.handle,
+            content: old_space.content,
+            hidden: old_space.hidden,
+            permissions: old_space.permissions,
+            max_comment_depth: None,
+        })
+    });
+
+    SpaceStatsStorageSplit::put(true);
+
+    T::DbWeight::get().reads_writes(
+        spaces_migrated,
+        spaces_migrated.saturating_mul(2) + 1,
+    )
+}
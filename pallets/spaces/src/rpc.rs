@@ -1,11 +1,25 @@
 use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_std::prelude::*;
+use sp_std::{vec, prelude::*};
 
-use pallet_utils::{bool_to_option, SpaceId, rpc::{FlatContent, FlatWhoAndWhen, ShouldSkip}};
+use frame_support::storage::StorageMap;
 
-use crate::{Module, Space, Config, FIRST_SPACE_ID};
+use pallet_utils::{
+    bool_to_option, HandleValidationError, Module as Utils, SpaceId,
+    rpc::{FlatContent, FlatWhoAndWhen, ShouldSkip},
+};
+
+use crate::{Module, Space, SpaceById, SpaceIdByHandle, SpaceStats, SpaceStatsById, Config, FIRST_SPACE_ID};
+
+/// A way to refer to a space in RPC: either by its id or by its handle.
+#[derive(Eq, PartialEq, Encode, Decode, Clone)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum SpaceIdOrHandle {
+    Id(SpaceId),
+    Handle(Vec<u8>),
+}
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -37,7 +51,7 @@ pub struct FlatSpace<AccountId, BlockNumber> {
 }
 
 #[cfg(feature = "std")]
-fn bytes_to_string<S>(field: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+pub fn bytes_to_string<S>(field: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
     let field_unwrapped = field.clone().unwrap_or_default();
     // If Bytes slice is invalid, then empty string will be returned
     serializer.serialize_str(
@@ -49,10 +63,13 @@ impl<T: Config> From<Space<T>> for FlatSpace<T::AccountId, T::BlockNumber> {
     fn from(from: Space<T>) -> Self {
         let Space {
             id, created, updated, owner,
-            parent_id, handle, content, hidden, posts_count,
-            hidden_posts_count, followers_count, ..
+            parent_id, handle, content, hidden, ..
         } = from;
 
+        let SpaceStats {
+            posts_count, hidden_posts_count, followers_count, ..
+        } = Module::<T>::space_stats_by_id(id);
+
         Self {
             id,
             who_and_when: (created, updated).into(),
@@ -113,12 +130,40 @@ impl<T: Config> Module<T> {
         Self::space_id_by_handle(handle)
     }
 
+    pub fn get_space_id_by_id_or_handle(space_id_or_handle: SpaceIdOrHandle) -> Option<SpaceId> {
+        match space_id_or_handle {
+            SpaceIdOrHandle::Id(space_id) => Some(space_id),
+            SpaceIdOrHandle::Handle(handle) => Self::space_id_by_handle(handle),
+        }
+    }
+
     pub fn get_space_by_handle(handle: Vec<u8>) -> Option<FlatSpace<T::AccountId, T::BlockNumber>> {
         Self::space_id_by_handle(handle)
             .and_then(|space_id| Self::require_space(space_id).ok())
             .map(|space| space.into())
     }
 
+    /// Get a detailed reason why a handle cannot be used for a space, if any.
+    /// Returns `None` if a handle is valid, though it still may be taken by another space.
+    pub fn get_handle_validation_error(handle: Vec<u8>) -> Option<HandleValidationError> {
+        Utils::<T>::lowercase_and_check_a_handle(handle).err()
+    }
+
+    /// Get storage keys needed to prove a space by its handle, so that a light client
+    /// can request storage proofs and verify them. The first key is of `SpaceIdByHandle`;
+    /// if the handle is registered, keys of `SpaceById` and `SpaceStatsById` follow.
+    pub fn get_space_storage_keys_by_handle(handle: Vec<u8>) -> Vec<Vec<u8>> {
+        let handle_in_lowercase = Utils::<T>::lowercase_handle(handle);
+        let mut keys = vec![SpaceIdByHandle::hashed_key_for(&handle_in_lowercase)];
+
+        if let Some(space_id) = Self::space_id_by_handle(handle_in_lowercase) {
+            keys.push(SpaceById::<T>::hashed_key_for(space_id));
+            keys.push(SpaceStatsById::hashed_key_for(space_id));
+        }
+
+        keys
+    }
+
     fn get_space_ids_by_owner<F: FnMut(&Space<T>) -> bool>(owner: T::AccountId, mut compare_fn: F) -> Vec<SpaceId> {
         Self::space_ids_by_owner(owner)
             .iter()
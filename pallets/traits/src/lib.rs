@@ -1,13 +1,16 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::{
+  dispatch::{DispatchError, DispatchResult},
+  sp_std::vec::Vec,
+};
 
 use pallet_permissions::{
   SpacePermission,
   SpacePermissions,
   SpacePermissionsContext
 };
-use pallet_utils::{SpaceId, User};
+use pallet_utils::{ContentDefaults, PostId, SpaceId, User};
 
 pub mod moderation;
 
@@ -15,6 +18,8 @@ pub mod moderation;
 pub struct SpaceForRoles<AccountId> {
   pub owner: AccountId,
   pub permissions: Option<SpacePermissions>,
+  /// Whether permission-sensitive changes are frozen in a space, e.g. during an ownership transfer.
+  pub frozen: bool,
 }
 
 pub trait SpaceForRolesProvider {
@@ -29,6 +34,63 @@ pub trait SpaceFollowsProvider {
   fn is_space_follower(account: Self::AccountId, space_id: SpaceId) -> bool;
 }
 
+/// Checks whether an account is active, i.e. it is not deactivated by its owner.
+/// Deactivated accounts are not allowed to create new content and to follow others.
+pub trait IsAccountActive<AccountId> {
+  fn is_active_account(account: &AccountId) -> bool;
+
+  fn ensure_account_is_active(account: &AccountId) -> DispatchResult;
+}
+
+impl<AccountId> IsAccountActive<AccountId> for () {
+  fn is_active_account(_account: &AccountId) -> bool {
+    true
+  }
+
+  fn ensure_account_is_active(_account: &AccountId) -> DispatchResult {
+    Ok(())
+  }
+}
+
+pub trait ContentDefaultsProvider<AccountId> {
+  fn content_defaults(account: &AccountId) -> Option<ContentDefaults>;
+}
+
+impl<AccountId> ContentDefaultsProvider<AccountId> for () {
+  fn content_defaults(_account: &AccountId) -> Option<ContentDefaults> {
+    None
+  }
+}
+
+/// Provides threads (root posts) muted by an account, so that they can be moved
+/// to another account together with its profile settings.
+pub trait MutedThreadsProvider<AccountId> {
+  fn muted_thread_ids(account: &AccountId) -> Vec<PostId>;
+
+  /// Replace all threads muted by an account with the given ones.
+  fn set_muted_thread_ids(account: &AccountId, thread_ids: Vec<PostId>) -> DispatchResult;
+}
+
+impl<AccountId> MutedThreadsProvider<AccountId> for () {
+  fn muted_thread_ids(_account: &AccountId) -> Vec<PostId> {
+    Vec::new()
+  }
+
+  fn set_muted_thread_ids(_account: &AccountId, _thread_ids: Vec<PostId>) -> DispatchResult {
+    Ok(())
+  }
+}
+
+pub trait AccountCreatedAt<AccountId, BlockNumber> {
+  fn account_created_at(account: AccountId) -> Option<BlockNumber>;
+}
+
+impl<AccountId, BlockNumber> AccountCreatedAt<AccountId, BlockNumber> for () {
+  fn account_created_at(_account: AccountId) -> Option<BlockNumber> {
+    None
+  }
+}
+
 pub trait PermissionChecker {
   type AccountId;
 
@@ -53,4 +115,7 @@ pub trait PermissionChecker {
       error
     )
   }
+
+  /// Whether any roles were created in a given space.
+  fn has_space_roles(space_id: SpaceId) -> bool;
 }
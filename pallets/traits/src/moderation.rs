@@ -15,6 +15,17 @@ impl<AccountId> IsAccountBlocked<AccountId> for () {
     }
 }
 
+pub trait AccountModerationFlags<AccountId> {
+    /// The number of upheld moderation flags (e.g. blocks) against an account.
+    fn moderation_flags_count(account: AccountId) -> u32;
+}
+
+impl<AccountId> AccountModerationFlags<AccountId> for () {
+    fn moderation_flags_count(_account: AccountId) -> u32 {
+        0
+    }
+}
+
 pub trait IsSpaceBlocked {
     fn is_blocked_space(space_id: SpaceId, scope: SpaceId) -> bool;
     fn is_allowed_space(space_id: SpaceId, scope: SpaceId) -> bool;
@@ -0,0 +1,49 @@
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+pub type DeferredJobId = u64;
+
+/// An identifier of a pallet that pushed a job, e.g. `*b"posts___"`.
+/// It is used to route a job to a handler that knows how to decode its data.
+pub type DeferredJobOwner = [u8; 8];
+
+/// A cleanup job that is too heavy to be done in a single extrinsic,
+/// so it is processed in bounded chunks in `on_idle`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct DeferredJob {
+    pub owner: DeferredJobOwner,
+    /// Job data encoded by its owner. It can be replaced by a handler
+    /// after every processed chunk to keep a cursor of the remaining work.
+    pub data: Vec<u8>,
+}
+
+/// The result of processing a chunk of a deferred job.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct DeferredJobProgress {
+    /// The weight consumed by the processed chunk.
+    pub consumed_weight: Weight,
+    /// `None` if the job is finished, otherwise data of the remaining work.
+    pub remaining_data: Option<Vec<u8>>,
+}
+
+/// A handler of deferred jobs pushed by a pallet.
+pub trait DeferredJobHandler {
+    /// Process a chunk of a job that fits into `weight_limit`.
+    /// Return `None` if a job is not owned by this handler.
+    fn process_job(job: &DeferredJob, weight_limit: Weight) -> Option<DeferredJobProgress>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl DeferredJobHandler for Tuple {
+    fn process_job(job: &DeferredJob, weight_limit: Weight) -> Option<DeferredJobProgress> {
+        for_tuples!( #(
+            if let Some(progress) = Tuple::process_job(job, weight_limit) {
+                return Some(progress);
+            }
+        )* );
+        None
+    }
+}
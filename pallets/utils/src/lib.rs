@@ -9,11 +9,12 @@ use frame_support::{
         Currency, Get,
         Imbalance, OnUnbalanced,
     },
+    weights::Weight,
 };
 use frame_system as system;
 
 #[cfg(feature = "std")]
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::{
     collections::btree_set::BTreeSet,
@@ -29,12 +30,18 @@ mod tests;
 
 pub mod rpc;
 
+pub mod deferred_jobs;
+pub use deferred_jobs::{DeferredJob, DeferredJobHandler, DeferredJobId, DeferredJobOwner, DeferredJobProgress};
+
 pub type SpaceId = u64;
 pub type PostId = u64;
 
 pub const DEFAULT_MIN_HANDLE_LEN: u32 = 5;
 pub const DEFAULT_MAX_HANDLE_LEN: u32 = 50;
 
+pub const MAX_LICENSE_LEN: usize = 64;
+pub const MAX_LANGUAGE_LEN: usize = 16;
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct WhoAndWhen<T: Config> {
@@ -123,6 +130,76 @@ impl Content {
     }
 }
 
+/// A hint about what kind of data a content holds,
+/// so clients can render it without fetching the content first.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Deserialize))]
+pub enum ContentKind {
+    Plain,
+    Markdown,
+    ImageSet,
+    VideoRef,
+}
+
+/// Defaults of an account that are used when creating content if the corresponding
+/// fields are omitted.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ContentDefaults {
+    /// A license identifier, e.g. `CC-BY-4.0`.
+    pub license: Option<Vec<u8>>,
+    /// A language tag, e.g. `en` or `pt-BR`.
+    pub language: Option<Vec<u8>>,
+    /// Whether new root posts should be hidden until an owner unhides them.
+    pub hidden_by_default: bool,
+}
+
+/// A class of a character that is not allowed in a handle.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum HandleCharClass {
+    Whitespace,
+    Punctuation,
+    Control,
+    NonAscii,
+}
+
+impl From<u8> for HandleCharClass {
+    fn from(c: u8) -> Self {
+        if c.is_ascii_whitespace() {
+            Self::Whitespace
+        } else if c.is_ascii_punctuation() {
+            Self::Punctuation
+        } else if c.is_ascii_control() {
+            Self::Control
+        } else {
+            Self::NonAscii
+        }
+    }
+}
+
+/// A detailed reason why a handle is invalid, so clients can point out what exactly is wrong.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum HandleValidationError {
+    /// A handle is shorter than `min_len` bytes.
+    TooShort { len: u32, min_len: u32 },
+    /// A handle is longer than `max_len` bytes.
+    TooLong { len: u32, max_len: u32 },
+    /// A byte at `index` (in a lower-cased handle) is not one of: 0-9, a-z, _.
+    InvalidChar { index: u32, char_class: HandleCharClass },
+}
+
+impl<T: Config> From<HandleValidationError> for Error<T> {
+    fn from(err: HandleValidationError) -> Self {
+        match err {
+            HandleValidationError::TooShort { .. } => Error::<T>::HandleIsTooShort,
+            HandleValidationError::TooLong { .. } => Error::<T>::HandleIsTooLong,
+            HandleValidationError::InvalidChar { .. } => Error::<T>::HandleContainsInvalidChars,
+        }
+    }
+}
+
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as system::Config>::AccountId>>::Balance;
 
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
@@ -140,11 +217,23 @@ pub trait Config: system::Config + pallet_timestamp::Config
 
     /// Max length of a space handle.
     type MaxHandleLen: Get<u32>;
+
+    /// Handlers of cleanup jobs pushed by other pallets into the deferred jobs queue.
+    type DeferredJobHandler: DeferredJobHandler;
 }
 
 decl_storage! {
     trait Store for Module<T: Config> as UtilsModule {
         pub TreasuryAccount get(fn treasury_account) build(|config| config.treasury_account.clone()): T::AccountId;
+
+        /// The id of the next job pushed into the deferred jobs queue.
+        pub NextDeferredJobId get(fn next_deferred_job_id): DeferredJobId;
+
+        /// The id of the oldest job in the deferred jobs queue that is not processed yet.
+        pub DeferredJobsHead get(fn deferred_jobs_head): DeferredJobId;
+
+        pub DeferredJobById get(fn deferred_job_by_id):
+            map hasher(twox_64_concat) DeferredJobId => Option<DeferredJob>;
     }
     add_extra_genesis {
         config(treasury_account): T::AccountId;
@@ -170,6 +259,10 @@ decl_module! {
 
         // Initializing events
         fn deposit_event() = default;
+
+        fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::process_deferred_jobs(remaining_weight)
+        }
     }
 }
 
@@ -195,6 +288,12 @@ decl_error! {
         HandleContainsInvalidChars,
         /// Content type is `None`.
         ContentIsEmpty,
+        /// Account is deactivated by its owner.
+        AccountIsDeactivated,
+        /// License is longer than `MAX_LICENSE_LEN`.
+        LicenseIsTooLong,
+        /// Language is longer than `MAX_LANGUAGE_LEN`.
+        LanguageIsTooLong,
     }
 }
 
@@ -202,6 +301,10 @@ decl_event!(
     pub enum Event<T> where Balance = BalanceOf<T>
     {
 		Deposit(Balance),
+		DeferredJobQueued(DeferredJobId),
+		DeferredJobFinished(DeferredJobId),
+		/// A job was removed from the queue because no handler recognized its owner.
+		DeferredJobDropped(DeferredJobId),
     }
 );
 
@@ -275,27 +378,123 @@ impl<T: Config> Module<T> {
     /// - Check if a handle length fits into min/max length constraints.
     /// - Lowercase a handle.
     /// - Check if a handle contains only valid chars: 0-9, a-z, _.
-    pub fn lowercase_and_validate_a_handle(handle: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+    ///
+    /// Unlike `lowercase_and_validate_a_handle`, it returns a detailed reason of a failure.
+    pub fn lowercase_and_check_a_handle(handle: Vec<u8>) -> Result<Vec<u8>, HandleValidationError> {
+        let len = handle.len() as u32;
 
         // Check if a handle length fits into min/max length constraints:
-        ensure!(handle.len() >= T::MinHandleLen::get() as usize, Error::<T>::HandleIsTooShort);
-        ensure!(handle.len() <= T::MaxHandleLen::get() as usize, Error::<T>::HandleIsTooLong);
+        let min_len = T::MinHandleLen::get();
+        ensure!(len >= min_len, HandleValidationError::TooShort { len, min_len });
+
+        let max_len = T::MaxHandleLen::get();
+        ensure!(len <= max_len, HandleValidationError::TooLong { len, max_len });
 
         let handle_in_lowercase = Self::lowercase_handle(handle);
 
         // Check if a handle contains only valid chars: 0-9, a-z, _.
-        let is_only_valid_chars = handle_in_lowercase.iter().all(|&x| Self::is_valid_handle_char(x));
-        ensure!(is_only_valid_chars, Error::<T>::HandleContainsInvalidChars);
+        if let Some(index) = handle_in_lowercase.iter().position(|&x| !Self::is_valid_handle_char(x)) {
+            return Err(HandleValidationError::InvalidChar {
+                index: index as u32,
+                char_class: handle_in_lowercase[index].into(),
+            });
+        }
 
         // Return a lower-cased version of a handle.
         Ok(handle_in_lowercase)
     }
 
+    /// The same as `lowercase_and_check_a_handle`, but returns a dispatch error.
+    pub fn lowercase_and_validate_a_handle(handle: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+        Self::lowercase_and_check_a_handle(handle).map_err(|err| Error::<T>::from(err).into())
+    }
+
     /// Ensure that a given content is not `None`.
     pub fn ensure_content_is_some(content: &Content) -> DispatchResult {
         ensure!(content.is_some(), Error::<T>::ContentIsEmpty);
         Ok(())
     }
+
+    /// Push a cleanup job into the deferred jobs queue. It will be processed in `on_idle`
+    /// by a `DeferredJobHandler` that recognizes a given `owner`.
+    pub fn push_deferred_job(owner: DeferredJobOwner, data: Vec<u8>) -> DeferredJobId {
+        let job_id = Self::next_deferred_job_id();
+
+        DeferredJobById::insert(job_id, DeferredJob { owner, data });
+        NextDeferredJobId::mutate(|n| { *n += 1; });
+
+        Self::deposit_event(RawEvent::DeferredJobQueued(job_id));
+        job_id
+    }
+
+    /// Process jobs from the head of the deferred jobs queue within `remaining_weight`.
+    /// A job that cannot be finished in the remaining weight stays at the head of the queue
+    /// with updated data, and the processing continues in the next block.
+    pub(crate) fn process_deferred_jobs(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut consumed_weight = db_weight.reads_writes(2, 1);
+        let job_overhead = db_weight.reads_writes(1, 1);
+
+        if remaining_weight < consumed_weight {
+            return 0;
+        }
+
+        let next_job_id = Self::next_deferred_job_id();
+        let mut head = Self::deferred_jobs_head();
+
+        while head < next_job_id {
+            let weight_limit = match remaining_weight.checked_sub(consumed_weight.saturating_add(job_overhead)) {
+                Some(weight_limit) if weight_limit > 0 => weight_limit,
+                _ => break,
+            };
+            consumed_weight = consumed_weight.saturating_add(job_overhead);
+
+            let job = match Self::deferred_job_by_id(head) {
+                Some(job) => job,
+                None => {
+                    head += 1;
+                    continue;
+                }
+            };
+
+            match T::DeferredJobHandler::process_job(&job, weight_limit) {
+                Some(progress) => {
+                    consumed_weight = consumed_weight.saturating_add(progress.consumed_weight);
+
+                    if let Some(data) = progress.remaining_data {
+                        DeferredJobById::insert(head, DeferredJob { data, ..job });
+                        break;
+                    }
+
+                    DeferredJobById::remove(head);
+                    Self::deposit_event(RawEvent::DeferredJobFinished(head));
+                },
+                None => {
+                    DeferredJobById::remove(head);
+                    Self::deposit_event(RawEvent::DeferredJobDropped(head));
+                }
+            }
+
+            head += 1;
+        }
+
+        DeferredJobsHead::put(head);
+        consumed_weight
+    }
+
+    /// Ensure that a license and a language (if any) are not too long.
+    pub fn ensure_license_and_language_are_valid(
+        license: &Option<Vec<u8>>,
+        language: &Option<Vec<u8>>,
+    ) -> DispatchResult {
+        if let Some(license) = license {
+            ensure!(license.len() <= MAX_LICENSE_LEN, Error::<T>::LicenseIsTooLong);
+        }
+        if let Some(language) = language {
+            ensure!(language.len() <= MAX_LANGUAGE_LEN, Error::<T>::LanguageIsTooLong);
+        }
+        Ok(())
+    }
 }
 
 impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Module<T> {
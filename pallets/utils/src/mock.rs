@@ -94,6 +94,29 @@ impl Config for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type DeferredJobHandler = TestJobHandler;
+}
+
+pub(crate) const TEST_JOB_OWNER: DeferredJobOwner = *b"testjobs";
+pub(crate) const WEIGHT_PER_JOB_ITEM: Weight = 10;
+
+/// Processes one byte of job data per `WEIGHT_PER_JOB_ITEM`.
+pub struct TestJobHandler;
+
+impl DeferredJobHandler for TestJobHandler {
+    fn process_job(job: &DeferredJob, weight_limit: Weight) -> Option<DeferredJobProgress> {
+        if job.owner != TEST_JOB_OWNER {
+            return None;
+        }
+
+        let items_to_process = job.data.len().min((weight_limit / WEIGHT_PER_JOB_ITEM) as usize);
+        let remaining_items = job.data[items_to_process..].to_vec();
+
+        Some(DeferredJobProgress {
+            consumed_weight: items_to_process as Weight * WEIGHT_PER_JOB_ITEM,
+            remaining_data: if remaining_items.is_empty() { None } else { Some(remaining_items) },
+        })
+    }
 }
 
 pub type AccountId = u64;
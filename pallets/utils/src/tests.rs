@@ -1,4 +1,4 @@
-use crate::{mock::*, remove_from_vec, log_2};
+use crate::{mock::*, remove_from_vec, log_2, DeferredJob, HandleCharClass, HandleValidationError, Error};
 
 #[test]
 fn log_2_should_work() {
@@ -89,3 +89,112 @@ fn convert_users_vec_to_btree_set_should_work() {
         );
     });
 }
+
+#[test]
+fn lowercase_and_check_a_handle_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_eq!(Utils::lowercase_and_check_a_handle(b"Good_Handle_123".to_vec()), Ok(b"good_handle_123".to_vec()));
+    });
+}
+
+#[test]
+fn lowercase_and_check_a_handle_should_fail_with_len_details() {
+    ExtBuilder::build().execute_with(|| {
+        let min_len = MinHandleLen::get();
+        let max_len = MaxHandleLen::get();
+
+        assert_eq!(
+            Utils::lowercase_and_check_a_handle(vec![b'a'; min_len as usize - 1]),
+            Err(HandleValidationError::TooShort { len: min_len - 1, min_len })
+        );
+
+        assert_eq!(
+            Utils::lowercase_and_check_a_handle(vec![b'a'; max_len as usize + 1]),
+            Err(HandleValidationError::TooLong { len: max_len + 1, max_len })
+        );
+    });
+}
+
+#[test]
+fn lowercase_and_check_a_handle_should_point_to_first_invalid_char() {
+    ExtBuilder::build().execute_with(|| {
+        assert_eq!(
+            Utils::lowercase_and_check_a_handle(b"good handle".to_vec()),
+            Err(HandleValidationError::InvalidChar { index: 4, char_class: HandleCharClass::Whitespace })
+        );
+
+        assert_eq!(
+            Utils::lowercase_and_check_a_handle(b"good-handle.".to_vec()),
+            Err(HandleValidationError::InvalidChar { index: 4, char_class: HandleCharClass::Punctuation })
+        );
+
+        assert_eq!(
+            Utils::lowercase_and_check_a_handle("good_hándle".as_bytes().to_vec()),
+            Err(HandleValidationError::InvalidChar { index: 6, char_class: HandleCharClass::NonAscii })
+        );
+    });
+}
+
+#[test]
+fn lowercase_and_validate_a_handle_should_map_details_to_dispatch_error() {
+    ExtBuilder::build().execute_with(|| {
+        assert_eq!(
+            Utils::lowercase_and_validate_a_handle(b"good handle".to_vec()),
+            Err(Error::<Test>::HandleContainsInvalidChars.into())
+        );
+    });
+}
+
+#[test]
+fn process_deferred_jobs_should_process_job_in_chunks() {
+    ExtBuilder::build().execute_with(|| {
+        let job_id = Utils::push_deferred_job(TEST_JOB_OWNER, vec![0; 5]);
+
+        assert_eq!(Utils::process_deferred_jobs(3 * WEIGHT_PER_JOB_ITEM), 3 * WEIGHT_PER_JOB_ITEM);
+        assert_eq!(
+            Utils::deferred_job_by_id(job_id),
+            Some(DeferredJob { owner: TEST_JOB_OWNER, data: vec![0; 2] })
+        );
+        assert_eq!(Utils::deferred_jobs_head(), job_id);
+
+        assert_eq!(Utils::process_deferred_jobs(100 * WEIGHT_PER_JOB_ITEM), 2 * WEIGHT_PER_JOB_ITEM);
+        assert!(Utils::deferred_job_by_id(job_id).is_none());
+        assert_eq!(Utils::deferred_jobs_head(), job_id + 1);
+    });
+}
+
+#[test]
+fn process_deferred_jobs_should_process_several_jobs_in_one_block() {
+    ExtBuilder::build().execute_with(|| {
+        Utils::push_deferred_job(TEST_JOB_OWNER, vec![0; 2]);
+        Utils::push_deferred_job(TEST_JOB_OWNER, vec![0; 2]);
+
+        assert_eq!(Utils::process_deferred_jobs(100 * WEIGHT_PER_JOB_ITEM), 4 * WEIGHT_PER_JOB_ITEM);
+        assert_eq!(Utils::deferred_jobs_head(), Utils::next_deferred_job_id());
+    });
+}
+
+#[test]
+fn process_deferred_jobs_should_drop_job_of_unknown_owner() {
+    ExtBuilder::build().execute_with(|| {
+        let unknown_job_id = Utils::push_deferred_job(*b"unknown_", vec![0; 2]);
+        let job_id = Utils::push_deferred_job(TEST_JOB_OWNER, vec![0; 1]);
+
+        Utils::process_deferred_jobs(100 * WEIGHT_PER_JOB_ITEM);
+
+        assert!(Utils::deferred_job_by_id(unknown_job_id).is_none());
+        assert!(Utils::deferred_job_by_id(job_id).is_none());
+        assert_eq!(Utils::deferred_jobs_head(), job_id + 1);
+    });
+}
+
+#[test]
+fn process_deferred_jobs_should_do_nothing_without_weight() {
+    ExtBuilder::build().execute_with(|| {
+        let job_id = Utils::push_deferred_job(TEST_JOB_OWNER, vec![0; 1]);
+
+        assert_eq!(Utils::process_deferred_jobs(0), 0);
+        assert!(Utils::deferred_job_by_id(job_id).is_some());
+        assert_eq!(Utils::deferred_jobs_head(), job_id);
+    });
+}
@@ -20,7 +20,7 @@ use sp_runtime::{
     transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-    BlakeTwo256, Block as BlockT, NumberFor, AccountIdLookup
+    BlakeTwo256, Block as BlockT, NumberFor, AccountIdLookup, Verify
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -55,16 +55,18 @@ use frame_system::{
 use pallet_transaction_payment::CurrencyAdapter;
 use static_assertions::const_assert;
 
+use pallet_activity_log::rpc::FlatActivity;
 use pallet_permissions::SpacePermission;
-use pallet_posts::rpc::{FlatPost, FlatPostKind, RepliesByPostId};
+use pallet_posts::rpc::{FlatContentProof, FlatPost, FlatPostKind, FlatSpaceView, RepliesByPostId};
 use pallet_profiles::rpc::FlatSocialAccount;
+use pallet_roles::RoleId;
 use pallet_reactions::{
     ReactionId,
     ReactionKind,
-    rpc::FlatReaction,
+    rpc::{FlatPostView, FlatReaction},
 };
-use pallet_spaces::rpc::FlatSpace;
-use pallet_utils::{SpaceId, PostId, DEFAULT_MIN_HANDLE_LEN, DEFAULT_MAX_HANDLE_LEN};
+use pallet_spaces::rpc::{FlatSpace, SpaceIdOrHandle};
+use pallet_utils::{SpaceId, PostId, HandleValidationError, DEFAULT_MIN_HANDLE_LEN, DEFAULT_MAX_HANDLE_LEN};
 
 pub mod constants;
 use constants::{currency::*, time::*};
@@ -99,10 +101,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("subsocial"),
 	impl_name: create_runtime_str!("dappforce-subsocial"),
 	authoring_version: 0,
-	spec_version: 17,
+	spec_version: 18,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 3,
+	transaction_version: 4,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -335,6 +337,7 @@ impl pallet_utils::Config for Runtime {
 	type Currency = Balances;
 	type MinHandleLen = MinHandleLen;
 	type MaxHandleLen = MaxHandleLen;
+	type DeferredJobHandler = Roles;
 }
 
 use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -345,57 +348,169 @@ impl pallet_permissions::Config for Runtime {
 
 parameter_types! {
   pub const MaxCommentDepth: u32 = 10;
+  pub const MaxMutedThreadsPerAccount: u16 = 1000;
+  pub const MaxScheduledPostsPerBlock: u16 = 20;
+  pub const RecentPostsPeriod: BlockNumber = 1 * HOURS;
+  pub const MaxSpamScore: u32 = 100;
+  pub const PostsByAccountEraLength: BlockNumber = 1 * DAYS;
+  pub const MaxPostsPerHideByAuthor: u32 = 100;
+  pub const MaxPostIdsPerReservation: u32 = 1_000;
+  pub const PostIdReservationDeposit: Balance = 1 * CENTS;
+  pub const PostIdReservationPeriod: BlockNumber = 7 * DAYS;
+  pub const MaxContentProofsPerPost: u32 = 20;
+  pub const MaxSharesToNotifyOnUpdate: u32 = 100;
+  pub const MaxScheduledPostsPerAccount: u16 = 10;
+  pub const MaxPostErasPerAccount: u32 = 365;
 }
 
 impl pallet_posts::Config for Runtime {
 	type Event = Event;
 	type MaxCommentDepth = MaxCommentDepth;
 	type AfterPostUpdated = PostHistory;
+	type PostHooks = (ActivityLog, ProfileBadges);
 	type IsPostBlocked = ()/*Moderation*/;
+	type MaxMutedThreadsPerAccount = MaxMutedThreadsPerAccount;
+	type MaxScheduledPostsPerBlock = MaxScheduledPostsPerBlock;
+	type RecentPostsPeriod = RecentPostsPeriod;
+	type MaxSpamScore = MaxSpamScore;
+	type AccountCreatedAt = Profiles;
+	type AccountModerationFlags = ()/*Moderation*/;
+	type ContentDefaults = Profiles;
+	type PostsByAccountEraLength = PostsByAccountEraLength;
+	type MaxPostsPerHideByAuthor = MaxPostsPerHideByAuthor;
+	type MaxPostIdsPerReservation = MaxPostIdsPerReservation;
+	type PostIdReservationDeposit = PostIdReservationDeposit;
+	type PostIdReservationPeriod = PostIdReservationPeriod;
+	type MaxContentProofsPerPost = MaxContentProofsPerPost;
+	type MaxSharesToNotifyOnUpdate = MaxSharesToNotifyOnUpdate;
+	type MaxScheduledPostsPerAccount = MaxScheduledPostsPerAccount;
+	type MaxPostErasPerAccount = MaxPostErasPerAccount;
 }
 
 impl pallet_post_history::Config for Runtime {}
 
+parameter_types! {
+	pub const MaxAccountsPerBatch: u16 = 20;
+}
+
 impl pallet_profile_follows::Config for Runtime {
 	type Event = Event;
 	type BeforeAccountFollowed = ();
+	type AfterAccountFollowed = ProfileBadges;
 	type BeforeAccountUnfollowed = ();
+	type IsAccountActive = Profiles;
+	type MaxAccountsPerBatch = MaxAccountsPerBatch;
+}
+
+parameter_types! {
+	pub const FollowersBadgeThreshold: u32 = 100;
+	pub const AccountAgeBadgeThreshold: BlockNumber = 365 * DAYS;
 }
 
 impl pallet_profiles::Config for Runtime {
 	type Event = Event;
 	type AfterProfileUpdated = ProfileHistory;
+	type FollowersBadgeThreshold = FollowersBadgeThreshold;
+	type AccountAgeBadgeThreshold = AccountAgeBadgeThreshold;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	type MutedThreads = Posts;
+}
+
+/// Awards profile badges for milestones reached in other pallets.
+pub struct ProfileBadges;
+
+impl pallet_posts::PostHooks<Runtime> for ProfileBadges {
+	fn on_post_created(account: &AccountId, _post: &pallet_posts::Post<Runtime>) {
+		Profiles::on_post_created(account);
+	}
+}
+
+impl pallet_profile_follows::AfterAccountFollowed<Runtime> for ProfileBadges {
+	fn after_account_followed(follower: &AccountId, following: &AccountId) {
+		Profiles::on_account_followed(follower, following);
+	}
 }
 
 impl pallet_profile_history::Config for Runtime {}
 
 impl pallet_reactions::Config for Runtime {
 	type Event = Event;
+	type ReactionHooks = ActivityLog;
 }
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+  pub const MaxUsersPerRoleChange: u16 = 40;
+  pub const MaxRolesPerSpace: u16 = 100;
 }
 
 impl pallet_roles::Config for Runtime {
 	type Event = Event;
 	type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+	type MaxUsersPerRoleChange = MaxUsersPerRoleChange;
+	type MaxRolesPerSpace = MaxRolesPerSpace;
 	type Spaces = Spaces;
 	type SpaceFollows = SpaceFollows;
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
 }
 
+parameter_types! {
+	pub const MaxSpacesPerBatch: u16 = 20;
+}
+
 impl pallet_space_follows::Config for Runtime {
 	type Event = Event;
 	type BeforeSpaceFollowed = ();
 	type BeforeSpaceUnfollowed = ();
+	type MaxSpacesPerBatch = MaxSpacesPerBatch;
 }
 
 impl pallet_space_ownership::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MaxCategories: u32 = 500;
+	pub const MaxCategoryDepth: u32 = 3;
+	pub const MaxSpacesPerCategory: u32 = 1_000;
+}
+
+parameter_types! {
+	pub const MaxActivities: u32 = 1_000;
+}
+
+impl pallet_activity_log::Config for Runtime {
+	type MaxActivities = MaxActivities;
+}
+
+impl pallet_onboarding::Config for Runtime {
+	type Event = Event;
+}
+
+impl pallet_space_categories::Config for Runtime {
+	type Event = Event;
+	type MaxCategories = MaxCategories;
+	type MaxCategoryDepth = MaxCategoryDepth;
+	type MaxSpacesPerCategory = MaxSpacesPerCategory;
+}
+
+parameter_types! {
+	pub const MaxTiersPerSpace: u32 = 10;
+	pub const MinSubscriptionPeriod: BlockNumber = 1 * DAYS;
+	pub const MaxRenewalsPerBlock: u32 = 100;
+	pub const MaxRenewalDelay: u32 = 10;
+}
+
+impl pallet_space_subscriptions::Config for Runtime {
+	type Event = Event;
+	type MaxTiersPerSpace = MaxTiersPerSpace;
+	type MinSubscriptionPeriod = MinSubscriptionPeriod;
+	type MaxRenewalsPerBlock = MaxRenewalsPerBlock;
+	type MaxRenewalDelay = MaxRenewalDelay;
+}
+
 // TODO: do not change until we save a handle deposit into a storage per every handle.
 parameter_types! {
 	pub HandleDeposit: Balance = 5 * DOLLARS;
@@ -408,8 +523,11 @@ impl pallet_spaces::Config for Runtime {
 	type SpaceFollows = SpaceFollows;
 	type BeforeSpaceCreated = SpaceFollows;
 	type AfterSpaceUpdated = SpaceHistory;
+	type SpaceHooks = ActivityLog;
+	type WelcomePost = Posts;
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
+	type IsAccountActive = Profiles;
 	type HandleDeposit = HandleDeposit;
 }
 
@@ -441,11 +559,16 @@ impl Contains<Call> for BaseFilter {
 
 /*parameter_types! {
     pub const DefaultAutoblockThreshold: u16 = 20;
+    pub const MaxBlocksExpiringAtBlock: u32 = 100;
+    pub const MaxPendingAppealsPerSpace: u32 = 1_000;
 }
 
 impl pallet_moderation::Config for Runtime {
     type Event = Event;
     type DefaultAutoblockThreshold = DefaultAutoblockThreshold;
+    type OnEntityStatusChanged = ();
+    type MaxBlocksExpiringAtBlock = MaxBlocksExpiringAtBlock;
+    type MaxPendingAppealsPerSpace = MaxPendingAppealsPerSpace;
 }*/
 
 impl pallet_faucets::Config for Runtime {
@@ -491,6 +614,13 @@ construct_runtime!(
 		Faucets: pallet_faucets::{Pallet, Call, Storage, Event<T>},
 		DotsamaClaims: pallet_dotsama_claims::{Pallet, Call, Storage, Event<T>},
 		// Moderation: pallet_moderation::{Pallet, Call, Storage, Event<T>},
+
+		// New pallets are appended here, so that indices of the existing pallets are kept.
+
+		ActivityLog: pallet_activity_log::{Pallet, Storage},
+		Onboarding: pallet_onboarding::{Pallet, Call, Event<T>},
+		SpaceCategories: pallet_space_categories::{Pallet, Call, Storage, Event<T>},
+		SpaceSubscriptions: pallet_space_subscriptions::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -512,6 +642,7 @@ pub type SignedExtra = (
     frame_system::CheckWeight<Runtime>,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
     pallet_dotsama_claims::EnsureAllowedToClaimTokens<Runtime>,
+    pallet_posts::CheckSpamScore<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -724,9 +855,12 @@ impl_runtime_apis! {
 			// let storage_info = AllPalletsWithSystem::storage_info();
             let mut storage_info = DotsamaClaims::storage_info();
             storage_info.append(&mut Faucets::storage_info());
+            storage_info.append(&mut ActivityLog::storage_info());
             storage_info.append(&mut Utils::storage_info());
             storage_info.append(&mut Spaces::storage_info());
             storage_info.append(&mut SpaceOwnership::storage_info());
+            storage_info.append(&mut SpaceSubscriptions::storage_info());
+            storage_info.append(&mut SpaceCategories::storage_info());
             storage_info.append(&mut SpaceHistory::storage_info());
             storage_info.append(&mut SpaceFollows::storage_info());
             storage_info.append(&mut Roles::storage_info());
@@ -821,6 +955,14 @@ impl_runtime_apis! {
         	Spaces::get_space_by_handle(handle)
         }
 
+        fn get_handle_validation_error(handle: Vec<u8>) -> Option<HandleValidationError> {
+        	Spaces::get_handle_validation_error(handle)
+        }
+
+        fn get_space_storage_keys_by_handle(handle: Vec<u8>) -> Vec<Vec<u8>> {
+        	Spaces::get_space_storage_keys_by_handle(handle)
+        }
+
         fn get_public_space_ids_by_owner(owner: AccountId) -> Vec<SpaceId> {
         	Spaces::get_public_space_ids_by_owner(owner)
         }
@@ -883,6 +1025,30 @@ impl_runtime_apis! {
 		fn get_feed(account: AccountId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
 			Posts::get_feed(account, offset, limit)
 		}
+
+		fn get_post_ids_by_account_in_block_range(
+			account: AccountId,
+			from_block: BlockNumber,
+			to_block: BlockNumber,
+			limit: u16
+		) -> Vec<PostId> {
+			Posts::get_post_ids_by_account_in_block_range(account, from_block, to_block, limit)
+		}
+
+		fn get_post_storage_key(post_id: PostId) -> Vec<u8> {
+			Posts::get_post_storage_key(post_id)
+		}
+
+		fn get_space_view(
+			space_id_or_handle: SpaceIdOrHandle,
+			viewer: Option<AccountId>,
+		) -> Option<FlatSpaceView<AccountId, BlockNumber>> {
+			Posts::get_space_view(space_id_or_handle, viewer)
+		}
+
+		fn get_content_proof(post_id: PostId, at_block: BlockNumber) -> Option<FlatContentProof<BlockNumber>> {
+			Posts::get_content_proof(post_id, at_block)
+		}
     }
 
 	impl profile_follows_runtime_api::ProfileFollowsApi<Block, AccountId> for Runtime
@@ -899,6 +1065,17 @@ impl_runtime_apis! {
         ) -> Vec<FlatSocialAccount<AccountId, BlockNumber>> {
         	Profiles::get_social_accounts_by_ids(account_ids)
         }
+
+		fn get_social_account_storage_key(account: AccountId) -> Vec<u8> {
+			Profiles::get_social_account_storage_key(account)
+		}
+	}
+
+    impl activity_log_runtime_api::ActivityLogApi<Block, AccountId, BlockNumber> for Runtime
+    {
+		fn get_recent_activities(limit: u32) -> Vec<FlatActivity<AccountId, BlockNumber>> {
+			ActivityLog::get_recent_activities(limit)
+		}
 	}
 
     impl reactions_runtime_api::ReactionsApi<Block, AccountId, BlockNumber> for Runtime
@@ -921,6 +1098,10 @@ impl_runtime_apis! {
 		) -> BTreeMap<PostId, ReactionKind> {
 			Reactions::get_reaction_kinds_by_post_ids_and_reactor(post_ids, reactor)
 		}
+
+		fn get_post_view(post_id: PostId, viewer: Option<AccountId>) -> Option<FlatPostView<AccountId, BlockNumber>> {
+			Reactions::get_post_view(post_id, viewer)
+		}
     }
 
 	impl roles_runtime_api::RolesApi<Block, AccountId> for Runtime
@@ -939,5 +1120,9 @@ impl_runtime_apis! {
         fn get_space_ids_for_account_with_any_role(account_id: AccountId) -> Vec<SpaceId> {
 			Roles::get_space_ids_for_account_with_any_role(account_id)
         }
+
+		fn get_role_ids_by_space_permission(space_id: SpaceId, permission: SpacePermission) -> Vec<RoleId> {
+			Roles::get_role_ids_by_space_permission(space_id, permission)
+		}
 	}
 }